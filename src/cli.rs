@@ -1,10 +1,67 @@
 use crate::constants::{NAME, VERSION};
-use clap::App;
+use clap::{App, AppSettings, Arg, SubCommand};
 
 pub(crate) fn build_cli() -> App<'static, 'static> {
 	App::new(NAME)
 		.version(VERSION)
 		.about("Full feature terminal based sequence editor for git interactive rebase.")
 		.author("Tim Oram <dev@mitmaro.ca>")
-		.args_from_usage("<rebase-todo-filepath> 'The path to the git rebase todo file'")
+		.args_from_usage(
+			"<rebase-todo-filepath> 'The path to the git rebase todo file'
+			--script=[script] 'Apply a script of commands to the todo file and write it, without opening the editor'
+			--import-json=[import-json] 'Replace the todo with the lines of an exported JSON file and write it, without opening the editor'
+			--export-json=[export-json] 'Write the todo as JSON to a file, or to standard output for -, without opening the editor'
+			--record=[record] 'Record the window size and each input to a file, for use with --replay'
+			--replay=[replay] 'Replay the inputs recorded to a file with --record, before reading from the terminal'",
+		)
+		.arg(
+			Arg::with_name("prepared-todo")
+				.long("prepared-todo")
+				.takes_value(true)
+				.hidden(true),
+		)
+		.setting(AppSettings::SubcommandsNegateReqs)
+		.subcommand(
+			SubCommand::with_name("install")
+				.about("Configure git to use this tool as the sequence editor")
+				.arg(Arg::with_name("global").long("global").help("Write to the global git config, the default"))
+				.arg(
+					Arg::with_name("local")
+						.long("local")
+						.conflicts_with("global")
+						.help("Write to the git config of the current repository"),
+				),
+		)
+		.subcommand(
+			SubCommand::with_name("uninstall")
+				.about("Remove this tool as the sequence editor from the git config")
+				.arg(Arg::with_name("global").long("global").help("Remove from the global git config, the default"))
+				.arg(
+					Arg::with_name("local")
+						.long("local")
+						.conflicts_with("global")
+						.help("Remove from the git config of the current repository"),
+				),
+		)
+		.subcommand(
+			SubCommand::with_name("rebase")
+				.about("Edit the todo for the commits after an upstream, then start git rebase with the edited todo")
+				.arg(
+					Arg::with_name("upstream")
+						.index(1)
+						.required_unless("range")
+						.conflicts_with("range")
+						.help("The upstream to rebase the current branch onto"),
+				)
+				.arg(
+					Arg::with_name("range")
+						.long("range")
+						.takes_value(true)
+						.help("The range of commits to rebase, as <upstream>..<branch>"),
+				),
+		)
+		.subcommand(
+			SubCommand::with_name("doctor")
+				.about("Report the effective configuration, terminal capabilities and any problems found"),
+		)
 }
@@ -5,7 +5,7 @@ mod utils;
 
 use crate::commit::file_stat::FileStat;
 use crate::commit::user::User;
-use crate::commit::utils::load_commit_state;
+use crate::commit::utils::{load_commit_state, resolve_commit};
 use chrono::{DateTime, Local};
 
 #[derive(Debug, PartialEq)]
@@ -42,6 +42,12 @@ impl Commit {
 		load_commit_state(hash).map_err(|e| String::from(e.message()))
 	}
 
+	/// Resolves a revision to the hash and subject of a commit, none when it is the commit of the current hash
+	pub(crate) fn resolve_revision(revision: &str, current_hash: &str) -> Result<Option<(String, String)>, String> {
+		resolve_commit(revision.trim(), current_hash)
+			.map_err(|e| format!("Invalid commit, {}\nReason: {}", revision.trim(), e.message()))
+	}
+
 	pub(crate) fn get_author(&self) -> &User {
 		&self.author
 	}
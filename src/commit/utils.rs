@@ -78,12 +78,33 @@ pub(super) fn load_commit_state(hash: &str) -> Result<Commit, Error> {
 	Ok(Commit::new(full_hash, author, committer, date, file_stats, body))
 }
 
+/// Resolve a revision to the hash and summary of its commit, or none when it is the commit of the current hash. The
+/// hash is abbreviated when the current hash is abbreviated.
+pub(super) fn resolve_commit(revision: &str, current_hash: &str) -> Result<Option<(String, String)>, Error> {
+	let repo = Repository::open_from_env()?;
+	let commit = repo.revparse_single(revision)?.peel_to_commit()?;
+	// the same commit can be written with a different abbreviation, so the resolved ids are compared
+	if let Ok(current) = repo.revparse_single(current_hash).and_then(|object| object.peel_to_commit()) {
+		if current.id() == commit.id() {
+			return Ok(None);
+		}
+	}
+	// the todo only has full hashes when git was asked for them, so the new hash keeps that form
+	let hash = if current_hash.len() < 40 {
+		String::from(commit.as_object().short_id()?.as_str().unwrap_or(""))
+	}
+	else {
+		commit.id().to_string()
+	};
+	Ok(Some((hash, String::from(commit.summary().unwrap_or("")))))
+}
+
 #[cfg(test)]
 mod tests {
 	// some of this file is difficult to test because it would require a non-standard git repo, so
 	// we test what is possible
 	use crate::commit::status::Status;
-	use crate::commit::utils::load_commit_state;
+	use crate::commit::utils::{load_commit_state, resolve_commit};
 	use serial_test::serial;
 	use std::env::set_var;
 	use std::path::Path;
@@ -210,4 +231,54 @@ mod tests {
 		assert_eq!(file_stat.get_from_name(), "d");
 		assert_eq!(file_stat.get_to_name(), "g");
 	}
+
+	#[test]
+	#[serial]
+	fn commit_utils_resolve_commit_full_hash() {
+		set_git_dir("simple");
+		let (hash, summary) = resolve_commit("e10b3f47", "18d82dcc4c36cade807d7cf79700b6bbad8080b9")
+			.unwrap()
+			.unwrap();
+		assert_eq!(hash.len(), 40);
+		assert!(hash.starts_with("e10b3f47"));
+		assert_eq!(summary, "First commit");
+	}
+
+	#[test]
+	#[serial]
+	fn commit_utils_resolve_commit_abbreviated_hash() {
+		set_git_dir("simple");
+		let (hash, summary) = resolve_commit("18d82dcc4c36cade807d7cf79700b6bbad8080b9", "e10b3f4")
+			.unwrap()
+			.unwrap();
+		assert!(hash.len() < 40);
+		assert!("18d82dcc4c36cade807d7cf79700b6bbad8080b9".starts_with(hash.as_str()));
+		assert_eq!(summary, "Empty commit title");
+	}
+
+	#[test]
+	#[serial]
+	fn commit_utils_resolve_commit_invalid_revision() {
+		set_git_dir("simple");
+		assert!(resolve_commit("not-a-revision", "e10b3f4").is_err());
+	}
+
+	#[test]
+	#[serial]
+	fn commit_utils_resolve_commit_same_commit() {
+		set_git_dir("simple");
+		assert_eq!(resolve_commit("e10b3f474644", "e10b3f4").unwrap(), None);
+		assert_eq!(
+			resolve_commit("e10b3f4", "e10b3f474644d8566947104c07acba4d6f4f4f9f").unwrap(),
+			None
+		);
+	}
+
+	#[test]
+	#[serial]
+	fn commit_utils_resolve_commit_unknown_current_hash() {
+		set_git_dir("simple");
+		let (hash, _) = resolve_commit("e10b3f47", "aaaaaaa").unwrap().unwrap();
+		assert!("e10b3f474644d8566947104c07acba4d6f4f4f9f".starts_with(hash.as_str()));
+	}
 }
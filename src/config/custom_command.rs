@@ -0,0 +1,7 @@
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct CustomCommand {
+	pub(crate) name: String,
+	pub(crate) key: String,
+	pub(crate) command: String,
+	pub(crate) show_output: bool,
+}
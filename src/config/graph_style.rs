@@ -0,0 +1,50 @@
+use std::convert::TryFrom;
+
+/// The characters used to draw the commit graph column of the list, or none to hide the column
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum GraphStyle {
+	Ascii,
+	None,
+	Unicode,
+}
+
+impl TryFrom<&str> for GraphStyle {
+	type Error = String;
+
+	fn try_from(s: &str) -> Result<Self, Self::Error> {
+		match s {
+			"ascii" => Ok(GraphStyle::Ascii),
+			"none" => Ok(GraphStyle::None),
+			"unicode" => Ok(GraphStyle::Unicode),
+			_ => {
+				Err(format!(
+					"Error reading git config: interactive-rebase-tool.graph, invalid value \"{}\", expected none, \
+					 ascii or unicode",
+					s
+				))
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::GraphStyle;
+	use std::convert::TryFrom;
+
+	#[test]
+	fn graph_style_try_from() {
+		assert_eq!(GraphStyle::try_from("none").unwrap(), GraphStyle::None);
+		assert_eq!(GraphStyle::try_from("ascii").unwrap(), GraphStyle::Ascii);
+		assert_eq!(GraphStyle::try_from("unicode").unwrap(), GraphStyle::Unicode);
+	}
+
+	#[test]
+	fn graph_style_try_from_invalid() {
+		assert_eq!(
+			GraphStyle::try_from("fancy").unwrap_err(),
+			"Error reading git config: interactive-rebase-tool.graph, invalid value \"fancy\", expected none, ascii or \
+			 unicode"
+		);
+	}
+}
@@ -0,0 +1,143 @@
+/// A column of the list that can be used in the list format
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum ListColumn {
+	Action,
+	Author,
+	AuthorInitials,
+	Date,
+	Files,
+	Hash,
+	RelativeDate,
+	Stats,
+	Subject,
+}
+
+impl ListColumn {
+	fn from_name(name: &str) -> Option<Self> {
+		match name {
+			"action" => Some(ListColumn::Action),
+			"author" => Some(ListColumn::Author),
+			"author_initials" => Some(ListColumn::AuthorInitials),
+			"date" => Some(ListColumn::Date),
+			"files" => Some(ListColumn::Files),
+			"hash" => Some(ListColumn::Hash),
+			"reldate" => Some(ListColumn::RelativeDate),
+			"stats" => Some(ListColumn::Stats),
+			"subject" => Some(ListColumn::Subject),
+			_ => None,
+		}
+	}
+
+	/// Checks if the column is read from the repository, these columns are hidden when there is not enough room
+	pub(crate) fn is_optional(self) -> bool {
+		self != ListColumn::Action && self != ListColumn::Hash && self != ListColumn::Subject
+	}
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum ListFormatItem {
+	Column(ListColumn),
+	Text(String),
+}
+
+fn format_error(format: &str, reason: &str) -> String {
+	format!(
+		"Error reading git config: interactive-rebase-tool.listFormat, invalid value \"{}\", {}",
+		format, reason
+	)
+}
+
+/// Parses a list format, such as `{action} {hash} {subject}`, into the columns and the text between them
+pub(crate) fn parse_list_format(format: &str) -> Result<Vec<ListFormatItem>, String> {
+	let mut items = vec![];
+	let mut rest = format;
+	while !rest.is_empty() {
+		match rest.find('{') {
+			Some(0) => {
+				let end = rest
+					.find('}')
+					.ok_or_else(|| format_error(format, "missing closing brace"))?;
+				let name = &rest[1..end];
+				let column = ListColumn::from_name(name)
+					.ok_or_else(|| format_error(format, format!("unknown column \"{}\"", name).as_str()))?;
+				items.push(ListFormatItem::Column(column));
+				rest = &rest[end + 1..];
+			},
+			Some(start) => {
+				items.push(ListFormatItem::Text(String::from(&rest[..start])));
+				rest = &rest[start..];
+			},
+			None => {
+				items.push(ListFormatItem::Text(String::from(rest)));
+				rest = "";
+			},
+		}
+	}
+	if !items.contains(&ListFormatItem::Column(ListColumn::Action)) {
+		return Err(format_error(format, "the action column is required"));
+	}
+	Ok(items)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_list_format, ListColumn, ListFormatItem};
+
+	#[test]
+	fn list_format_parse_default() {
+		assert_eq!(parse_list_format("{action} {hash} {subject}").unwrap(), vec![
+			ListFormatItem::Column(ListColumn::Action),
+			ListFormatItem::Text(String::from(" ")),
+			ListFormatItem::Column(ListColumn::Hash),
+			ListFormatItem::Text(String::from(" ")),
+			ListFormatItem::Column(ListColumn::Subject),
+		]);
+	}
+
+	#[test]
+	fn list_format_parse_all_columns() {
+		assert_eq!(
+			parse_list_format("{action}{hash}{author}{author_initials}{date}{reldate}{files}{stats}| {subject}")
+				.unwrap(),
+			vec![
+				ListFormatItem::Column(ListColumn::Action),
+				ListFormatItem::Column(ListColumn::Hash),
+				ListFormatItem::Column(ListColumn::Author),
+				ListFormatItem::Column(ListColumn::AuthorInitials),
+				ListFormatItem::Column(ListColumn::Date),
+				ListFormatItem::Column(ListColumn::RelativeDate),
+				ListFormatItem::Column(ListColumn::Files),
+				ListFormatItem::Column(ListColumn::Stats),
+				ListFormatItem::Text(String::from("| ")),
+				ListFormatItem::Column(ListColumn::Subject),
+			]
+		);
+	}
+
+	#[test]
+	fn list_format_parse_unknown_column() {
+		assert_eq!(
+			parse_list_format("{action} {branch}").unwrap_err(),
+			"Error reading git config: interactive-rebase-tool.listFormat, invalid value \"{action} {branch}\", \
+			 unknown column \"branch\""
+		);
+	}
+
+	#[test]
+	fn list_format_parse_missing_brace() {
+		assert_eq!(
+			parse_list_format("{action} {hash").unwrap_err(),
+			"Error reading git config: interactive-rebase-tool.listFormat, invalid value \"{action} {hash\", missing \
+			 closing brace"
+		);
+	}
+
+	#[test]
+	fn list_format_parse_missing_action() {
+		assert_eq!(
+			parse_list_format("{hash} {subject}").unwrap_err(),
+			"Error reading git config: interactive-rebase-tool.listFormat, invalid value \"{hash} {subject}\", the \
+			 action column is required"
+		);
+	}
+}
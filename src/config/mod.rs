@@ -1,16 +1,36 @@
+pub(crate) mod custom_command;
+pub(crate) mod graph_style;
+pub(crate) mod list_format;
+pub(crate) mod split_layout;
 pub(crate) mod theme;
 mod utils;
 
+use crate::config::custom_command::CustomCommand;
+use crate::config::graph_style::GraphStyle;
+use crate::config::list_format::{parse_list_format, ListFormatItem};
+use crate::config::split_layout::SplitLayout;
 use crate::config::theme::Theme;
-use crate::config::utils::{editor_from_env, get_bool, get_color, get_input, get_string, open_git_config};
-use crate::display::color::Color;
+use crate::config::utils::{
+	editor_from_env,
+	get_bool,
+	get_custom_commands,
+	get_input,
+	get_key_binding_conflicts,
+	get_string,
+	get_theme,
+	open_git_config,
+	KeyBindingConflict,
+};
+use std::convert::TryFrom;
 
 #[derive(Clone, Debug)]
 pub(crate) struct Config {
 	pub(crate) theme: Theme,
 	pub(crate) auto_select_next: bool,
 	pub(crate) comment_char: String,
+	pub(crate) custom_commands: Vec<CustomCommand>,
 	pub(crate) editor: String,
+	pub(crate) graph: GraphStyle,
 	pub(crate) input_abort: String,
 	pub(crate) input_action_break: String,
 	pub(crate) input_action_drop: String,
@@ -19,9 +39,12 @@ pub(crate) struct Config {
 	pub(crate) input_action_pick: String,
 	pub(crate) input_action_reword: String,
 	pub(crate) input_action_squash: String,
+	pub(crate) input_autosquash: String,
 	pub(crate) input_confirm_no: String,
 	pub(crate) input_confirm_yes: String,
 	pub(crate) input_edit: String,
+	pub(crate) input_edit_hash: String,
+	pub(crate) input_export_json: String,
 	pub(crate) input_force_abort: String,
 	pub(crate) input_force_rebase: String,
 	pub(crate) input_help: String,
@@ -36,76 +59,199 @@ pub(crate) struct Config {
 	pub(crate) input_open_in_external_editor: String,
 	pub(crate) input_rebase: String,
 	pub(crate) input_show_commit: String,
+	pub(crate) input_toggle_collapse: String,
+	pub(crate) input_toggle_focus: String,
 	pub(crate) input_toggle_visual_mode: String,
+	pub(crate) list_format: Vec<ListFormatItem>,
+	pub(crate) mouse: bool,
+	pub(crate) split_layout: SplitLayout,
+	pub(crate) status_bar: bool,
 }
 
 impl Config {
 	pub(crate) fn new() -> Result<Self, String> {
-		let git_config = open_git_config()?;
-		Ok(Config {
-			theme: Theme {
-				color_foreground: get_color(&git_config, "interactive-rebase-tool.foregroundColor", Color::Default)?,
-				color_background: get_color(&git_config, "interactive-rebase-tool.backgroundColor", Color::Default)?,
-				color_selected_background: get_color(
-					&git_config,
-					"interactive-rebase-tool.selectedBackgroundColor",
-					Color::Index(237),
-				)?,
-				color_indicator: get_color(&git_config, "interactive-rebase-tool.indicatorColor", Color::LightCyan)?,
-				color_action_break: get_color(&git_config, "interactive-rebase-tool.breakColor", Color::LightWhite)?,
-				color_action_drop: get_color(&git_config, "interactive-rebase-tool.dropColor", Color::LightRed)?,
-				color_action_edit: get_color(&git_config, "interactive-rebase-tool.editColor", Color::LightBlue)?,
-				color_action_exec: get_color(&git_config, "interactive-rebase-tool.execColor", Color::LightWhite)?,
-				color_action_fixup: get_color(&git_config, "interactive-rebase-tool.fixupColor", Color::LightMagenta)?,
-				color_action_pick: get_color(&git_config, "interactive-rebase-tool.pickColor", Color::LightGreen)?,
-				color_action_reword: get_color(&git_config, "interactive-rebase-tool.rewordColor", Color::LightYellow)?,
-				color_action_squash: get_color(&git_config, "interactive-rebase-tool.squashColor", Color::LightCyan)?,
-				color_diff_add: get_color(&git_config, "interactive-rebase-tool.diffAddColor", Color::LightGreen)?,
-				color_diff_change: get_color(
-					&git_config,
-					"interactive-rebase-tool.diffChangeColor",
-					Color::LightYellow,
-				)?,
-				color_diff_remove: get_color(&git_config, "interactive-rebase-tool.diffRemoveColor", Color::LightRed)?,
-				character_vertical_spacing: get_string(
-					&git_config,
-					"interactive-rebase-tool.verticalSpacingCharacter",
-					"~",
-				)?,
-			},
-			auto_select_next: get_bool(&git_config, "interactive-rebase-tool.autoSelectNext", false)?,
-			comment_char: get_string(&git_config, "core.commentChar", "#")?,
-			editor: get_string(&git_config, "core.editor", editor_from_env().as_str())?,
-			input_abort: get_input(&git_config, "interactive-rebase-tool.inputAbort", "q")?,
-			input_action_break: get_input(&git_config, "interactive-rebase-tool.inputActionBreak", "b")?,
-			input_action_drop: get_input(&git_config, "interactive-rebase-tool.inputActionDrop", "d")?,
-			input_action_edit: get_input(&git_config, "interactive-rebase-tool.inputActionEdit", "e")?,
-			input_action_fixup: get_input(&git_config, "interactive-rebase-tool.inputActionFixup", "f")?,
-			input_action_pick: get_input(&git_config, "interactive-rebase-tool.inputActionPick", "p")?,
-			input_action_reword: get_input(&git_config, "interactive-rebase-tool.inputActionReword", "r")?,
-			input_action_squash: get_input(&git_config, "interactive-rebase-tool.inputActionSquash", "s")?,
-			input_confirm_no: get_input(&git_config, "interactive-rebase-tool.inputConfirmNo", "n")?,
-			input_confirm_yes: get_input(&git_config, "interactive-rebase-tool.inputConfirmYes", "y")?,
-			input_edit: get_input(&git_config, "interactive-rebase-tool.inputEdit", "E")?,
-			input_force_abort: get_input(&git_config, "interactive-rebase-tool.inputForceAbort", "Q")?,
-			input_force_rebase: get_input(&git_config, "interactive-rebase-tool.inputForceRebase", "W")?,
-			input_help: get_input(&git_config, "interactive-rebase-tool.inputHelp", "?")?,
-			input_move_down: get_input(&git_config, "interactive-rebase-tool.inputMoveDown", "Down")?,
-			input_move_left: get_input(&git_config, "interactive-rebase-tool.inputMoveLeft", "Left")?,
-			input_move_right: get_input(&git_config, "interactive-rebase-tool.inputMoveRight", "Right")?,
-			input_move_up_step: get_input(&git_config, "interactive-rebase-tool.inputMoveStepUp", "PageUp")?,
-			input_move_down_step: get_input(&git_config, "interactive-rebase-tool.inputMoveStepDown", "PageDown")?,
-			input_move_selection_down: get_input(&git_config, "interactive-rebase-tool.inputMoveSelectionDown", "j")?,
-			input_move_selection_up: get_input(&git_config, "interactive-rebase-tool.inputMoveSelectionUp", "k")?,
-			input_move_up: get_input(&git_config, "interactive-rebase-tool.inputMoveUp", "Up")?,
+		Self::new_from_config(&open_git_config()?)
+	}
+
+	pub(crate) fn new_from_config(git_config: &git2::Config) -> Result<Self, String> {
+		let config = Config {
+			theme: get_theme(git_config)?,
+			auto_select_next: get_bool(git_config, "interactive-rebase-tool.autoSelectNext", false)?,
+			comment_char: get_string(git_config, "core.commentChar", "#")?,
+			custom_commands: get_custom_commands(git_config)?,
+			editor: get_string(git_config, "core.editor", editor_from_env().as_str())?,
+			graph: GraphStyle::try_from(
+				get_string(git_config, "interactive-rebase-tool.graph", "none")?
+					.to_lowercase()
+					.as_str(),
+			)?,
+			input_abort: get_input(git_config, "interactive-rebase-tool.inputAbort", "q")?,
+			input_action_break: get_input(git_config, "interactive-rebase-tool.inputActionBreak", "b")?,
+			input_action_drop: get_input(git_config, "interactive-rebase-tool.inputActionDrop", "d")?,
+			input_action_edit: get_input(git_config, "interactive-rebase-tool.inputActionEdit", "e")?,
+			input_action_fixup: get_input(git_config, "interactive-rebase-tool.inputActionFixup", "f")?,
+			input_action_pick: get_input(git_config, "interactive-rebase-tool.inputActionPick", "p")?,
+			input_action_reword: get_input(git_config, "interactive-rebase-tool.inputActionReword", "r")?,
+			input_action_squash: get_input(git_config, "interactive-rebase-tool.inputActionSquash", "s")?,
+			input_autosquash: get_input(git_config, "interactive-rebase-tool.inputAutosquash", "A")?,
+			input_confirm_no: get_input(git_config, "interactive-rebase-tool.inputConfirmNo", "n")?,
+			input_confirm_yes: get_input(git_config, "interactive-rebase-tool.inputConfirmYes", "y")?,
+			input_edit: get_input(git_config, "interactive-rebase-tool.inputEdit", "E")?,
+			input_edit_hash: get_input(git_config, "interactive-rebase-tool.inputEditHash", "H")?,
+			input_export_json: get_input(git_config, "interactive-rebase-tool.inputExportJson", "X")?,
+			input_force_abort: get_input(git_config, "interactive-rebase-tool.inputForceAbort", "Q")?,
+			input_force_rebase: get_input(git_config, "interactive-rebase-tool.inputForceRebase", "W")?,
+			input_help: get_input(git_config, "interactive-rebase-tool.inputHelp", "?")?,
+			input_move_down: get_input(git_config, "interactive-rebase-tool.inputMoveDown", "Down")?,
+			input_move_left: get_input(git_config, "interactive-rebase-tool.inputMoveLeft", "Left")?,
+			input_move_right: get_input(git_config, "interactive-rebase-tool.inputMoveRight", "Right")?,
+			input_move_up_step: get_input(git_config, "interactive-rebase-tool.inputMoveStepUp", "PageUp")?,
+			input_move_down_step: get_input(git_config, "interactive-rebase-tool.inputMoveStepDown", "PageDown")?,
+			input_move_selection_down: get_input(git_config, "interactive-rebase-tool.inputMoveSelectionDown", "j")?,
+			input_move_selection_up: get_input(git_config, "interactive-rebase-tool.inputMoveSelectionUp", "k")?,
+			input_move_up: get_input(git_config, "interactive-rebase-tool.inputMoveUp", "Up")?,
 			input_open_in_external_editor: get_input(
-				&git_config,
+				git_config,
 				"interactive-rebase-tool.inputOpenInExternalEditor",
 				"!",
 			)?,
-			input_rebase: get_input(&git_config, "interactive-rebase-tool.inputRebase", "w")?,
-			input_show_commit: get_input(&git_config, "interactive-rebase-tool.inputShowCommit", "c")?,
-			input_toggle_visual_mode: get_input(&git_config, "interactive-rebase-tool.inputToggleVisualMode", "v")?,
-		})
+			input_rebase: get_input(git_config, "interactive-rebase-tool.inputRebase", "w")?,
+			input_show_commit: get_input(git_config, "interactive-rebase-tool.inputShowCommit", "c")?,
+			input_toggle_collapse: get_input(git_config, "interactive-rebase-tool.inputToggleCollapse", "z")?,
+			input_toggle_focus: get_input(git_config, "interactive-rebase-tool.inputToggleFocus", "Tab")?,
+			input_toggle_visual_mode: get_input(git_config, "interactive-rebase-tool.inputToggleVisualMode", "v")?,
+			list_format: parse_list_format(
+				get_string(git_config, "interactive-rebase-tool.listFormat", "{action} {hash} {subject}")?.as_str(),
+			)?,
+			mouse: get_bool(git_config, "interactive-rebase-tool.mouse", false)?,
+			split_layout: SplitLayout::try_from(
+				get_string(git_config, "interactive-rebase-tool.splitLayout", "none")?
+					.to_lowercase()
+					.as_str(),
+			)?,
+			status_bar: get_bool(git_config, "interactive-rebase-tool.statusBar", false)?,
+		};
+
+		if !get_bool(git_config, "interactive-rebase-tool.allowKeyBindingConflicts", false)? {
+			config.check_key_binding_conflicts()?;
+		}
+
+		Ok(config)
+	}
+
+	fn check_key_binding_conflicts(&self) -> Result<(), String> {
+		let movement = [
+			("interactive-rebase-tool.inputMoveUp", self.input_move_up.as_str()),
+			("interactive-rebase-tool.inputMoveDown", self.input_move_down.as_str()),
+			("interactive-rebase-tool.inputMoveLeft", self.input_move_left.as_str()),
+			("interactive-rebase-tool.inputMoveRight", self.input_move_right.as_str()),
+			("interactive-rebase-tool.inputMoveStepUp", self.input_move_up_step.as_str()),
+			("interactive-rebase-tool.inputMoveStepDown", self.input_move_down_step.as_str()),
+		];
+
+		let visual = [
+			("interactive-rebase-tool.inputAbort", self.input_abort.as_str()),
+			("interactive-rebase-tool.inputRebase", self.input_rebase.as_str()),
+			("interactive-rebase-tool.inputForceAbort", self.input_force_abort.as_str()),
+			("interactive-rebase-tool.inputForceRebase", self.input_force_rebase.as_str()),
+			("interactive-rebase-tool.inputHelp", self.input_help.as_str()),
+			(
+				"interactive-rebase-tool.inputToggleVisualMode",
+				self.input_toggle_visual_mode.as_str(),
+			),
+			("interactive-rebase-tool.inputActionDrop", self.input_action_drop.as_str()),
+			("interactive-rebase-tool.inputActionEdit", self.input_action_edit.as_str()),
+			("interactive-rebase-tool.inputActionFixup", self.input_action_fixup.as_str()),
+			("interactive-rebase-tool.inputActionPick", self.input_action_pick.as_str()),
+			("interactive-rebase-tool.inputActionReword", self.input_action_reword.as_str()),
+			("interactive-rebase-tool.inputActionSquash", self.input_action_squash.as_str()),
+			(
+				"interactive-rebase-tool.inputMoveSelectionDown",
+				self.input_move_selection_down.as_str(),
+			),
+			(
+				"interactive-rebase-tool.inputMoveSelectionUp",
+				self.input_move_selection_up.as_str(),
+			),
+		];
+
+		let list_only = [
+			(
+				"interactive-rebase-tool.inputOpenInExternalEditor",
+				self.input_open_in_external_editor.as_str(),
+			),
+			("interactive-rebase-tool.inputShowCommit", self.input_show_commit.as_str()),
+			("interactive-rebase-tool.inputEdit", self.input_edit.as_str()),
+			("interactive-rebase-tool.inputEditHash", self.input_edit_hash.as_str()),
+			("interactive-rebase-tool.inputActionBreak", self.input_action_break.as_str()),
+			("interactive-rebase-tool.inputAutosquash", self.input_autosquash.as_str()),
+			("interactive-rebase-tool.inputExportJson", self.input_export_json.as_str()),
+			(
+				"interactive-rebase-tool.inputToggleCollapse",
+				self.input_toggle_collapse.as_str(),
+			),
+		];
+
+		let toggle_focus = [("interactive-rebase-tool.inputToggleFocus", self.input_toggle_focus.as_str())];
+
+		let custom_commands: Vec<(&str, &str)> = self
+			.custom_commands
+			.iter()
+			.map(|c| (c.name.as_str(), c.key.as_str()))
+			.collect();
+
+		let confirm = [("interactive-rebase-tool.inputConfirmYes", self.input_confirm_yes.as_str())];
+
+		let modes = [
+			(
+				"list",
+				[
+					&visual[..],
+					&list_only[..],
+					&toggle_focus[..],
+					&movement[..],
+					&custom_commands[..],
+				]
+				.concat(),
+			),
+			("visual", [&visual[..], &movement[..]].concat()),
+			("confirm", [&confirm[..], &movement[..]].concat()),
+			("default", [&movement[..], &toggle_focus[..]].concat()),
+		];
+
+		// the confirm yes key responds to both the uppercase and lowercase letter
+		let case_insensitive_names = ["interactive-rebase-tool.inputConfirmYes"];
+
+		let mut conflicts: Vec<(KeyBindingConflict, Vec<&str>)> = vec![];
+		for (mode, bindings) in modes.iter() {
+			for conflict in get_key_binding_conflicts(bindings, &case_insensitive_names) {
+				match conflicts.iter_mut().find(|(c, _)| *c == conflict) {
+					Some((_, conflict_modes)) => conflict_modes.push(mode),
+					None => conflicts.push((conflict, vec![mode])),
+				}
+			}
+		}
+
+		if conflicts.is_empty() {
+			return Ok(());
+		}
+
+		Err(format!(
+			"Error reading git config: conflicting key bindings found\n{}\nSet \
+			 interactive-rebase-tool.allowKeyBindingConflicts to true to allow conflicting key bindings",
+			conflicts
+				.iter()
+				.map(|((first, second, key), modes)| {
+					format!(
+						"{} and {} are both bound to \"{}\" in {} mode",
+						first,
+						second,
+						key,
+						modes.join(" and ")
+					)
+				})
+				.collect::<Vec<String>>()
+				.join("\n")
+		))
 	}
 }
@@ -0,0 +1,50 @@
+use std::convert::TryFrom;
+
+/// Where the commit details are shown next to the todo list, when the window is large enough
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum SplitLayout {
+	Horizontal,
+	None,
+	Vertical,
+}
+
+impl TryFrom<&str> for SplitLayout {
+	type Error = String;
+
+	fn try_from(s: &str) -> Result<Self, Self::Error> {
+		match s {
+			"horizontal" => Ok(SplitLayout::Horizontal),
+			"none" => Ok(SplitLayout::None),
+			"vertical" => Ok(SplitLayout::Vertical),
+			_ => {
+				Err(format!(
+					"Error reading git config: interactive-rebase-tool.splitLayout, invalid value \"{}\", expected \
+					 none, horizontal or vertical",
+					s
+				))
+			},
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::SplitLayout;
+	use std::convert::TryFrom;
+
+	#[test]
+	fn split_layout_try_from() {
+		assert_eq!(SplitLayout::try_from("none").unwrap(), SplitLayout::None);
+		assert_eq!(SplitLayout::try_from("horizontal").unwrap(), SplitLayout::Horizontal);
+		assert_eq!(SplitLayout::try_from("vertical").unwrap(), SplitLayout::Vertical);
+	}
+
+	#[test]
+	fn split_layout_try_from_invalid() {
+		assert_eq!(
+			SplitLayout::try_from("diagonal").unwrap_err(),
+			"Error reading git config: interactive-rebase-tool.splitLayout, invalid value \"diagonal\", expected none, \
+			 horizontal or vertical"
+		);
+	}
+}
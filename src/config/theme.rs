@@ -1,3 +1,4 @@
+use crate::config::utils::{get_color, get_string};
 use crate::display::color::Color;
 
 #[derive(Clone, Debug)]
@@ -19,3 +20,183 @@ pub(crate) struct Theme {
 	pub(crate) color_diff_remove: Color,
 	pub(crate) character_vertical_spacing: String,
 }
+
+fn rgb(red: i16, green: i16, blue: i16) -> Color {
+	Color::RGB { red, green, blue }
+}
+
+impl Theme {
+	/// Creates one of the built-in themes, by name
+	pub(super) fn from_preset(name: &str) -> Result<Self, String> {
+		match name.to_lowercase().as_str() {
+			"dark" => Ok(Self::dark()),
+			"light" => Ok(Self::light()),
+			"solarized" => Ok(Self::solarized()),
+			"high-contrast" => Ok(Self::high_contrast()),
+			_ => {
+				Err(format!(
+					"Error reading git config: Invalid theme: {}. Available themes are dark, light, solarized and \
+					 high-contrast.",
+					name
+				))
+			},
+		}
+	}
+
+	fn dark() -> Self {
+		Self {
+			color_foreground: Color::Default,
+			color_background: Color::Default,
+			color_selected_background: Color::Index(237),
+			color_indicator: Color::LightCyan,
+			color_action_break: Color::LightWhite,
+			color_action_drop: Color::LightRed,
+			color_action_edit: Color::LightBlue,
+			color_action_exec: Color::LightWhite,
+			color_action_fixup: Color::LightMagenta,
+			color_action_pick: Color::LightGreen,
+			color_action_reword: Color::LightYellow,
+			color_action_squash: Color::LightCyan,
+			color_diff_add: Color::LightGreen,
+			color_diff_change: Color::LightYellow,
+			color_diff_remove: Color::LightRed,
+			character_vertical_spacing: String::from("~"),
+		}
+	}
+
+	fn light() -> Self {
+		Self {
+			color_foreground: Color::Default,
+			color_background: Color::Default,
+			color_selected_background: rgb(218, 218, 218),
+			color_indicator: rgb(0, 110, 140),
+			color_action_break: rgb(88, 88, 88),
+			color_action_drop: rgb(175, 0, 0),
+			color_action_edit: rgb(0, 0, 175),
+			color_action_exec: rgb(88, 88, 88),
+			color_action_fixup: rgb(135, 0, 135),
+			color_action_pick: rgb(0, 120, 0),
+			color_action_reword: rgb(135, 95, 0),
+			color_action_squash: rgb(0, 110, 140),
+			color_diff_add: rgb(0, 120, 0),
+			color_diff_change: rgb(135, 95, 0),
+			color_diff_remove: rgb(175, 0, 0),
+			character_vertical_spacing: String::from("~"),
+		}
+	}
+
+	fn solarized() -> Self {
+		Self {
+			color_foreground: rgb(131, 148, 150),
+			color_background: rgb(0, 43, 54),
+			color_selected_background: rgb(7, 54, 66),
+			color_indicator: rgb(42, 161, 152),
+			color_action_break: rgb(147, 161, 161),
+			color_action_drop: rgb(220, 50, 47),
+			color_action_edit: rgb(38, 139, 210),
+			color_action_exec: rgb(147, 161, 161),
+			color_action_fixup: rgb(211, 54, 130),
+			color_action_pick: rgb(133, 153, 0),
+			color_action_reword: rgb(181, 137, 0),
+			color_action_squash: rgb(108, 113, 196),
+			color_diff_add: rgb(133, 153, 0),
+			color_diff_change: rgb(181, 137, 0),
+			color_diff_remove: rgb(220, 50, 47),
+			character_vertical_spacing: String::from("~"),
+		}
+	}
+
+	fn high_contrast() -> Self {
+		Self {
+			color_foreground: Color::LightWhite,
+			color_background: Color::LightBlack,
+			color_selected_background: Color::LightBlue,
+			color_indicator: Color::LightYellow,
+			color_action_break: Color::LightWhite,
+			color_action_drop: Color::LightRed,
+			color_action_edit: Color::LightCyan,
+			color_action_exec: Color::LightWhite,
+			color_action_fixup: Color::LightMagenta,
+			color_action_pick: Color::LightGreen,
+			color_action_reword: Color::LightYellow,
+			color_action_squash: Color::LightCyan,
+			color_diff_add: Color::LightGreen,
+			color_diff_change: Color::LightYellow,
+			color_diff_remove: Color::LightRed,
+			character_vertical_spacing: String::from("~"),
+		}
+	}
+
+	/// Overrides the values of the theme with any values that are set in the provided config
+	pub(super) fn apply_config(self, config: &git2::Config) -> Result<Self, String> {
+		Ok(Self {
+			color_foreground: get_color(config, "interactive-rebase-tool.foregroundColor", self.color_foreground)?,
+			color_background: get_color(config, "interactive-rebase-tool.backgroundColor", self.color_background)?,
+			color_selected_background: get_color(
+				config,
+				"interactive-rebase-tool.selectedBackgroundColor",
+				self.color_selected_background,
+			)?,
+			color_indicator: get_color(config, "interactive-rebase-tool.indicatorColor", self.color_indicator)?,
+			color_action_break: get_color(config, "interactive-rebase-tool.breakColor", self.color_action_break)?,
+			color_action_drop: get_color(config, "interactive-rebase-tool.dropColor", self.color_action_drop)?,
+			color_action_edit: get_color(config, "interactive-rebase-tool.editColor", self.color_action_edit)?,
+			color_action_exec: get_color(config, "interactive-rebase-tool.execColor", self.color_action_exec)?,
+			color_action_fixup: get_color(config, "interactive-rebase-tool.fixupColor", self.color_action_fixup)?,
+			color_action_pick: get_color(config, "interactive-rebase-tool.pickColor", self.color_action_pick)?,
+			color_action_reword: get_color(config, "interactive-rebase-tool.rewordColor", self.color_action_reword)?,
+			color_action_squash: get_color(config, "interactive-rebase-tool.squashColor", self.color_action_squash)?,
+			color_diff_add: get_color(config, "interactive-rebase-tool.diffAddColor", self.color_diff_add)?,
+			color_diff_change: get_color(config, "interactive-rebase-tool.diffChangeColor", self.color_diff_change)?,
+			color_diff_remove: get_color(config, "interactive-rebase-tool.diffRemoveColor", self.color_diff_remove)?,
+			character_vertical_spacing: get_string(
+				config,
+				"interactive-rebase-tool.verticalSpacingCharacter",
+				self.character_vertical_spacing.as_str(),
+			)?,
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Theme;
+	use crate::display::color::Color;
+
+	#[test]
+	fn theme_from_preset_dark() {
+		let theme = Theme::from_preset("dark").unwrap();
+		assert_eq!(theme.color_selected_background, Color::Index(237));
+		assert_eq!(theme.color_action_pick, Color::LightGreen);
+	}
+
+	#[test]
+	fn theme_from_preset_case_insensitive() {
+		let theme = Theme::from_preset("High-Contrast").unwrap();
+		assert_eq!(theme.color_background, Color::LightBlack);
+	}
+
+	#[test]
+	fn theme_from_preset_light() {
+		assert!(Theme::from_preset("light").is_ok());
+	}
+
+	#[test]
+	fn theme_from_preset_solarized() {
+		let theme = Theme::from_preset("solarized").unwrap();
+		assert_eq!(theme.color_background, Color::RGB {
+			red: 0,
+			green: 43,
+			blue: 54
+		});
+	}
+
+	#[test]
+	fn theme_from_preset_invalid() {
+		assert_eq!(
+			Theme::from_preset("neon").unwrap_err(),
+			"Error reading git config: Invalid theme: neon. Available themes are dark, light, solarized and \
+			 high-contrast."
+		);
+	}
+}
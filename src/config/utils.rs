@@ -1,58 +1,61 @@
+use crate::config::custom_command::CustomCommand;
+use crate::config::theme::Theme;
 use crate::display::color::Color;
+use crate::input::utils::{format_key_binding, format_key_bindings, parse_key_bindings};
 use std::convert::TryFrom;
 use std::env;
 
 pub(super) fn get_input(config: &git2::Config, name: &str, default: &str) -> Result<String, String> {
 	let value = get_string(config, name, default)?;
 
-	match value.to_lowercase().as_ref() {
-		"backspace" => Ok(String::from("Backspace")),
-		"delete" => Ok(String::from("Delete")),
-		"down" => Ok(String::from("Down")),
-		"end" => Ok(String::from("End")),
-		"enter" => Ok(String::from("Enter")),
-		"f0" => Ok(String::from("F0")),
-		"f1" => Ok(String::from("F1")),
-		"f2" => Ok(String::from("F2")),
-		"f3" => Ok(String::from("F3")),
-		"f4" => Ok(String::from("F4")),
-		"f5" => Ok(String::from("F5")),
-		"f6" => Ok(String::from("F6")),
-		"f7" => Ok(String::from("F7")),
-		"f8" => Ok(String::from("F8")),
-		"f9" => Ok(String::from("F9")),
-		"f10" => Ok(String::from("F10")),
-		"f11" => Ok(String::from("F11")),
-		"f12" => Ok(String::from("F12")),
-		"f13" => Ok(String::from("F13")),
-		"f14" => Ok(String::from("F14")),
-		"f15" => Ok(String::from("F15")),
-		"home" => Ok(String::from("Home")),
-		"insert" => Ok(String::from("Insert")),
-		"left" => Ok(String::from("Left")),
-		"pagedown" => Ok(String::from("PageDown")),
-		"pageup" => Ok(String::from("PageUp")),
-		"right" => Ok(String::from("Right")),
-		"shift+delete" => Ok(String::from("ShiftDelete")),
-		"shift+down" => Ok(String::from("ShiftDown")),
-		"shift+end" => Ok(String::from("ShiftEnd")),
-		"shift+home" => Ok(String::from("ShiftHome")),
-		"shift+left" => Ok(String::from("ShiftLeft")),
-		"shift+right" => Ok(String::from("ShiftRight")),
-		"shift+tab" => Ok(String::from("ShiftTab")),
-		"shift+up" => Ok(String::from("ShiftUp")),
-		"tab" => Ok(String::from("Tab")),
-		"up" => Ok(String::from("Up")),
-		_ => {
-			if value.len() > 1 {
-				return Err(format!(
-					"Error reading git config: {} must contain only one character",
-					name
-				));
+	match parse_key_bindings(value.as_str()) {
+		Ok(bindings) => Ok(format_key_bindings(&bindings)),
+		Err(e) => Err(format!("Error reading git config: {}, {}", name, e)),
+	}
+}
+
+/// The two config names and the key, or key sequence, that they share
+pub(super) type KeyBindingConflict = (String, String, String);
+
+/// Gets the lowercase and uppercase variants of a single character key, other keys and sequences are unchanged
+fn get_case_variants(sequence: Vec<String>) -> Vec<Vec<String>> {
+	if sequence.len() != 1 || sequence[0].chars().count() != 1 {
+		return vec![sequence];
+	}
+	let lowercase = sequence[0].to_lowercase();
+	let uppercase = sequence[0].to_uppercase();
+	if lowercase == uppercase {
+		vec![vec![lowercase]]
+	}
+	else {
+		vec![vec![lowercase], vec![uppercase]]
+	}
+}
+
+/// Finds keys, or key sequences, that are bound to more than one config name, the single character keys of the case
+/// insensitive names are bound in both cases
+pub(super) fn get_key_binding_conflicts(
+	bindings: &[(&str, &str)],
+	case_insensitive_names: &[&str],
+) -> Vec<KeyBindingConflict>
+{
+	let mut seen: Vec<(Vec<String>, &str)> = vec![];
+	let mut conflicts = vec![];
+	for (name, value) in bindings {
+		let mut sequences = parse_key_bindings(value).unwrap_or_default();
+		if case_insensitive_names.contains(name) {
+			sequences = sequences.into_iter().flat_map(get_case_variants).collect();
+		}
+		for sequence in sequences {
+			match seen.iter().find(|(s, n)| *s == sequence && n != name) {
+				Some((_, other_name)) => {
+					conflicts.push((String::from(*other_name), String::from(*name), format_key_binding(&sequence)));
+				},
+				None => seen.push((sequence, name)),
 			}
-			Ok(value)
-		},
+		}
 	}
+	conflicts
 }
 
 pub(super) fn get_string(config: &git2::Config, name: &str, default: &str) -> Result<String, String> {
@@ -96,3 +99,249 @@ pub(super) fn open_git_config() -> Result<git2::Config, String> {
 		Err(e) => Err(format!("Error reading git config: {}", e)),
 	}
 }
+
+/// Loads the theme preset, then the theme file, with any values in the git config taking precedence
+pub(super) fn get_theme(config: &git2::Config) -> Result<Theme, String> {
+	let mut theme = Theme::from_preset(get_string(config, "interactive-rebase-tool.theme", "dark")?.as_str())?;
+
+	match config.get_path("interactive-rebase-tool.themeFile") {
+		Ok(path) => {
+			if !path.is_file() {
+				return Err(format!(
+					"Error reading git config: theme file {} does not exist",
+					path.display()
+				));
+			}
+			match git2::Config::open(path.as_path()) {
+				Ok(theme_config) => theme = theme.apply_config(&theme_config)?,
+				Err(e) => return Err(format!("Error reading theme file: {}", e)),
+			}
+		},
+		Err(ref e) if e.code() == git2::ErrorCode::NotFound => {},
+		Err(e) => return Err(format!("Error reading git config: {}", e)),
+	}
+
+	theme.apply_config(config)
+}
+
+/// Reads the custom commands, where the last part of the config name is the name of the command, and the key is read
+/// from the `customCommandKey` value of the same name, or is the name when that is not set
+pub(super) fn get_custom_commands(config: &git2::Config) -> Result<Vec<CustomCommand>, String> {
+	let mut names: Vec<String> = vec![];
+	let entries = config
+		.entries(Some(r"^interactive-rebase-tool\.customCommand\."))
+		.map_err(|e| format!("Error reading git config: {}", e))?;
+	for entry in &entries {
+		let entry = entry.map_err(|e| format!("Error reading git config: {}", e))?;
+		if let Some(name) = entry.name() {
+			if !names.iter().any(|n| n == name) {
+				names.push(String::from(name));
+			}
+		}
+	}
+
+	let mut custom_commands = vec![];
+	for name in names {
+		// safe unwrap, the entry name always contains the matched prefix
+		let command_name = name.rsplit('.').next().unwrap();
+		// git lowercases config names, so any other key is set with a value
+		let key_config_name = format!("interactive-rebase-tool.customCommandKey.{}", command_name);
+		let key = match parse_key_bindings(get_string(config, key_config_name.as_str(), command_name)?.as_str()) {
+			Ok(bindings) => format_key_bindings(&bindings),
+			Err(e) => return Err(format!("Error reading git config: {}, {}", key_config_name, e)),
+		};
+		let command = get_string(config, name.as_str(), "")?;
+		if command.trim().is_empty() {
+			return Err(format!("Error reading git config: {}, command cannot be empty", name));
+		}
+		custom_commands.push(CustomCommand {
+			show_output: get_bool(
+				config,
+				format!("interactive-rebase-tool.customCommandShowOutput.{}", command_name).as_str(),
+				false,
+			)?,
+			name,
+			key,
+			command,
+		});
+	}
+	Ok(custom_commands)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_custom_commands, get_key_binding_conflicts, get_theme, KeyBindingConflict};
+	use crate::config::Config;
+	use crate::display::color::Color;
+	use crate::temp_path::TempPath;
+	use std::fs;
+
+	fn conflict(first: &str, second: &str, key: &str) -> KeyBindingConflict {
+		(String::from(first), String::from(second), String::from(key))
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_none() {
+		assert!(get_key_binding_conflicts(&[("a.inputOne", "a b"), ("a.inputTwo", "c aa")], &[]).is_empty());
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_same_key() {
+		assert_eq!(
+			get_key_binding_conflicts(&[("a.inputOne", "a b"), ("a.inputTwo", "c b")], &[]),
+			vec![conflict("a.inputOne", "a.inputTwo", "b")]
+		);
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_same_sequence() {
+		assert_eq!(
+			get_key_binding_conflicts(&[("a.inputOne", "gg"), ("a.inputTwo", "Control+d gg")], &[]),
+			vec![conflict("a.inputOne", "a.inputTwo", "g g")]
+		);
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_duplicate_within_binding() {
+		assert!(get_key_binding_conflicts(&[("a.inputOne", "a a")], &[]).is_empty());
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_case_insensitive() {
+		assert_eq!(
+			get_key_binding_conflicts(&[("a.inputOne", "y"), ("a.inputTwo", "Y")], &["a.inputOne"]),
+			vec![conflict("a.inputOne", "a.inputTwo", "Y")]
+		);
+		assert!(get_key_binding_conflicts(&[("a.inputOne", "Y"), ("a.inputTwo", "y")], &[]).is_empty());
+	}
+
+	#[test]
+	fn get_key_binding_conflicts_case_insensitive_only_named() {
+		// only the single character keys of the case insensitive name are folded
+		assert!(get_key_binding_conflicts(
+			&[("a.inputYes", "y"), ("a.inputUp", "K Up"), ("a.inputDown", "k Down")],
+			&["a.inputYes"]
+		)
+		.is_empty());
+	}
+
+	fn create_config(name: &str, values: &[(&str, &str)]) -> (git2::Config, TempPath) {
+		let path = TempPath::new(format!("config-{}.gitconfig", name).as_str());
+		fs::write(path.get_path(), "").unwrap();
+		let mut config = git2::Config::open(path.get_path()).unwrap();
+		for (name, value) in values {
+			config.set_str(name, value).unwrap();
+		}
+		(config, path)
+	}
+
+	#[test]
+	fn get_custom_commands_keys() {
+		let (config, _path) = create_config("custom-commands", &[
+			("interactive-rebase-tool.customCommand.tig", "tig show {hash}"),
+			("interactive-rebase-tool.customCommandKey.tig", "T Control+t"),
+			("interactive-rebase-tool.customCommand.l", "git log {hash}"),
+			("interactive-rebase-tool.customCommandShowOutput.l", "true"),
+		]);
+		let custom_commands = get_custom_commands(&config).unwrap();
+		assert_eq!(custom_commands.len(), 2);
+		assert_eq!(custom_commands[0].name, "interactive-rebase-tool.customCommand.tig");
+		assert_eq!(custom_commands[0].key, "T Control+t");
+		assert_eq!(custom_commands[0].command, "tig show {hash}");
+		assert!(!custom_commands[0].show_output);
+		assert_eq!(custom_commands[1].key, "l");
+		assert_eq!(custom_commands[1].command, "git log {hash}");
+		assert!(custom_commands[1].show_output);
+	}
+
+	#[test]
+	fn get_custom_commands_invalid_key() {
+		let (config, _path) = create_config("custom-commands-invalid-key", &[
+			("interactive-rebase-tool.customCommand.tig", "tig show {hash}"),
+			("interactive-rebase-tool.customCommandKey.tig", "Control+1"),
+		]);
+		let error = get_custom_commands(&config).unwrap_err();
+		assert_eq!(
+			error,
+			"Error reading git config: interactive-rebase-tool.customCommandKey.tig, Invalid key binding: Control+1"
+		);
+	}
+
+	#[test]
+	fn get_custom_commands_empty_command() {
+		let (config, _path) =
+			create_config("custom-commands-empty", &[("interactive-rebase-tool.customCommand.t", " ")]);
+		let error = get_custom_commands(&config).unwrap_err();
+		assert_eq!(
+			error,
+			"Error reading git config: interactive-rebase-tool.customCommand.t, command cannot be empty"
+		);
+	}
+
+	#[test]
+	fn get_custom_commands_key_conflict() {
+		let (config, _path) = create_config("custom-commands-conflict", &[
+			("interactive-rebase-tool.customCommand.tig", "tig show {hash}"),
+			("interactive-rebase-tool.customCommandKey.tig", "W"),
+		]);
+		let error = Config::new_from_config(&config).err().unwrap();
+		assert!(error.contains(
+			"interactive-rebase-tool.inputForceRebase and interactive-rebase-tool.customCommand.tig are both bound to \
+			 \"W\" in list mode"
+		));
+	}
+
+	/// Writes a theme file with the contents, and a config that uses it, with any other values
+	fn create_theme_config(name: &str, contents: &str, values: &[(&str, &str)]) -> (git2::Config, TempPath, TempPath) {
+		let theme_path = TempPath::new(format!("theme-{}.gitconfig", name).as_str());
+		fs::write(theme_path.get_path(), contents).unwrap();
+		let theme_file = theme_path.get_path().to_string_lossy().into_owned();
+		let mut values = values.to_vec();
+		values.push(("interactive-rebase-tool.themeFile", theme_file.as_str()));
+		let (config, path) = create_config(name, &values);
+		(config, path, theme_path)
+	}
+
+	#[test]
+	fn get_theme_file() {
+		let (config, _path, _theme_path) = create_theme_config(
+			"theme-file",
+			"[interactive-rebase-tool]\n\tpickColor = dark red\n",
+			&[],
+		);
+		let theme = get_theme(&config).unwrap();
+		assert_eq!(theme.color_action_pick, Color::DarkRed);
+		// values that are not in the file are from the preset
+		assert_eq!(theme.color_action_drop, Color::LightRed);
+	}
+
+	#[test]
+	fn get_theme_file_missing() {
+		let (config, _path, theme_path) = create_theme_config("theme-file-missing", "", &[]);
+		let theme_file = theme_path.get_path().to_path_buf();
+		drop(theme_path);
+		assert_eq!(
+			get_theme(&config).err().unwrap(),
+			format!("Error reading git config: theme file {} does not exist", theme_file.display())
+		);
+	}
+
+	#[test]
+	fn get_theme_file_invalid() {
+		let (config, _path, _theme_path) =
+			create_theme_config("theme-file-invalid", "[interactive-rebase-tool\n", &[]);
+		assert!(get_theme(&config).err().unwrap().starts_with("Error reading theme file: "));
+	}
+
+	#[test]
+	fn get_theme_file_config_override() {
+		let (config, _path, _theme_path) = create_theme_config(
+			"theme-file-override",
+			"[interactive-rebase-tool]\n\tpickColor = dark red\n\tdropColor = dark blue\n",
+			&[("interactive-rebase-tool.pickColor", "dark green")],
+		);
+		let theme = get_theme(&config).unwrap();
+		assert_eq!(theme.color_action_pick, Color::DarkGreen);
+		assert_eq!(theme.color_action_drop, Color::DarkBlue);
+	}
+}
@@ -1,3 +1,8 @@
+mod summary;
+mod utils;
+
+use crate::confirm_rebase::summary::Summary;
+use crate::confirm_rebase::utils::get_summary_view_lines;
 use crate::git_interactive::GitInteractive;
 use crate::input::input_handler::{InputHandler, InputMode};
 use crate::input::Input;
@@ -5,19 +10,35 @@ use crate::process::exit_status::ExitStatus;
 use crate::process::handle_input_result::{HandleInputResult, HandleInputResultBuilder};
 use crate::process::process_module::ProcessModule;
 use crate::process::state::State;
+use crate::view::scroll_position::ScrollPosition;
+use crate::view::view_line::ViewLine;
 use crate::view::View;
 
-pub(crate) struct ConfirmRebase {}
+pub(crate) struct ConfirmRebase {
+	scroll_position: ScrollPosition,
+	view_lines: Vec<ViewLine>,
+}
 
 impl ProcessModule for ConfirmRebase {
+	fn activate(&mut self, _state: State, git_interactive: &GitInteractive) {
+		self.scroll_position.reset();
+		let summary = Summary::new(git_interactive.get_original_lines(), git_interactive.get_lines());
+		self.view_lines = get_summary_view_lines(&summary);
+	}
+
+	fn deactivate(&mut self) {
+		self.view_lines.clear();
+	}
+
 	fn handle_input(
 		&mut self,
 		input_handler: &InputHandler,
 		_git_interactive: &mut GitInteractive,
-		_view: &View,
+		view: &View,
 	) -> HandleInputResult
 	{
-		let input = input_handler.get_input(InputMode::Confirm);
+		let input = input_handler.get_input(InputMode::ConfirmRebase);
+		let (view_width, view_height) = view.get_view_size();
 		let mut result = HandleInputResultBuilder::new(input);
 		match input {
 			Input::Yes => {
@@ -26,18 +47,56 @@ impl ProcessModule for ConfirmRebase {
 			Input::No => {
 				result = result.state(State::List(false));
 			},
+			Input::MoveCursorLeft => {
+				self.scroll_position
+					.scroll_left(view_width, self.get_max_line_length())
+			},
+			Input::MoveCursorRight => {
+				self.scroll_position
+					.scroll_right(view_width, self.get_max_line_length())
+			},
+			Input::MoveCursorDown => self.scroll_position.scroll_down(view_height, self.view_lines.len()),
+			Input::MoveCursorUp => self.scroll_position.scroll_up(view_height, self.view_lines.len()),
+			Input::MoveCursorPageDown => self.scroll_position.page_down(view_height, self.view_lines.len()),
+			Input::MoveCursorPageUp => self.scroll_position.page_up(view_height, self.view_lines.len()),
+			Input::Resize => {
+				self.scroll_position.view_resize(
+					view_height,
+					view_width,
+					self.view_lines.len(),
+					self.get_max_line_length(),
+				)
+			},
 			_ => {},
 		}
 		result.build()
 	}
 
 	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
-		view.draw_confirm("Are you sure you want to rebase");
+		let (_, view_height) = view.get_view_size();
+
+		view.draw_title(false);
+
+		view.draw_view_lines(
+			&self.view_lines,
+			self.scroll_position.get_top_position(),
+			self.scroll_position.get_left_position(),
+			view_height - 2,
+		);
+
+		view.draw_confirm_prompt("Are you sure you want to rebase");
 	}
 }
 
 impl ConfirmRebase {
 	pub(crate) fn new() -> Self {
-		Self {}
+		Self {
+			scroll_position: ScrollPosition::new(2),
+			view_lines: vec![],
+		}
+	}
+
+	fn get_max_line_length(&self) -> usize {
+		self.view_lines.iter().map(ViewLine::get_length).max().unwrap_or(0)
 	}
 }
@@ -0,0 +1,292 @@
+use crate::list::action::Action;
+use crate::list::line::Line;
+
+/// Finds the indexes of the longest increasing subsequence of values, these are the items that did not move
+fn get_unmoved_indexes(values: &[usize]) -> Vec<usize> {
+	let mut lengths: Vec<usize> = vec![1; values.len()];
+	let mut previous: Vec<Option<usize>> = vec![None; values.len()];
+
+	for i in 0..values.len() {
+		for j in 0..i {
+			if values[j] < values[i] && lengths[j] + 1 > lengths[i] {
+				lengths[i] = lengths[j] + 1;
+				previous[i] = Some(j);
+			}
+		}
+	}
+
+	let mut end = None;
+	for (index, length) in lengths.iter().enumerate() {
+		match end {
+			Some(e) if lengths[e] >= *length => {},
+			_ => end = Some(index),
+		}
+	}
+
+	let mut indexes = vec![];
+	while let Some(index) = end {
+		indexes.push(index);
+		end = previous[index];
+	}
+	indexes
+}
+
+#[derive(Debug)]
+pub(super) struct Summary {
+	breaks_added: usize,
+	dropped: Vec<Line>,
+	edited: Vec<Line>,
+	execs_added: Vec<String>,
+	fixed_up: Vec<Line>,
+	moved: Vec<(Line, isize)>,
+	reworded: Vec<Line>,
+	squashed: Vec<Line>,
+}
+
+impl Summary {
+	pub(super) fn new(original_lines: &[Line], lines: &[Line]) -> Self {
+		let mut dropped = vec![];
+		let mut edited = vec![];
+		let mut fixed_up = vec![];
+		let mut reworded = vec![];
+		let mut squashed = vec![];
+
+		// only lines with a different action than in the original todo are changes
+		for line in lines.iter().filter(|l| l.get_action().has_commit()) {
+			let original_action = original_lines
+				.iter()
+				.find(|l| l.get_hash() == line.get_hash())
+				.map(|l| *l.get_action());
+			if original_action == Some(*line.get_action()) {
+				continue;
+			}
+			match *line.get_action() {
+				Action::Drop => dropped.push(line.clone()),
+				Action::Edit => edited.push(line.clone()),
+				Action::Fixup => fixed_up.push(line.clone()),
+				Action::Reword => reworded.push(line.clone()),
+				Action::Squash => squashed.push(line.clone()),
+				_ => {},
+			}
+		}
+
+		// lines removed from the todo, for example with the external editor, are dropped by git
+		for original_line in original_lines.iter().filter(|l| l.get_action().has_commit()) {
+			if !lines.iter().any(|l| l.get_hash() == original_line.get_hash()) {
+				let mut line = original_line.clone();
+				line.set_action(Action::Drop);
+				dropped.push(line);
+			}
+		}
+
+		Self {
+			breaks_added: Self::calculate_breaks_added(original_lines, lines),
+			dropped,
+			edited,
+			execs_added: Self::calculate_execs_added(original_lines, lines),
+			fixed_up,
+			moved: Self::calculate_moved(original_lines, lines),
+			reworded,
+			squashed,
+		}
+	}
+
+	fn calculate_breaks_added(original_lines: &[Line], lines: &[Line]) -> usize {
+		let original_breaks = original_lines
+			.iter()
+			.filter(|l| *l.get_action() == Action::Break)
+			.count();
+		let breaks = lines.iter().filter(|l| *l.get_action() == Action::Break).count();
+		breaks.saturating_sub(original_breaks)
+	}
+
+	fn calculate_execs_added(original_lines: &[Line], lines: &[Line]) -> Vec<String> {
+		let mut original_commands: Vec<&String> = original_lines
+			.iter()
+			.filter(|l| *l.get_action() == Action::Exec)
+			.map(Line::get_command)
+			.collect();
+
+		let mut execs_added = vec![];
+		for line in lines.iter().filter(|l| *l.get_action() == Action::Exec) {
+			match original_commands.iter().position(|c| *c == line.get_command()) {
+				Some(index) => {
+					original_commands.remove(index);
+				},
+				None => execs_added.push(line.get_command().clone()),
+			}
+		}
+		execs_added
+	}
+
+	fn calculate_moved(original_lines: &[Line], lines: &[Line]) -> Vec<(Line, isize)> {
+		let original_hashes: Vec<&String> = original_lines
+			.iter()
+			.filter(|l| l.get_action().has_commit())
+			.map(Line::get_hash)
+			.filter(|h| lines.iter().any(|l| l.get_hash() == *h))
+			.collect();
+
+		let commit_lines: Vec<(&Line, usize)> = lines
+			.iter()
+			.filter(|l| l.get_action().has_commit())
+			.filter_map(|l| {
+				original_hashes
+					.iter()
+					.position(|h| *h == l.get_hash())
+					.map(|original_index| (l, original_index))
+			})
+			.collect();
+
+		let original_indexes: Vec<usize> = commit_lines.iter().map(|(_, i)| *i).collect();
+		let unmoved = get_unmoved_indexes(&original_indexes);
+
+		commit_lines
+			.iter()
+			.enumerate()
+			.filter(|(index, _)| !unmoved.contains(index))
+			.map(|(index, (line, original_index))| ((*line).clone(), index as isize - *original_index as isize))
+			.collect()
+	}
+
+	pub(super) fn is_empty(&self) -> bool {
+		self.breaks_added == 0
+			&& self.dropped.is_empty()
+			&& self.edited.is_empty()
+			&& self.execs_added.is_empty()
+			&& self.fixed_up.is_empty()
+			&& self.moved.is_empty()
+			&& self.reworded.is_empty()
+			&& self.squashed.is_empty()
+	}
+
+	pub(super) fn get_breaks_added_count(&self) -> usize {
+		self.breaks_added
+	}
+
+	pub(super) fn get_dropped(&self) -> &Vec<Line> {
+		&self.dropped
+	}
+
+	pub(super) fn get_edited(&self) -> &Vec<Line> {
+		&self.edited
+	}
+
+	pub(super) fn get_execs_added(&self) -> &Vec<String> {
+		&self.execs_added
+	}
+
+	pub(super) fn get_fixed_up(&self) -> &Vec<Line> {
+		&self.fixed_up
+	}
+
+	pub(super) fn get_moved_lines(&self) -> &Vec<(Line, isize)> {
+		&self.moved
+	}
+
+	pub(super) fn get_reworded(&self) -> &Vec<Line> {
+		&self.reworded
+	}
+
+	pub(super) fn get_squashed(&self) -> &Vec<Line> {
+		&self.squashed
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Summary;
+	use crate::list::line::Line;
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	#[test]
+	fn summary_no_changes() {
+		let lines = create_lines(&["pick aaa c1", "pick bbb c2", "exec make", "break"]);
+		let summary = Summary::new(&lines, &lines);
+		assert!(summary.is_empty());
+	}
+
+	#[test]
+	fn summary_changed_actions() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4", "pick eee c5"]);
+		let lines = create_lines(&["drop aaa c1", "edit bbb c2", "fixup ccc c3", "reword ddd c4", "squash eee c5"]);
+		let summary = Summary::new(&original, &lines);
+		assert!(!summary.is_empty());
+		assert_eq!(summary.get_dropped()[0].get_hash(), "aaa");
+		assert_eq!(summary.get_edited()[0].get_hash(), "bbb");
+		assert_eq!(summary.get_fixed_up()[0].get_hash(), "ccc");
+		assert_eq!(summary.get_reworded()[0].get_hash(), "ddd");
+		assert_eq!(summary.get_squashed()[0].get_hash(), "eee");
+		assert!(summary.get_moved_lines().is_empty());
+	}
+
+	#[test]
+	fn summary_original_actions_not_changed() {
+		let lines = create_lines(&["pick aaa c1", "fixup bbb fixup! c1", "squash ccc c3", "edit ddd c4"]);
+		let summary = Summary::new(&lines, &lines);
+		assert!(summary.is_empty());
+	}
+
+	#[test]
+	fn summary_action_changed_from_original() {
+		let original = create_lines(&["pick aaa c1", "fixup bbb fixup! c1", "pick ccc c3"]);
+		let lines = create_lines(&["pick aaa c1", "fixup bbb fixup! c1", "fixup ccc c3"]);
+		let summary = Summary::new(&original, &lines);
+		assert_eq!(summary.get_fixed_up().len(), 1);
+		assert_eq!(summary.get_fixed_up()[0].get_hash(), "ccc");
+	}
+
+	#[test]
+	fn summary_removed_line_is_dropped() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let lines = create_lines(&["pick aaa c1"]);
+		let summary = Summary::new(&original, &lines);
+		assert_eq!(summary.get_dropped().len(), 1);
+		assert_eq!(summary.get_dropped()[0].get_hash(), "bbb");
+		assert!(summary.get_moved_lines().is_empty());
+	}
+
+	#[test]
+	fn summary_single_line_moved_up() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"]);
+		let lines = create_lines(&["pick ddd c4", "pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		let summary = Summary::new(&original, &lines);
+		let moved = summary.get_moved_lines();
+		assert_eq!(moved.len(), 1);
+		assert_eq!(moved[0].0.get_hash(), "ddd");
+		assert_eq!(moved[0].1, -3);
+	}
+
+	#[test]
+	fn summary_single_line_moved_down() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2", "pick ccc c3"]);
+		let lines = create_lines(&["pick bbb c2", "pick ccc c3", "pick aaa c1"]);
+		let summary = Summary::new(&original, &lines);
+		let moved = summary.get_moved_lines();
+		assert_eq!(moved.len(), 1);
+		assert_eq!(moved[0].0.get_hash(), "aaa");
+		assert_eq!(moved[0].1, 2);
+	}
+
+	#[test]
+	fn summary_break_and_exec_not_moved() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let lines = create_lines(&["pick aaa c1", "break", "exec make", "pick bbb c2"]);
+		let summary = Summary::new(&original, &lines);
+		assert!(summary.get_moved_lines().is_empty());
+		assert_eq!(summary.get_breaks_added_count(), 1);
+		assert_eq!(summary.get_execs_added(), &vec![String::from("make")]);
+	}
+
+	#[test]
+	fn summary_existing_exec_not_added() {
+		let original = create_lines(&["pick aaa c1", "exec make", "pick bbb c2", "break"]);
+		let lines = create_lines(&["pick aaa c1", "exec make", "pick bbb c2", "exec make test", "break"]);
+		let summary = Summary::new(&original, &lines);
+		assert_eq!(summary.get_breaks_added_count(), 0);
+		assert_eq!(summary.get_execs_added(), &vec![String::from("make test")]);
+	}
+}
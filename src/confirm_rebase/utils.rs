@@ -0,0 +1,105 @@
+use crate::confirm_rebase::summary::Summary;
+use crate::display::display_color::DisplayColor;
+use crate::list::line::Line;
+use crate::view::line_segment::LineSegment;
+use crate::view::view_line::ViewLine;
+use std::cmp;
+
+fn get_header_line(title: &str, count: usize, color: DisplayColor) -> ViewLine {
+	ViewLine::new(vec![LineSegment::new_with_color(
+		format!("{} ({})", title, count).as_str(),
+		color,
+	)])
+}
+
+fn get_commit_line(line: &Line) -> ViewLine {
+	let max_index = cmp::min(line.get_hash().len(), 8);
+	ViewLine::new_with_pinned_segments(
+		vec![
+			LineSegment::new(format!("   {:8} ", line.get_hash()[0..max_index].to_string()).as_str()),
+			LineSegment::new(line.get_comment().as_str()),
+		],
+		1,
+	)
+}
+
+fn push_commit_lines(view_lines: &mut Vec<ViewLine>, title: &str, lines: &[Line], color: DisplayColor) {
+	if lines.is_empty() {
+		return;
+	}
+	view_lines.push(get_header_line(title, lines.len(), color));
+	for line in lines {
+		view_lines.push(get_commit_line(line));
+	}
+}
+
+pub(super) fn get_summary_view_lines(summary: &Summary) -> Vec<ViewLine> {
+	let mut view_lines = vec![];
+
+	if summary.is_empty() {
+		view_lines.push(ViewLine::new(vec![LineSegment::new("No changes to the rebase todo")]));
+		return view_lines;
+	}
+
+	push_commit_lines(&mut view_lines, "Dropped", summary.get_dropped(), DisplayColor::ActionDrop);
+	push_commit_lines(
+		&mut view_lines,
+		"Squashed",
+		summary.get_squashed(),
+		DisplayColor::ActionSquash,
+	);
+	push_commit_lines(
+		&mut view_lines,
+		"Fixed-up",
+		summary.get_fixed_up(),
+		DisplayColor::ActionFixup,
+	);
+	push_commit_lines(
+		&mut view_lines,
+		"Reworded",
+		summary.get_reworded(),
+		DisplayColor::ActionReword,
+	);
+	push_commit_lines(&mut view_lines, "Edited", summary.get_edited(), DisplayColor::ActionEdit);
+
+	let moved = summary.get_moved_lines();
+	if !moved.is_empty() {
+		view_lines.push(get_header_line("Moved", moved.len(), DisplayColor::IndicatorColor));
+		for (line, offset) in moved {
+			let max_index = cmp::min(line.get_hash().len(), 8);
+			let direction = if *offset < 0 { "up" } else { "down" };
+			view_lines.push(ViewLine::new_with_pinned_segments(
+				vec![
+					LineSegment::new(format!("   {:8} ", line.get_hash()[0..max_index].to_string()).as_str()),
+					LineSegment::new_with_color(
+						format!("{:>4} {:<3} ", direction, offset.abs()).as_str(),
+						DisplayColor::IndicatorColor,
+					),
+					LineSegment::new(line.get_comment().as_str()),
+				],
+				2,
+			));
+		}
+	}
+
+	let execs_added = summary.get_execs_added();
+	if !execs_added.is_empty() {
+		view_lines.push(get_header_line(
+			"Exec added",
+			execs_added.len(),
+			DisplayColor::ActionExec,
+		));
+		for command in execs_added {
+			view_lines.push(ViewLine::new(vec![LineSegment::new(
+				format!("   {}", command).as_str(),
+			)]));
+		}
+	}
+
+	let breaks_added = summary.get_breaks_added_count();
+	if breaks_added > 0 {
+		view_lines.push(get_header_line("Break added", breaks_added, DisplayColor::ActionBreak));
+	}
+
+	view_lines
+}
@@ -0,0 +1,59 @@
+use crate::git_interactive::GitInteractive;
+use crate::input::input_handler::{InputHandler, InputMode};
+use crate::input::Input;
+use crate::process::handle_input_result::{HandleInputResult, HandleInputResultBuilder};
+use crate::process::process_module::ProcessModule;
+use crate::process::process_result::{ProcessResult, ProcessResultBuilder};
+use crate::process::state::State;
+use crate::view::View;
+
+pub(crate) struct ConfirmRestore {
+	error: Option<String>,
+}
+
+impl ProcessModule for ConfirmRestore {
+	fn process(&mut self, _git_interactive: &mut GitInteractive, _view: &View) -> ProcessResult {
+		let mut result = ProcessResultBuilder::new();
+		if let Some(error) = self.error.take() {
+			result = result.error(error.as_str(), State::List(false));
+		}
+		result.build()
+	}
+
+	fn handle_input(
+		&mut self,
+		input_handler: &InputHandler,
+		git_interactive: &mut GitInteractive,
+		_view: &View,
+	) -> HandleInputResult
+	{
+		let input = input_handler.get_input(InputMode::Confirm);
+		let mut result = HandleInputResultBuilder::new(input);
+		match input {
+			Input::Yes => {
+				git_interactive.restore_session();
+				result = result.state(State::List(false));
+			},
+			Input::No => {
+				if let Err(e) = git_interactive.discard_session() {
+					self.error = Some(e);
+				}
+				else {
+					result = result.state(State::List(false));
+				}
+			},
+			_ => {},
+		}
+		result.build()
+	}
+
+	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
+		view.draw_confirm("A previous unsaved session was found, do you want to restore it");
+	}
+}
+
+impl ConfirmRestore {
+	pub(crate) fn new() -> Self {
+		Self { error: None }
+	}
+}
@@ -12,9 +12,20 @@ pub(crate) const SHORT_ERROR_MESSAGE_WIDTH: usize = 16;
 pub(crate) const MINIMUM_WINDOW_HEIGHT: usize = 5; // title + pad top + line + pad bottom + help
 pub(crate) const MINIMUM_COMPACT_WINDOW_WIDTH: usize = 20; // ">s ccc mmmmmmmmmmmmm".len()
 pub(crate) const MINIMUM_FULL_WINDOW_WIDTH: usize = 34; // " > squash cccccccc mmmmmmmmmmmmm %".len()
+pub(crate) const MINIMUM_SPLIT_WINDOW_WIDTH: usize = 100; // two panes wider than a full width window
+pub(crate) const MINIMUM_SPLIT_WINDOW_HEIGHT: usize = 20; // two panes showing at least a few lines each
+
+pub(crate) const INPUT_MODIFIER_TIMEOUT: i32 = 50; // time to wait for a key after escape, before treating it as Alt
+pub(crate) const INPUT_SEQUENCE_TIMEOUT: i32 = 1000; // time to wait for the next key in a sequence
 
 pub(crate) const NAME: &str = "interactive-rebase-tool";
 
+pub(crate) const SESSION_FILE_NAME: &str = "interactive-rebase-tool-session";
+pub(crate) const EXPORT_JSON_FILE_NAME: &str = "interactive-rebase-tool-todo.json";
+pub(crate) const EXEC_HISTORY_FILE_NAME: &str = "interactive-rebase-tool-exec-history";
+pub(crate) const EXEC_HISTORY_LIMIT: usize = 100;
+pub(crate) const MESSAGE_FILE_PREFIX: &str = "interactive-rebase-tool-message-";
+
 #[cfg(not(feature = "nightly"))]
 pub(crate) const VERSION: &str = env!("CARGO_PKG_VERSION");
 #[cfg(feature = "nightly")]
@@ -0,0 +1,198 @@
+mod utils;
+
+use crate::config::Config;
+use crate::custom_command::utils::{get_command_arguments, uses_commit_placeholder};
+use crate::display::display_color::DisplayColor;
+use crate::display::Display;
+use crate::git_interactive::GitInteractive;
+use crate::input::input_handler::{InputHandler, InputMode};
+use crate::input::Input;
+use crate::process::handle_input_result::{HandleInputResult, HandleInputResultBuilder};
+use crate::process::process_module::ProcessModule;
+use crate::process::process_result::{ProcessResult, ProcessResultBuilder};
+use crate::process::state::State;
+use crate::view::line_segment::LineSegment;
+use crate::view::scroll_position::ScrollPosition;
+use crate::view::view_line::ViewLine;
+use crate::view::View;
+use std::process::Command;
+
+#[derive(Clone, Debug, PartialEq)]
+enum CustomCommandState {
+	Active,
+	Output,
+}
+
+pub(crate) struct CustomCommand<'c> {
+	arguments: Result<Vec<String>, String>,
+	config: &'c Config,
+	display: &'c Display<'c>,
+	index: usize,
+	scroll_position: ScrollPosition,
+	state: CustomCommandState,
+	view_lines: Vec<ViewLine>,
+}
+
+impl<'c> ProcessModule for CustomCommand<'c> {
+	fn activate(&mut self, state: State, git_interactive: &GitInteractive) {
+		if let State::CustomCommand(index) = state {
+			self.index = index;
+		}
+		self.scroll_position.reset();
+		self.state = CustomCommandState::Active;
+		self.arguments = self.load_arguments(git_interactive);
+	}
+
+	fn deactivate(&mut self) {
+		self.view_lines.clear();
+	}
+
+	fn process(&mut self, _git_interactive: &mut GitInteractive, _view: &View) -> ProcessResult {
+		match self.state {
+			CustomCommandState::Active => self.process_active(),
+			CustomCommandState::Output => ProcessResult::new(),
+		}
+	}
+
+	fn handle_input(
+		&mut self,
+		input_handler: &InputHandler,
+		_git_interactive: &mut GitInteractive,
+		view: &View,
+	) -> HandleInputResult
+	{
+		let input = input_handler.get_input(InputMode::Default);
+		let (view_width, view_height) = view.get_view_size();
+		let mut result = HandleInputResultBuilder::new(input);
+		match input {
+			Input::MoveCursorLeft => {
+				self.scroll_position
+					.scroll_left(view_width, self.get_max_line_length())
+			},
+			Input::MoveCursorRight => {
+				self.scroll_position
+					.scroll_right(view_width, self.get_max_line_length())
+			},
+			Input::MoveCursorDown => self.scroll_position.scroll_down(view_height, self.view_lines.len()),
+			Input::MoveCursorUp => self.scroll_position.scroll_up(view_height, self.view_lines.len()),
+			Input::MoveCursorPageDown => self.scroll_position.page_down(view_height, self.view_lines.len()),
+			Input::MoveCursorPageUp => self.scroll_position.page_up(view_height, self.view_lines.len()),
+			Input::Resize => {
+				self.scroll_position.view_resize(
+					view_height,
+					view_width,
+					self.view_lines.len(),
+					self.get_max_line_length(),
+				)
+			},
+			_ => {
+				result = result.state(State::List(false));
+			},
+		}
+		result.build()
+	}
+
+	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
+		let (_, view_height) = view.get_view_size();
+
+		view.draw_title(false);
+
+		view.draw_view_lines(
+			&self.view_lines,
+			self.scroll_position.get_top_position(),
+			self.scroll_position.get_left_position(),
+			view_height - 2,
+		);
+
+		view.set_color(DisplayColor::IndicatorColor, false);
+		view.draw_str("Any key to close");
+	}
+}
+
+impl<'c> CustomCommand<'c> {
+	pub(crate) fn new(display: &'c Display, config: &'c Config) -> Self {
+		Self {
+			arguments: Ok(vec![]),
+			config,
+			display,
+			index: 0,
+			scroll_position: ScrollPosition::new(2),
+			state: CustomCommandState::Active,
+			view_lines: vec![],
+		}
+	}
+
+	fn load_arguments(&self, git_interactive: &GitInteractive) -> Result<Vec<String>, String> {
+		let command = self.config.custom_commands[self.index].command.as_str();
+		let commit = if uses_commit_placeholder(command) {
+			Some(git_interactive.load_commit_stats()?)
+		}
+		else {
+			None
+		};
+		get_command_arguments(
+			command,
+			&git_interactive.get_lines()[git_interactive.get_selected_line_index() - 1],
+			commit.as_ref(),
+			git_interactive.get_filepath().to_string_lossy().as_ref(),
+		)
+	}
+
+	fn run_command(&mut self) -> Result<(), String> {
+		let arguments = self.arguments.clone()?;
+		let config = self.config;
+		let custom_command = &config.custom_commands[self.index];
+		let mut command = Command::new(&arguments[0]);
+		command.args(&arguments[1..]);
+
+		if custom_command.show_output {
+			let output = command
+				.output()
+				.map_err(|e| format!("Unable to run command ({}):\n{}", custom_command.command, e))?;
+			let mut view_lines: Vec<ViewLine> = String::from_utf8_lossy(&output.stdout)
+				.lines()
+				.chain(String::from_utf8_lossy(&output.stderr).lines())
+				.map(|l| ViewLine::new(vec![LineSegment::new(l.replace('\t', "    ").as_str())]))
+				.collect();
+			if !output.status.success() {
+				view_lines.push(ViewLine::new(vec![LineSegment::new_with_color(
+					"Command returned non-zero exit status.",
+					DisplayColor::IndicatorColor,
+				)]));
+			}
+			self.view_lines = view_lines;
+			self.state = CustomCommandState::Output;
+			return Ok(());
+		}
+
+		let exit_status = self.display.leave_temporarily(|| {
+			command
+				.status()
+				.map_err(|e| format!("Unable to run command ({}):\n{}", custom_command.command, e))
+		})?;
+
+		if !exit_status.success() {
+			return Err(String::from("Command returned non-zero exit status."));
+		}
+		Ok(())
+	}
+
+	fn process_active(&mut self) -> ProcessResult {
+		let mut result = ProcessResultBuilder::new();
+		match self.run_command() {
+			Ok(_) => {
+				if self.state == CustomCommandState::Active {
+					result = result.state(State::List(false));
+				}
+			},
+			Err(e) => {
+				result = result.error(e.as_str(), State::List(false));
+			},
+		}
+		result.build()
+	}
+
+	fn get_max_line_length(&self) -> usize {
+		self.view_lines.iter().map(ViewLine::get_length).max().unwrap_or(0)
+	}
+}
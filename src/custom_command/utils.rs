@@ -0,0 +1,210 @@
+use crate::commit::Commit;
+use crate::external_editor::argument_tolkenizer::tolkenize;
+use crate::list::line::Line;
+
+const PLACEHOLDERS: [&str; 8] = [
+	"{action}",
+	"{author}",
+	"{command}",
+	"{files}",
+	"{full_hash}",
+	"{hash}",
+	"{subject}",
+	"{todo}",
+];
+
+const COMMIT_PLACEHOLDERS: [&str; 3] = ["{author}", "{files}", "{full_hash}"];
+
+pub(super) fn uses_commit_placeholder(command: &str) -> bool {
+	COMMIT_PLACEHOLDERS.iter().any(|p| command.contains(p))
+}
+
+fn get_placeholder_values(
+	placeholder: &str,
+	line: &Line,
+	commit: Option<&Commit>,
+	todo_path: &str,
+) -> Result<Vec<String>, String>
+{
+	if COMMIT_PLACEHOLDERS.contains(&placeholder) && commit.is_none() {
+		return Err(String::from("Cannot load commit for the selected action"));
+	}
+
+	Ok(match placeholder {
+		"{action}" => vec![line.get_action().as_string()],
+		"{author}" => vec![commit.unwrap().get_author().to_string().unwrap_or_default()],
+		"{command}" => vec![line.get_command().clone()],
+		"{files}" => {
+			match commit.unwrap().get_file_stats() {
+				Some(file_stats) => file_stats.iter().map(|f| f.get_to_name().clone()).collect(),
+				None => vec![],
+			}
+		},
+		"{full_hash}" => vec![commit.unwrap().get_hash().clone()],
+		"{hash}" => {
+			if line.get_hash().is_empty() {
+				return Err(String::from("The selected line does not have a commit hash"));
+			}
+			vec![line.get_hash().clone()]
+		},
+		"{subject}" => vec![line.get_comment().clone()],
+		"{todo}" => vec![String::from(todo_path)],
+		_ => vec![],
+	})
+}
+
+/// Replaces the placeholders in a single argument, values are never expanded a second time
+fn expand_argument(
+	argument: &str,
+	line: &Line,
+	commit: Option<&Commit>,
+	todo_path: &str,
+) -> Result<String, String>
+{
+	let mut expanded = String::new();
+	let mut rest = argument;
+	while let Some(start) = rest.find('{') {
+		expanded.push_str(&rest[..start]);
+		rest = &rest[start..];
+		match PLACEHOLDERS.iter().find(|p| rest.starts_with(*p)) {
+			Some(placeholder) => {
+				expanded.push_str(get_placeholder_values(placeholder, line, commit, todo_path)?.join(" ").as_str());
+				rest = &rest[placeholder.len()..];
+			},
+			None => {
+				expanded.push('{');
+				rest = &rest[1..];
+			},
+		}
+	}
+	expanded.push_str(rest);
+	Ok(expanded)
+}
+
+/// Splits the command into arguments and then expands the placeholders, so values are never split or re-parsed
+pub(super) fn get_command_arguments(
+	command: &str,
+	line: &Line,
+	commit: Option<&Commit>,
+	todo_path: &str,
+) -> Result<Vec<String>, String>
+{
+	let tokens = match tolkenize(command) {
+		Some(tokens) if !tokens.is_empty() => tokens,
+		_ => return Err(format!("Invalid custom command: {}", command)),
+	};
+
+	let mut arguments = vec![];
+	for token in tokens {
+		// a placeholder on its own expands to one argument per value, for example one per file
+		if PLACEHOLDERS.contains(&token.as_str()) {
+			arguments.extend(get_placeholder_values(token.as_str(), line, commit, todo_path)?);
+		}
+		else {
+			arguments.push(expand_argument(token.as_str(), line, commit, todo_path)?);
+		}
+	}
+	Ok(arguments)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_command_arguments, uses_commit_placeholder};
+	use crate::list::line::Line;
+
+	fn arguments(command: &str, line: &str) -> Result<Vec<String>, String> {
+		get_command_arguments(command, &Line::new(line).unwrap(), None, "/tmp/todo")
+	}
+
+	#[test]
+	fn get_command_arguments_no_placeholders() {
+		assert_eq!(arguments("tig show", "pick aaa comment").unwrap(), vec!["tig", "show"]);
+	}
+
+	#[test]
+	fn get_command_arguments_line_placeholders() {
+		assert_eq!(
+			arguments("echo {action} {hash} {subject} {todo}", "pick aaa a comment").unwrap(),
+			vec!["echo", "pick", "aaa", "a comment", "/tmp/todo"]
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_placeholder_within_argument() {
+		assert_eq!(
+			arguments("git show --format=%s {hash}^", "pick aaa comment").unwrap(),
+			vec!["git", "show", "--format=%s", "aaa^"]
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_quoted_argument() {
+		assert_eq!(
+			arguments("sh -c \"echo {subject} > out\"", "pick aaa a comment").unwrap(),
+			vec!["sh", "-c", "echo a comment > out"]
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_values_not_expanded_again() {
+		assert_eq!(
+			arguments("echo {subject}", "pick aaa fix {hash}").unwrap(),
+			vec!["echo", "fix {hash}"]
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_unknown_placeholder() {
+		assert_eq!(arguments("echo {unknown}", "pick aaa comment").unwrap(), vec![
+			"echo",
+			"{unknown}"
+		]);
+	}
+
+	#[test]
+	fn get_command_arguments_exec_command() {
+		assert_eq!(arguments("echo {command}", "exec make test").unwrap(), vec![
+			"echo",
+			"make test"
+		]);
+	}
+
+	#[test]
+	fn get_command_arguments_hash_without_commit() {
+		assert_eq!(
+			arguments("tig show {hash}", "break").unwrap_err(),
+			"The selected line does not have a commit hash"
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_commit_placeholder_without_commit() {
+		assert_eq!(
+			arguments("git log -p {hash} -- {files}", "pick aaa comment").unwrap_err(),
+			"Cannot load commit for the selected action"
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_invalid_command() {
+		assert_eq!(
+			arguments("echo \"unclosed", "pick aaa comment").unwrap_err(),
+			"Invalid custom command: echo \"unclosed"
+		);
+	}
+
+	#[test]
+	fn get_command_arguments_empty_command() {
+		assert_eq!(arguments("", "pick aaa comment").unwrap_err(), "Invalid custom command: ");
+	}
+
+	#[test]
+	fn uses_commit_placeholder_true() {
+		assert!(uses_commit_placeholder("git log -p {hash} -- {files}"));
+	}
+
+	#[test]
+	fn uses_commit_placeholder_false() {
+		assert!(!uses_commit_placeholder("tig show {hash}"));
+	}
+}
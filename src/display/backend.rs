@@ -0,0 +1,31 @@
+use crate::display::color::Color;
+use pancurses::{chtype, Input, MEVENT};
+
+/// The terminal operations used by `Display`, implemented by curses and by the in-memory virtual terminal
+pub(crate) trait Backend {
+	fn register_selectable_color_pairs(
+		&mut self,
+		foreground: Color,
+		background: Color,
+		selected_background: Color,
+	) -> (chtype, chtype);
+	fn erase(&self);
+	fn refresh(&self);
+	fn addstr(&self, s: &str);
+	fn move_cursor(&self, y: i32, x: i32);
+	fn attrset(&self, attributes: chtype);
+	fn attron(&self, attributes: chtype);
+	fn attroff(&self, attributes: chtype);
+	fn getch(&self) -> Option<Input>;
+	fn ungetch(&self, input: &Input);
+	fn set_timeout(&self, milliseconds: i32);
+	fn enable_mouse(&self);
+	fn get_mouse_event(&self) -> Option<MEVENT>;
+	/// Resizes to the provided size, or to the size of the terminal when both are zero
+	fn resize_term(&self, height: i32, width: i32);
+	fn get_max_y(&self) -> i32;
+	fn get_max_x(&self) -> i32;
+	fn def_prog_mode(&self);
+	fn reset_prog_mode(&self);
+	fn endwin(&self);
+}
@@ -53,6 +53,21 @@ impl TryFrom<&str> for Color {
 			"dark white" => Ok(Color::DarkWhite),
 			"dark yellow" => Ok(Color::DarkYellow),
 			"transparent" | "-1" => Ok(Color::Default),
+			_ if s.starts_with('#') => {
+				let hex = &s[1..];
+				if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+					return Err(format!(
+						"Invalid color string: {}. Hex colors must be in the form #rrggbb.",
+						s
+					));
+				}
+				// safe unwraps, since the value is validated as hex digits above
+				Ok(Color::RGB {
+					red: i16::from_str_radix(&hex[0..2], 16).unwrap(),
+					green: i16::from_str_radix(&hex[2..4], 16).unwrap(),
+					blue: i16::from_str_radix(&hex[4..6], 16).unwrap(),
+				})
+			},
 			_ => {
 				let matches: Vec<&str> = s.split(',').collect();
 
@@ -298,6 +313,31 @@ mod tests {
 		assert_eq!(Color::try_from("-2").unwrap_err(), "Invalid color value: -2");
 	}
 
+	#[test]
+	fn action_try_from_str_hex_color() {
+		assert_eq!(Color::try_from("#0a80ff").unwrap(), Color::RGB {
+			red: 10,
+			green: 128,
+			blue: 255
+		});
+	}
+
+	#[test]
+	fn action_try_from_str_hex_color_invalid_length() {
+		assert_eq!(
+			Color::try_from("#0a80f").unwrap_err(),
+			"Invalid color string: #0a80f. Hex colors must be in the form #rrggbb."
+		);
+	}
+
+	#[test]
+	fn action_try_from_str_hex_color_invalid_digit() {
+		assert_eq!(
+			Color::try_from("#0a80fg").unwrap_err(),
+			"Invalid color string: #0a80fg. Hex colors must be in the form #rrggbb."
+		);
+	}
+
 	#[test]
 	fn action_try_from_str_invalid_single_value() {
 		assert_eq!(Color::try_from("invalid").unwrap_err(), "Invalid color value: invalid");
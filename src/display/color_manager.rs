@@ -1,5 +1,5 @@
 use crate::config::theme::Theme;
-use crate::display::curses::Curses;
+use crate::display::backend::Backend;
 use crate::display::display_color::DisplayColor;
 use pancurses::chtype;
 
@@ -20,69 +20,69 @@ pub(super) struct ColorManager {
 }
 
 impl ColorManager {
-	pub(super) fn new(theme: &Theme, curses: &mut Curses) -> Self {
+	pub(super) fn new(theme: &Theme, backend: &mut dyn Backend) -> Self {
 		Self {
-			normal: curses.register_selectable_color_pairs(
+			normal: backend.register_selectable_color_pairs(
 				theme.color_foreground,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			indicator: curses.register_selectable_color_pairs(
+			indicator: backend.register_selectable_color_pairs(
 				theme.color_indicator,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_break: curses.register_selectable_color_pairs(
+			action_break: backend.register_selectable_color_pairs(
 				theme.color_action_break,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_drop: curses.register_selectable_color_pairs(
+			action_drop: backend.register_selectable_color_pairs(
 				theme.color_action_drop,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_edit: curses.register_selectable_color_pairs(
+			action_edit: backend.register_selectable_color_pairs(
 				theme.color_action_edit,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_exec: curses.register_selectable_color_pairs(
+			action_exec: backend.register_selectable_color_pairs(
 				theme.color_action_exec,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_fixup: curses.register_selectable_color_pairs(
+			action_fixup: backend.register_selectable_color_pairs(
 				theme.color_action_fixup,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_pick: curses.register_selectable_color_pairs(
+			action_pick: backend.register_selectable_color_pairs(
 				theme.color_action_pick,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_reword: curses.register_selectable_color_pairs(
+			action_reword: backend.register_selectable_color_pairs(
 				theme.color_action_reword,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			action_squash: curses.register_selectable_color_pairs(
+			action_squash: backend.register_selectable_color_pairs(
 				theme.color_action_squash,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			diff_add: curses.register_selectable_color_pairs(
+			diff_add: backend.register_selectable_color_pairs(
 				theme.color_diff_add,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			diff_change: curses.register_selectable_color_pairs(
+			diff_change: backend.register_selectable_color_pairs(
 				theme.color_diff_change,
 				theme.color_background,
 				theme.color_selected_background,
 			),
-			diff_remove: curses.register_selectable_color_pairs(
+			diff_remove: backend.register_selectable_color_pairs(
 				theme.color_diff_remove,
 				theme.color_background,
 				theme.color_selected_background,
@@ -1,9 +1,11 @@
+use crate::display::backend::Backend;
 use crate::display::color::Color;
 use crate::display::color_mode::ColorMode;
 use crate::display::utils::detect_color_mode;
 use pancurses::{
 	chtype,
 	Input,
+	MEVENT,
 	COLOR_BLACK,
 	COLOR_BLUE,
 	COLOR_CYAN,
@@ -56,6 +58,20 @@ impl Curses {
 		}
 	}
 
+	/// Describes the color support and size of the terminal
+	pub(crate) fn get_capabilities(&self) -> Vec<(&'static str, String)> {
+		vec![
+			("Color mode", format!("{:?}", self.color_mode)),
+			("Colors", pancurses::COLORS().to_string()),
+			("Color pairs", pancurses::COLOR_PAIRS().to_string()),
+			("Can change colors", pancurses::can_change_color().to_string()),
+			(
+				"Size",
+				format!("{}x{}", self.window.get_max_x(), self.window.get_max_y()),
+			),
+		]
+	}
+
 	fn init_color(&mut self, red: i16, green: i16, blue: i16) -> i16 {
 		match self.color_lookup.get(&(red, green, blue)) {
 			Some(index) => *index,
@@ -190,8 +206,10 @@ impl Curses {
 		// curses seems to init a pair for i16 but read with u64
 		pancurses::COLOR_PAIR(index as chtype)
 	}
+}
 
-	pub(super) fn register_selectable_color_pairs(
+impl Backend for Curses {
+	fn register_selectable_color_pairs(
 		&mut self,
 		foreground: Color,
 		background: Color,
@@ -206,51 +224,75 @@ impl Curses {
 		(standard_pair, standard_pair)
 	}
 
-	pub(super) fn erase(&self) {
+	fn erase(&self) {
 		self.window.erase();
 	}
 
-	pub(super) fn refresh(&self) {
+	fn refresh(&self) {
 		self.window.refresh();
 	}
 
-	pub(super) fn addstr(&self, s: &str) {
+	fn addstr(&self, s: &str) {
 		self.window.addstr(s);
 	}
 
-	pub(super) fn attrset<T: Into<chtype>>(&self, attributes: T) {
+	fn move_cursor(&self, y: i32, x: i32) {
+		self.window.mv(y, x);
+	}
+
+	fn attrset(&self, attributes: chtype) {
 		self.window.attrset(attributes);
 	}
 
-	pub(super) fn attron<T: Into<chtype>>(&self, attributes: T) {
+	fn attron(&self, attributes: chtype) {
 		self.window.attron(attributes);
 	}
 
-	pub(super) fn attroff<T: Into<chtype>>(&self, attributes: T) {
+	fn attroff(&self, attributes: chtype) {
 		self.window.attroff(attributes);
 	}
 
-	pub(super) fn getch(&self) -> Option<Input> {
+	fn getch(&self) -> Option<Input> {
 		self.window.getch()
 	}
 
-	pub(super) fn get_max_y(&self) -> i32 {
+	fn ungetch(&self, input: &Input) {
+		self.window.ungetch(input);
+	}
+
+	fn set_timeout(&self, milliseconds: i32) {
+		self.window.timeout(milliseconds);
+	}
+
+	fn enable_mouse(&self) {
+		pancurses::mousemask(pancurses::ALL_MOUSE_EVENTS, std::ptr::null_mut());
+	}
+
+	fn get_mouse_event(&self) -> Option<MEVENT> {
+		pancurses::getmouse().ok()
+	}
+
+	fn resize_term(&self, height: i32, width: i32) {
+		pancurses::resize_term(height, width);
+	}
+
+	fn get_max_y(&self) -> i32 {
 		self.window.get_max_y()
 	}
 
-	pub(super) fn get_max_x(&self) -> i32 {
+	fn get_max_x(&self) -> i32 {
 		self.window.get_max_x()
 	}
 
-	pub(super) fn def_prog_mode(&self) {
+	fn def_prog_mode(&self) {
 		pancurses::def_prog_mode();
 	}
 
-	pub(super) fn reset_prog_mode(&self) {
+	fn reset_prog_mode(&self) {
 		pancurses::reset_prog_mode();
 	}
 
-	pub(super) fn endwin(&self) {
+	fn endwin(&self) {
 		pancurses::endwin();
 	}
 }
@@ -1,50 +1,60 @@
+pub(crate) mod backend;
 pub(crate) mod color;
 mod color_manager;
 mod color_mode;
 pub(crate) mod curses;
 pub(crate) mod display_color;
 mod utils;
+#[cfg(test)]
+pub(crate) mod virtual_terminal;
 
 use crate::config::Config;
+use crate::display::backend::Backend;
 use crate::display::color_manager::ColorManager;
-use crate::display::curses::Curses;
 use crate::display::display_color::DisplayColor;
-use pancurses::Input;
+use pancurses::{Input, MEVENT};
 use std::cell::RefCell;
 
 pub(crate) struct Display<'d> {
 	color_manager: ColorManager,
-	curses: &'d Curses,
+	backend: &'d dyn Backend,
 	height: RefCell<i32>,
 	width: RefCell<i32>,
 }
 
 impl<'d> Display<'d> {
-	pub(crate) fn new(curses: &'d mut Curses, config: &'d Config) -> Self {
+	pub(crate) fn new(backend: &'d mut dyn Backend, config: &'d Config) -> Self {
+		if config.mouse {
+			backend.enable_mouse();
+		}
 		Self {
-			color_manager: ColorManager::new(&config.theme, curses),
-			curses,
-			height: RefCell::new(curses.get_max_y()),
-			width: RefCell::new(curses.get_max_x()),
+			color_manager: ColorManager::new(&config.theme, backend),
+			backend,
+			height: RefCell::new(backend.get_max_y()),
+			width: RefCell::new(backend.get_max_x()),
 		}
 	}
 
 	pub(crate) fn draw_str(&self, s: &str) {
-		self.curses.addstr(s);
+		self.backend.addstr(s);
+	}
+
+	pub(crate) fn move_cursor(&self, x: i32, y: i32) {
+		self.backend.move_cursor(y, x);
 	}
 
 	pub(crate) fn clear(&self) {
 		self.color(DisplayColor::Normal, false);
 		self.set_style(false, false, false);
-		self.curses.erase();
+		self.backend.erase();
 	}
 
 	pub(crate) fn refresh(&self) {
-		self.curses.refresh();
+		self.backend.refresh();
 	}
 
 	pub(crate) fn color(&self, color: DisplayColor, selected: bool) {
-		self.curses.attrset(self.color_manager.get_color(color, selected));
+		self.backend.attrset(self.color_manager.get_color(color, selected));
 	}
 
 	pub(crate) fn set_style(&self, dim: bool, underline: bool, reverse: bool) {
@@ -55,39 +65,53 @@ impl<'d> Display<'d> {
 
 	fn set_dim(&self, on: bool) {
 		if on {
-			self.curses.attron(pancurses::A_DIM);
+			self.backend.attron(pancurses::A_DIM);
 		}
 		else {
-			self.curses.attroff(pancurses::A_DIM);
+			self.backend.attroff(pancurses::A_DIM);
 		}
 	}
 
 	fn set_underline(&self, on: bool) {
 		// Windows uses blue text for underlined words
 		if !cfg!(windows) && on {
-			self.curses.attron(pancurses::A_UNDERLINE);
+			self.backend.attron(pancurses::A_UNDERLINE);
 		}
 		else {
-			self.curses.attroff(pancurses::A_UNDERLINE);
+			self.backend.attroff(pancurses::A_UNDERLINE);
 		}
 	}
 
 	fn set_reverse(&self, on: bool) {
 		if on {
-			self.curses.attron(pancurses::A_REVERSE);
+			self.backend.attron(pancurses::A_REVERSE);
 		}
 		else {
-			self.curses.attroff(pancurses::A_REVERSE);
+			self.backend.attroff(pancurses::A_REVERSE);
 		}
 	}
 
+	/// Gets the next input, waiting at most the provided number of milliseconds before returning `None`
+	pub(crate) fn getch_with_timeout(&self, milliseconds: i32) -> Option<Input> {
+		self.backend.set_timeout(milliseconds);
+		let input = self.getch();
+		self.backend.set_timeout(-1);
+		input
+	}
+
+	pub(crate) fn ungetch(&self, input: &Input) {
+		self.backend.ungetch(input);
+	}
+
+	pub(crate) fn get_mouse_event(&self) -> Option<MEVENT> {
+		self.backend.get_mouse_event()
+	}
+
 	pub(crate) fn getch(&self) -> Option<Input> {
-		let input = self.curses.getch();
+		let input = self.backend.getch();
 
 		if let Some(Input::KeyResize) = input {
-			pancurses::resize_term(0, 0);
-			self.height.replace(self.curses.get_max_y());
-			self.width.replace(self.curses.get_max_x());
+			self.reset_window_size();
 		}
 		input
 	}
@@ -96,17 +120,34 @@ impl<'d> Display<'d> {
 		(*self.width.borrow(), *self.height.borrow())
 	}
 
+	/// Resizes the window, used to replay a recorded session at the size it was recorded
+	pub(crate) fn set_window_size(&self, width: i32, height: i32) {
+		self.backend.resize_term(height, width);
+		self.update_window_size();
+	}
+
+	/// Resizes the window to the size of the terminal
+	pub(crate) fn reset_window_size(&self) {
+		self.backend.resize_term(0, 0);
+		self.update_window_size();
+	}
+
+	fn update_window_size(&self) {
+		self.height.replace(self.backend.get_max_y());
+		self.width.replace(self.backend.get_max_x());
+	}
+
 	/// Leaves curses mode, runs the specified callback, and re-enables curses.
 	pub(crate) fn leave_temporarily<F, T>(&self, callback: F) -> T
 	where F: FnOnce() -> T {
-		self.curses.def_prog_mode();
-		self.curses.endwin();
+		self.backend.def_prog_mode();
+		self.backend.endwin();
 		let rv = callback();
-		self.curses.reset_prog_mode();
+		self.backend.reset_prog_mode();
 		rv
 	}
 
 	pub(crate) fn end(&self) {
-		self.curses.endwin();
+		self.backend.endwin();
 	}
 }
@@ -0,0 +1,399 @@
+use crate::display::backend::Backend;
+use crate::display::color::Color;
+use pancurses::{chtype, Input, MEVENT};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use unicode_width::UnicodeWidthChar;
+
+const TAB_SIZE: usize = 8;
+
+/// An in-memory terminal that records written text in a grid of cells and reads from scripted input
+///
+/// Like a terminal, a wide character takes two cells, where the second cell is left empty, and combining characters are
+/// added to the cell of the character before them.
+pub(crate) struct VirtualTerminal {
+	attributes: Cell<chtype>,
+	cells: RefCell<Vec<Vec<(String, chtype)>>>,
+	color_pairs: Vec<(Color, Color)>,
+	cursor: Cell<(usize, usize)>,
+	frames: RefCell<Vec<Vec<String>>>,
+	height: Cell<usize>,
+	inputs: RefCell<VecDeque<Input>>,
+	mouse_events: RefCell<VecDeque<MEVENT>>,
+	pending_sizes: RefCell<VecDeque<(usize, usize)>>,
+	terminal_size: Cell<(usize, usize)>,
+	timeout: Cell<i32>,
+	width: Cell<usize>,
+}
+
+impl VirtualTerminal {
+	pub(crate) fn new(width: usize, height: usize) -> Self {
+		Self {
+			attributes: Cell::new(0),
+			cells: RefCell::new(vec![vec![(String::from(" "), 0); width]; height]),
+			// pair zero is always the default colors
+			color_pairs: vec![(Color::Default, Color::Default)],
+			cursor: Cell::new((0, 0)),
+			frames: RefCell::new(vec![]),
+			height: Cell::new(height),
+			inputs: RefCell::new(VecDeque::new()),
+			mouse_events: RefCell::new(VecDeque::new()),
+			pending_sizes: RefCell::new(VecDeque::new()),
+			terminal_size: Cell::new((width, height)),
+			timeout: Cell::new(-1),
+			width: Cell::new(width),
+		}
+	}
+
+	pub(crate) fn push_input(&self, input: Input) {
+		self.inputs.borrow_mut().push_back(input);
+	}
+
+	/// Queues each character of the string as a separate input
+	pub(crate) fn push_str(&self, s: &str) {
+		for c in s.chars() {
+			self.push_input(Input::Character(c));
+		}
+	}
+
+	pub(crate) fn push_mouse_event(&self, event: MEVENT) {
+		self.mouse_events.borrow_mut().push_back(event);
+		self.push_input(Input::KeyMouse);
+	}
+
+	/// Queues a resize of the terminal, applied when the window is next resized to the terminal size
+	pub(crate) fn push_resize(&self, width: usize, height: usize) {
+		self.pending_sizes.borrow_mut().push_back((width, height));
+		self.push_input(Input::KeyResize);
+	}
+
+	/// Gets the contents of each row, without trailing whitespace
+	pub(crate) fn get_screen(&self) -> Vec<String> {
+		self.cells
+			.borrow()
+			.iter()
+			.map(|row| row.iter().map(|(c, _)| c.as_str()).collect::<String>().trim_end().to_string())
+			.collect()
+	}
+
+	pub(crate) fn get_attributes(&self, x: usize, y: usize) -> chtype {
+		self.cells.borrow()[y][x].1
+	}
+
+	/// Gets the foreground and background colors of a cell
+	pub(crate) fn get_colors(&self, x: usize, y: usize) -> (Color, Color) {
+		// the color pair number is stored in the second byte of the attributes
+		let pair = ((self.get_attributes(x, y) & pancurses::A_COLOR) >> 8) as usize;
+		self.color_pairs[pair]
+	}
+
+	/// Gets the contents of the screen at each refresh
+	pub(crate) fn get_frames(&self) -> Vec<Vec<String>> {
+		self.frames.borrow().clone()
+	}
+
+	fn add_color_pair(&mut self, foreground: Color, background: Color) -> chtype {
+		self.color_pairs.push((foreground, background));
+		pancurses::COLOR_PAIR((self.color_pairs.len() - 1) as chtype)
+	}
+
+	fn addch(&self, c: char) {
+		let (x, y) = self.cursor.get();
+		let width = self.width.get();
+		if y >= self.height.get() {
+			return;
+		}
+		match c {
+			'\n' => {
+				let mut cells = self.cells.borrow_mut();
+				for cell in cells[y].iter_mut().skip(x) {
+					*cell = (String::from(" "), self.attributes.get());
+				}
+				self.cursor.set((0, y + 1));
+			},
+			'\t' => {
+				let spaces = TAB_SIZE - (x % TAB_SIZE);
+				for _ in 0..spaces {
+					self.addch(' ');
+				}
+			},
+			_ => {
+				let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+				let mut cells = self.cells.borrow_mut();
+				if char_width == 0 {
+					if let Some(cell) = cells[y].iter_mut().take(x).rev().find(|(content, _)| !content.is_empty()) {
+						cell.0.push(c);
+					}
+					return;
+				}
+				// a wide character that does not fit at the end of a row is drawn on the next row
+				if x + char_width > width {
+					drop(cells);
+					self.addch('\n');
+					self.addch(c);
+					return;
+				}
+				// replacing half of a wide character clears the other half
+				if cells[y][x].0.is_empty() && x > 0 {
+					cells[y][x - 1].0 = String::from(" ");
+				}
+				if x + char_width < width && cells[y][x + char_width].0.is_empty() {
+					cells[y][x + char_width].0 = String::from(" ");
+				}
+				cells[y][x] = (c.to_string(), self.attributes.get());
+				if char_width == 2 {
+					cells[y][x + 1] = (String::new(), self.attributes.get());
+				}
+				if x + char_width >= width {
+					self.cursor.set((0, y + 1));
+				}
+				else {
+					self.cursor.set((x + char_width, y));
+				}
+			},
+		}
+	}
+}
+
+impl Backend for VirtualTerminal {
+	fn register_selectable_color_pairs(
+		&mut self,
+		foreground: Color,
+		background: Color,
+		selected_background: Color,
+	) -> (chtype, chtype)
+	{
+		(
+			self.add_color_pair(foreground, background),
+			self.add_color_pair(foreground, selected_background),
+		)
+	}
+
+	fn erase(&self) {
+		for row in self.cells.borrow_mut().iter_mut() {
+			for cell in row.iter_mut() {
+				*cell = (String::from(" "), 0);
+			}
+		}
+		self.cursor.set((0, 0));
+	}
+
+	fn refresh(&self) {
+		self.frames.borrow_mut().push(self.get_screen());
+	}
+
+	fn addstr(&self, s: &str) {
+		for c in s.chars() {
+			self.addch(c);
+		}
+	}
+
+	fn move_cursor(&self, y: i32, x: i32) {
+		self.cursor.set((x as usize, y as usize));
+	}
+
+	fn attrset(&self, attributes: chtype) {
+		self.attributes.set(attributes);
+	}
+
+	fn attron(&self, attributes: chtype) {
+		self.attributes.set(self.attributes.get() | attributes);
+	}
+
+	fn attroff(&self, attributes: chtype) {
+		self.attributes.set(self.attributes.get() & !attributes);
+	}
+
+	fn getch(&self) -> Option<Input> {
+		let input = self.inputs.borrow_mut().pop_front();
+		if input.is_none() && self.timeout.get() < 0 {
+			panic!("Virtual terminal input exhausted");
+		}
+		input
+	}
+
+	fn ungetch(&self, input: &Input) {
+		self.inputs.borrow_mut().push_front(*input);
+	}
+
+	fn set_timeout(&self, milliseconds: i32) {
+		self.timeout.set(milliseconds);
+	}
+
+	fn enable_mouse(&self) {}
+
+	fn get_mouse_event(&self) -> Option<MEVENT> {
+		self.mouse_events.borrow_mut().pop_front()
+	}
+
+	fn resize_term(&self, height: i32, width: i32) {
+		let (width, height) = if height == 0 && width == 0 {
+			if let Some(size) = self.pending_sizes.borrow_mut().pop_front() {
+				self.terminal_size.set(size);
+			}
+			self.terminal_size.get()
+		}
+		else {
+			(width as usize, height as usize)
+		};
+		self.width.set(width);
+		self.height.set(height);
+		self.cells.replace(vec![vec![(String::from(" "), 0); width]; height]);
+		self.cursor.set((0, 0));
+	}
+
+	fn get_max_y(&self) -> i32 {
+		self.height.get() as i32
+	}
+
+	fn get_max_x(&self) -> i32 {
+		self.width.get() as i32
+	}
+
+	fn def_prog_mode(&self) {}
+
+	fn reset_prog_mode(&self) {}
+
+	fn endwin(&self) {}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::VirtualTerminal;
+	use crate::display::backend::Backend;
+	use crate::display::color::Color;
+	use pancurses::Input;
+
+	#[test]
+	fn virtual_terminal_addstr() {
+		let terminal = VirtualTerminal::new(10, 3);
+		terminal.addstr("abc");
+		terminal.addstr("def");
+		assert_eq!(terminal.get_screen(), vec!["abcdef", "", ""]);
+	}
+
+	#[test]
+	fn virtual_terminal_addstr_wraps() {
+		let terminal = VirtualTerminal::new(4, 3);
+		terminal.addstr("abcdefghijklmnop");
+		assert_eq!(terminal.get_screen(), vec!["abcd", "efgh", "ijkl"]);
+	}
+
+	#[test]
+	fn virtual_terminal_addstr_wide_and_combining() {
+		let terminal = VirtualTerminal::new(5, 3);
+		terminal.addstr("a修复e\u{301}🎉");
+		assert_eq!(terminal.get_screen(), vec!["a修复", "e\u{301}🎉", ""]);
+		terminal.move_cursor(0, 2);
+		terminal.addstr("x");
+		assert_eq!(terminal.get_screen()[0], "a x复");
+	}
+
+	#[test]
+	fn virtual_terminal_addstr_new_line_and_tab() {
+		let terminal = VirtualTerminal::new(12, 3);
+		terminal.addstr("abc\tx\ndef");
+		assert_eq!(terminal.get_screen(), vec!["abc     x", "def", ""]);
+	}
+
+	#[test]
+	fn virtual_terminal_move_cursor() {
+		let terminal = VirtualTerminal::new(10, 2);
+		terminal.addstr("abc");
+		terminal.move_cursor(1, 4);
+		terminal.addstr("x");
+		assert_eq!(terminal.get_screen(), vec!["abc", "    x"]);
+	}
+
+	#[test]
+	fn virtual_terminal_erase() {
+		let terminal = VirtualTerminal::new(10, 2);
+		terminal.addstr("abc");
+		terminal.erase();
+		terminal.addstr("x");
+		assert_eq!(terminal.get_screen(), vec!["x", ""]);
+	}
+
+	#[test]
+	fn virtual_terminal_attributes() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.attron(pancurses::A_DIM);
+		terminal.addstr("a");
+		terminal.attroff(pancurses::A_DIM);
+		terminal.addstr("b");
+		assert_eq!(terminal.get_attributes(0, 0), pancurses::A_DIM);
+		assert_eq!(terminal.get_attributes(1, 0), 0);
+	}
+
+	#[test]
+	fn virtual_terminal_colors() {
+		let mut terminal = VirtualTerminal::new(10, 1);
+		let (standard, selected) =
+			terminal.register_selectable_color_pairs(Color::LightRed, Color::Default, Color::Index(237));
+		terminal.attrset(standard);
+		terminal.addstr("a");
+		terminal.attrset(selected);
+		terminal.addstr("b");
+		assert_eq!(terminal.get_colors(0, 0), (Color::LightRed, Color::Default));
+		assert_eq!(terminal.get_colors(1, 0), (Color::LightRed, Color::Index(237)));
+		assert_eq!(terminal.get_colors(2, 0), (Color::Default, Color::Default));
+	}
+
+	#[test]
+	fn virtual_terminal_scripted_input() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.push_str("ab");
+		terminal.push_input(Input::KeyDown);
+		terminal.ungetch(&Input::Character('z'));
+		assert_eq!(terminal.getch(), Some(Input::Character('z')));
+		assert_eq!(terminal.getch(), Some(Input::Character('a')));
+		assert_eq!(terminal.getch(), Some(Input::Character('b')));
+		assert_eq!(terminal.getch(), Some(Input::KeyDown));
+	}
+
+	#[test]
+	fn virtual_terminal_input_timeout() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.set_timeout(10);
+		assert_eq!(terminal.getch(), None);
+	}
+
+	#[test]
+	#[should_panic(expected = "Virtual terminal input exhausted")]
+	fn virtual_terminal_input_exhausted() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.getch();
+	}
+
+	#[test]
+	fn virtual_terminal_resize() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.push_resize(20, 5);
+		assert_eq!(terminal.getch(), Some(Input::KeyResize));
+		terminal.resize_term(0, 0);
+		assert_eq!(terminal.get_max_x(), 20);
+		assert_eq!(terminal.get_max_y(), 5);
+		assert_eq!(terminal.get_screen().len(), 5);
+	}
+
+	#[test]
+	fn virtual_terminal_resize_to_size() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.resize_term(3, 8);
+		assert_eq!(terminal.get_max_x(), 8);
+		assert_eq!(terminal.get_max_y(), 3);
+		terminal.resize_term(0, 0);
+		assert_eq!(terminal.get_max_x(), 10);
+		assert_eq!(terminal.get_max_y(), 1);
+	}
+
+	#[test]
+	fn virtual_terminal_frames() {
+		let terminal = VirtualTerminal::new(10, 1);
+		terminal.addstr("a");
+		terminal.refresh();
+		terminal.addstr("b");
+		terminal.refresh();
+		assert_eq!(terminal.get_frames(), vec![vec!["a"], vec!["ab"]]);
+	}
+}
@@ -0,0 +1,117 @@
+use crate::constants::{EXEC_HISTORY_FILE_NAME, EXEC_HISTORY_LIMIT};
+use git2::Repository;
+use std::fs;
+use std::path::PathBuf;
+
+/// The previously entered exec commands, oldest first
+pub(crate) struct ExecHistory {
+	entries: Vec<String>,
+	filepath: Option<PathBuf>,
+}
+
+fn parse_entries(contents: &str) -> Vec<String> {
+	let entries: Vec<String> = contents
+		.lines()
+		.filter(|l| !l.trim().is_empty())
+		.map(String::from)
+		.collect();
+	let skip = entries.len().saturating_sub(EXEC_HISTORY_LIMIT);
+	entries.into_iter().skip(skip).collect()
+}
+
+impl ExecHistory {
+	/// Creates a history that is not persisted
+	pub(crate) fn new() -> Self {
+		Self {
+			entries: vec![],
+			filepath: None,
+		}
+	}
+
+	/// Loads the history from the git directory of the current repository, so it is shared across rebases
+	pub(crate) fn load() -> Self {
+		match Repository::open_from_env() {
+			Ok(repository) => Self::from_filepath(repository.path().join(EXEC_HISTORY_FILE_NAME)),
+			Err(_) => Self::new(),
+		}
+	}
+
+	fn from_filepath(filepath: PathBuf) -> Self {
+		// a missing or unreadable history file starts an empty history, it is created on the first save
+		let entries = fs::read_to_string(&filepath)
+			.map(|contents| parse_entries(contents.as_str()))
+			.unwrap_or_default();
+		Self {
+			entries,
+			filepath: Some(filepath),
+		}
+	}
+
+	pub(crate) fn get_entries(&self) -> &Vec<String> {
+		&self.entries
+	}
+
+	/// Adds a command as the newest entry, removing an older duplicate, and saves the history
+	pub(crate) fn add(&mut self, command: &str) -> Result<(), String> {
+		if command.trim().is_empty() {
+			return Ok(());
+		}
+		self.entries.retain(|entry| entry != command);
+		self.entries.push(String::from(command));
+		let skip = self.entries.len().saturating_sub(EXEC_HISTORY_LIMIT);
+		self.entries.drain(..skip);
+
+		match self.filepath {
+			Some(ref filepath) => {
+				let mut contents = self.entries.join("\n");
+				contents.push('\n');
+				fs::write(filepath, contents).map_err(|why| {
+					format!("Error writing exec history, {}\nReason: {}", filepath.display(), why)
+				})
+			},
+			None => Ok(()),
+		}
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{parse_entries, ExecHistory};
+	use crate::constants::EXEC_HISTORY_LIMIT;
+	use crate::temp_path::TempPath;
+
+	#[test]
+	fn exec_history_parse_entries() {
+		assert_eq!(parse_entries("make\n\ncargo test\n"), vec!["make", "cargo test"]);
+	}
+
+	#[test]
+	fn exec_history_parse_entries_limit() {
+		let contents: String = (0..EXEC_HISTORY_LIMIT + 5).map(|i| format!("make {}\n", i)).collect();
+		let entries = parse_entries(contents.as_str());
+		assert_eq!(entries.len(), EXEC_HISTORY_LIMIT);
+		assert_eq!(entries[0], "make 5");
+	}
+
+	#[test]
+	fn exec_history_add_moves_duplicate_to_newest() {
+		let mut history = ExecHistory::new();
+		history.add("make").unwrap();
+		history.add("cargo test").unwrap();
+		history.add("  ").unwrap();
+		history.add("make").unwrap();
+		assert_eq!(history.get_entries(), &vec!["cargo test", "make"]);
+	}
+
+	#[test]
+	fn exec_history_save_and_load() {
+		let filepath = TempPath::new("exec-history");
+		let mut history = ExecHistory::from_filepath(filepath.get_path().to_path_buf());
+		history.add("make").unwrap();
+		history.add("cargo test").unwrap();
+		assert_eq!(
+			ExecHistory::from_filepath(filepath.get_path().to_path_buf()).get_entries(),
+			&vec!["make", "cargo test"]
+		);
+	}
+}
@@ -0,0 +1,350 @@
+use crate::input::Input;
+use crate::view::utils::get_grapheme_width;
+use unicode_segmentation::UnicodeSegmentation;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Kill {
+	Backward,
+	Forward,
+	None,
+}
+
+fn is_word_grapheme(grapheme: &str) -> bool {
+	grapheme.chars().any(char::is_alphanumeric)
+}
+
+fn is_whitespace_grapheme(grapheme: &str) -> bool {
+	grapheme.chars().all(char::is_whitespace)
+}
+
+/// A single line of text with a cursor, supporting the common readline movement and kill commands
+pub(crate) struct LineEditor {
+	content: String,
+	cursor_position: usize,
+	kill_buffer: String,
+	last_kill: Kill,
+	scroll_position: usize,
+}
+
+impl LineEditor {
+	pub(crate) fn new() -> Self {
+		Self {
+			content: String::from(""),
+			cursor_position: 0,
+			kill_buffer: String::from(""),
+			last_kill: Kill::None,
+			scroll_position: 0,
+		}
+	}
+
+	/// Replaces the content, placing the cursor at the end, the kill buffer is kept
+	pub(crate) fn set_content(&mut self, content: &str) {
+		self.content = String::from(content);
+		self.cursor_position = self.get_graphemes().len();
+		self.last_kill = Kill::None;
+		self.scroll_position = 0;
+	}
+
+	pub(crate) fn get_content(&self) -> &str {
+		self.content.as_str()
+	}
+
+	pub(crate) fn get_cursor_position(&self) -> usize {
+		self.cursor_position
+	}
+
+	pub(crate) fn set_cursor_position(&mut self, cursor_position: usize) {
+		self.cursor_position = cursor_position.min(self.get_graphemes().len());
+	}
+
+	pub(crate) fn get_scroll_position(&self) -> usize {
+		self.scroll_position
+	}
+
+	pub(crate) fn get_graphemes(&self) -> Vec<&str> {
+		UnicodeSegmentation::graphemes(self.content.as_str(), true).collect()
+	}
+
+	/// Applies an editing input, returning false for inputs the editor does not handle
+	pub(crate) fn handle_input(&mut self, input: Input) -> bool {
+		let length = self.get_graphemes().len();
+		let kill = match input {
+			Input::Character(c) => {
+				self.insert(c.to_string().as_str());
+				Kill::None
+			},
+			Input::Backspace => {
+				if self.cursor_position > 0 {
+					self.remove(self.cursor_position - 1, self.cursor_position);
+					self.cursor_position -= 1;
+				}
+				Kill::None
+			},
+			Input::Delete => {
+				if self.cursor_position < length {
+					self.remove(self.cursor_position, self.cursor_position + 1);
+				}
+				Kill::None
+			},
+			Input::MoveCursorLeft => {
+				self.cursor_position = self.cursor_position.saturating_sub(1);
+				Kill::None
+			},
+			Input::MoveCursorRight => {
+				if self.cursor_position < length {
+					self.cursor_position += 1;
+				}
+				Kill::None
+			},
+			Input::MoveCursorHome => {
+				self.cursor_position = 0;
+				Kill::None
+			},
+			Input::MoveCursorEnd => {
+				self.cursor_position = length;
+				Kill::None
+			},
+			Input::MoveCursorWordLeft => {
+				self.cursor_position = self.get_word_start();
+				Kill::None
+			},
+			Input::MoveCursorWordRight => {
+				self.cursor_position = self.get_word_end();
+				Kill::None
+			},
+			Input::KillWordBefore => {
+				let start = self.get_whitespace_word_start();
+				self.kill(start, self.cursor_position, Kill::Backward);
+				self.cursor_position = start;
+				Kill::Backward
+			},
+			Input::KillToStart => {
+				self.kill(0, self.cursor_position, Kill::Backward);
+				self.cursor_position = 0;
+				Kill::Backward
+			},
+			Input::KillToEnd => {
+				self.kill(self.cursor_position, length, Kill::Forward);
+				Kill::Forward
+			},
+			Input::Yank => {
+				let text = self.kill_buffer.clone();
+				self.insert(text.as_str());
+				Kill::None
+			},
+			_ => return false,
+		};
+		self.last_kill = kill;
+		true
+	}
+
+	/// Scrolls horizontally so the cursor, and the grapheme under it, fit within the view width
+	pub(crate) fn update_scroll_position(&mut self, view_width: usize) {
+		let graphemes = self.get_graphemes();
+		let pointer = self.cursor_position;
+		let mut scroll_position = self.scroll_position.min(pointer);
+		let cursor_width = graphemes.get(pointer).map(|g| get_grapheme_width(g)).unwrap_or(1);
+		let mut width_to_cursor = cursor_width
+			+ graphemes[scroll_position..pointer]
+				.iter()
+				.map(|g| get_grapheme_width(g))
+				.sum::<usize>();
+		while width_to_cursor > view_width && scroll_position < pointer {
+			width_to_cursor -= get_grapheme_width(graphemes[scroll_position]);
+			scroll_position += 1;
+		}
+		self.scroll_position = scroll_position;
+	}
+
+	fn get_byte_offset(&self, grapheme_index: usize) -> usize {
+		UnicodeSegmentation::grapheme_indices(self.content.as_str(), true)
+			.nth(grapheme_index)
+			.map(|(offset, _)| offset)
+			.unwrap_or_else(|| self.content.len())
+	}
+
+	fn insert(&mut self, text: &str) {
+		let offset = self.get_byte_offset(self.cursor_position);
+		let prefix = format!("{}{}", &self.content[..offset], text);
+		// a combining character joins the grapheme before it, so the cursor is placed by the new grapheme count
+		self.cursor_position = UnicodeSegmentation::graphemes(prefix.as_str(), true).count();
+		self.content = format!("{}{}", prefix, &self.content[offset..]);
+	}
+
+	fn remove(&mut self, start: usize, end: usize) -> String {
+		let start_offset = self.get_byte_offset(start);
+		let end_offset = self.get_byte_offset(end);
+		self.content.drain(start_offset..end_offset).collect()
+	}
+
+	/// Removes the text into the kill buffer, consecutive kills are accumulated so they can be yanked together
+	fn kill(&mut self, start: usize, end: usize, direction: Kill) {
+		let text = self.remove(start, end);
+		if self.last_kill == Kill::None {
+			self.kill_buffer = text;
+		}
+		else if direction == Kill::Backward {
+			self.kill_buffer = format!("{}{}", text, self.kill_buffer);
+		}
+		else {
+			self.kill_buffer.push_str(text.as_str());
+		}
+	}
+
+	fn get_word_start(&self) -> usize {
+		let graphemes = self.get_graphemes();
+		let mut index = self.cursor_position;
+		while index > 0 && !is_word_grapheme(graphemes[index - 1]) {
+			index -= 1;
+		}
+		while index > 0 && is_word_grapheme(graphemes[index - 1]) {
+			index -= 1;
+		}
+		index
+	}
+
+	fn get_word_end(&self) -> usize {
+		let graphemes = self.get_graphemes();
+		let mut index = self.cursor_position;
+		while index < graphemes.len() && !is_word_grapheme(graphemes[index]) {
+			index += 1;
+		}
+		while index < graphemes.len() && is_word_grapheme(graphemes[index]) {
+			index += 1;
+		}
+		index
+	}
+
+	/// Gets the start of the whitespace delimited word before the cursor, like the Control+w of a shell
+	fn get_whitespace_word_start(&self) -> usize {
+		let graphemes = self.get_graphemes();
+		let mut index = self.cursor_position;
+		while index > 0 && is_whitespace_grapheme(graphemes[index - 1]) {
+			index -= 1;
+		}
+		while index > 0 && !is_whitespace_grapheme(graphemes[index - 1]) {
+			index -= 1;
+		}
+		index
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::LineEditor;
+	use crate::input::Input;
+
+	fn create_editor(content: &str, cursor_position: usize) -> LineEditor {
+		let mut editor = LineEditor::new();
+		editor.set_content(content);
+		editor.cursor_position = cursor_position;
+		editor
+	}
+
+	fn apply(editor: &mut LineEditor, inputs: &[Input]) {
+		for input in inputs {
+			assert!(editor.handle_input(*input));
+		}
+	}
+
+	#[test]
+	fn line_editor_insert_and_delete() {
+		let mut editor = create_editor("mke", 1);
+		apply(&mut editor, &[Input::Character('a'), Input::Delete, Input::Backspace]);
+		assert_eq!(editor.get_content(), "me");
+		assert_eq!(editor.get_cursor_position(), 1);
+	}
+
+	#[test]
+	fn line_editor_insert_combining_character() {
+		let mut editor = create_editor("cafe", 4);
+		apply(&mut editor, &[Input::Character('\u{301}'), Input::Character('s')]);
+		assert_eq!(editor.get_content(), "cafe\u{301}s");
+		assert_eq!(editor.get_cursor_position(), 5);
+	}
+
+	#[test]
+	fn line_editor_home_and_end() {
+		let mut editor = create_editor("make test", 4);
+		apply(&mut editor, &[Input::MoveCursorHome]);
+		assert_eq!(editor.get_cursor_position(), 0);
+		apply(&mut editor, &[Input::MoveCursorEnd]);
+		assert_eq!(editor.get_cursor_position(), 9);
+	}
+
+	#[test]
+	fn line_editor_word_movement() {
+		let mut editor = create_editor("cargo test --all-targets", 24);
+		apply(&mut editor, &[Input::MoveCursorWordLeft]);
+		assert_eq!(editor.get_cursor_position(), 17);
+		apply(&mut editor, &[Input::MoveCursorWordLeft, Input::MoveCursorWordLeft]);
+		assert_eq!(editor.get_cursor_position(), 6);
+		apply(&mut editor, &[Input::MoveCursorWordRight]);
+		assert_eq!(editor.get_cursor_position(), 10);
+		apply(&mut editor, &[Input::MoveCursorWordRight]);
+		assert_eq!(editor.get_cursor_position(), 16);
+	}
+
+	#[test]
+	fn line_editor_kill_word_before() {
+		let mut editor = create_editor("cargo test --all  ", 18);
+		apply(&mut editor, &[Input::KillWordBefore]);
+		assert_eq!(editor.get_content(), "cargo test ");
+		assert_eq!(editor.get_cursor_position(), 11);
+	}
+
+	#[test]
+	fn line_editor_kill_to_start_and_end() {
+		let mut editor = create_editor("cargo test --all", 6);
+		apply(&mut editor, &[Input::KillToEnd]);
+		assert_eq!(editor.get_content(), "cargo ");
+		apply(&mut editor, &[Input::MoveCursorLeft, Input::KillToStart]);
+		assert_eq!(editor.get_content(), " ");
+		assert_eq!(editor.get_cursor_position(), 0);
+	}
+
+	#[test]
+	fn line_editor_yank() {
+		let mut editor = create_editor("cargo test", 10);
+		apply(&mut editor, &[Input::KillWordBefore, Input::MoveCursorHome, Input::Yank]);
+		assert_eq!(editor.get_content(), "testcargo ");
+		assert_eq!(editor.get_cursor_position(), 4);
+	}
+
+	#[test]
+	fn line_editor_consecutive_kills_accumulate() {
+		let mut editor = create_editor("one two three", 13);
+		apply(&mut editor, &[Input::KillWordBefore, Input::KillWordBefore, Input::Yank]);
+		assert_eq!(editor.get_content(), "one two three");
+
+		let mut editor = create_editor("one two three", 13);
+		apply(&mut editor, &[
+			Input::KillWordBefore,
+			Input::MoveCursorLeft,
+			Input::KillWordBefore,
+			Input::MoveCursorEnd,
+			Input::Yank,
+		]);
+		assert_eq!(editor.get_content(), "one  two");
+	}
+
+	#[test]
+	fn line_editor_unhandled_input() {
+		let mut editor = create_editor("make", 4);
+		assert!(!editor.handle_input(Input::Enter));
+		assert!(!editor.handle_input(Input::MoveCursorUp));
+	}
+
+	#[test]
+	fn line_editor_scroll_keeps_position() {
+		let mut editor = create_editor("abcdefghij", 10);
+		editor.update_scroll_position(5);
+		assert_eq!(editor.get_scroll_position(), 6);
+		apply(&mut editor, &[Input::MoveCursorLeft, Input::MoveCursorLeft]);
+		editor.update_scroll_position(5);
+		assert_eq!(editor.get_scroll_position(), 6);
+		apply(&mut editor, &[Input::MoveCursorHome]);
+		editor.update_scroll_position(5);
+		assert_eq!(editor.get_scroll_position(), 0);
+	}
+}
@@ -1,4 +1,10 @@
+pub(crate) mod history;
+pub(crate) mod line_editor;
+
+use crate::commit::Commit;
 use crate::display::display_color::DisplayColor;
+use crate::edit::history::ExecHistory;
+use crate::edit::line_editor::LineEditor;
 use crate::git_interactive::GitInteractive;
 use crate::input::input_handler::{InputHandler, InputMode};
 use crate::input::Input;
@@ -6,40 +12,87 @@ use crate::process::handle_input_result::HandleInputResult;
 use crate::process::process_module::ProcessModule;
 use crate::process::process_result::{ProcessResult, ProcessResultBuilder};
 use crate::process::state::State;
+use crate::view::utils::get_grapheme_width;
 use crate::view::View;
-use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
 enum EditState {
 	Active,
+	Cancel,
 	Finish,
 }
 
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EditTarget {
+	Command,
+	Hash,
+}
+
 pub(crate) struct Edit {
-	content: String,
-	cursor_position: usize,
+	draft: String,
+	editor: LineEditor,
+	exec_history: ExecHistory,
+	history: Vec<String>,
+	history_index: Option<usize>,
 	state: EditState,
+	target: EditTarget,
 }
 
 impl ProcessModule for Edit {
-	fn activate(&mut self, _state: State, application: &GitInteractive) {
+	fn activate(&mut self, state: State, application: &GitInteractive) {
 		self.state = EditState::Active;
-		self.content = application.get_selected_line_edit_content().clone();
-		self.cursor_position = UnicodeSegmentation::graphemes(self.content.as_str(), true).count();
+		if let State::EditHash = state {
+			self.target = EditTarget::Hash;
+			self.editor.set_content(application.get_selected_line_hash());
+			// the exec history has nothing to offer when changing the commit of a line
+			self.history.clear();
+		}
+		else {
+			self.target = EditTarget::Command;
+			self.editor.set_content(application.get_selected_line_edit_content());
+			self.history = self.exec_history.get_entries().clone();
+		}
+		self.history_index = None;
 	}
 
 	fn deactivate(&mut self) {
-		self.content.clear();
-		self.cursor_position = 0;
+		self.editor.set_content("");
+		self.draft.clear();
+		self.history.clear();
 	}
 
-	fn process(&mut self, git_interactive: &mut GitInteractive, _view: &View) -> ProcessResult {
+	fn process(&mut self, git_interactive: &mut GitInteractive, view: &View) -> ProcessResult {
 		let mut result = ProcessResultBuilder::new();
 		match self.state {
-			EditState::Active => {},
+			EditState::Active => {
+				let (view_width, _) = view.get_view_size();
+				self.editor.update_scroll_position(view_width);
+			},
+			EditState::Cancel => {
+				result = result.state(State::List(false));
+			},
 			EditState::Finish => {
-				git_interactive.edit_selected_line(self.content.as_str());
 				result = result.state(State::List(false));
+				match self.target {
+					EditTarget::Command => {
+						git_interactive.edit_selected_line(self.editor.get_content());
+						if let Err(message) = self.exec_history.add(self.editor.get_content()) {
+							result = result.error(message.as_str(), State::List(false));
+						}
+					},
+					EditTarget::Hash => {
+						match Commit::resolve_revision(
+							self.editor.get_content(),
+							git_interactive.get_selected_line_hash(),
+						) {
+							Ok(Some((hash, subject))) => {
+								git_interactive.set_selected_line_target(hash.as_str(), subject.as_str())
+							},
+							Ok(None) => {},
+							Err(message) => result = result.error(message.as_str(), State::List(false)),
+						}
+					},
+				}
 			},
 		};
 		result.build()
@@ -52,63 +105,21 @@ impl ProcessModule for Edit {
 		_view: &View,
 	) -> HandleInputResult
 	{
-		if self.state == EditState::Finish {
+		if self.state != EditState::Active {
 			return HandleInputResult::new(Input::Enter);
 		}
 		let mut input;
 		loop {
 			input = input_handler.get_input(InputMode::Raw);
 			match input {
-				Input::Character(c) => {
-					let start = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.take(self.cursor_position)
-						.collect::<String>();
-					let end = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.skip(self.cursor_position)
-						.collect::<String>();
-					self.content = format!("{}{}{}", start, c, end);
-					self.cursor_position += 1;
-				},
-				Input::Backspace => {
-					if self.cursor_position == 0 {
-						break;
-					}
-					let start = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.take(self.cursor_position - 1)
-						.collect::<String>();
-					let end = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.skip(self.cursor_position)
-						.collect::<String>();
-					self.content = format!("{}{}", start, end);
-					self.cursor_position -= 1;
-				},
-				Input::Delete => {
-					let length = UnicodeSegmentation::graphemes(self.content.as_str(), true).count();
-					if self.cursor_position == length {
-						break;
-					}
-					let start = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.take(self.cursor_position)
-						.collect::<String>();
-					let end = UnicodeSegmentation::graphemes(self.content.as_str(), true)
-						.skip(self.cursor_position + 1)
-						.collect::<String>();
-					self.content = format!("{}{}", start, end);
-				},
-				Input::MoveCursorRight => {
-					let length = UnicodeSegmentation::graphemes(self.content.as_str(), true).count();
-					if self.cursor_position < length {
-						self.cursor_position += 1;
-					}
-				},
-				Input::MoveCursorLeft => {
-					if self.cursor_position != 0 {
-						self.cursor_position -= 1;
-					}
-				},
 				Input::Enter => self.state = EditState::Finish,
+				Input::Escape => self.state = EditState::Cancel,
+				Input::MoveCursorUp => self.show_previous_history_entry(),
+				Input::MoveCursorDown => self.show_next_history_entry(),
 				_ => {
-					continue;
+					if !self.editor.handle_input(input) {
+						continue;
+					}
 				},
 			}
 			break;
@@ -117,27 +128,31 @@ impl ProcessModule for Edit {
 	}
 
 	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
-		let line = self.content.as_str();
-		let pointer = self.cursor_position;
+		let (view_width, _) = view.get_view_size();
+		let graphemes = self.editor.get_graphemes();
+		let pointer = self.editor.get_cursor_position();
 
 		view.draw_title(false);
 		view.set_style(false, true, false);
 		view.set_color(DisplayColor::Normal, false);
 
-		// this could probably be made way more efficient
-		let graphemes = UnicodeSegmentation::graphemes(line, true);
-		let segment_length = graphemes.clone().count();
-		for (counter, c) in graphemes.enumerate() {
-			if counter == pointer {
+		let start = self.editor.get_scroll_position();
+		let mut width = 0;
+		for (index, grapheme) in graphemes.iter().enumerate().skip(start) {
+			width += get_grapheme_width(grapheme);
+			if width > view_width {
+				break;
+			}
+			if index == pointer {
 				view.set_style(false, true, false);
-				view.draw_str(c);
+				view.draw_str(grapheme);
 				view.set_style(false, false, false);
 			}
 			else {
-				view.draw_str(c);
+				view.draw_str(grapheme);
 			}
 		}
-		if pointer >= segment_length {
+		if pointer >= graphemes.len() {
 			view.set_style(false, true, false);
 			view.draw_str(" ");
 			view.set_style(false, false, false);
@@ -145,16 +160,49 @@ impl ProcessModule for Edit {
 
 		view.draw_str("\n\n");
 		view.set_color(DisplayColor::IndicatorColor, false);
-		view.draw_str("Enter to finish");
+		view.draw_str("Enter to finish, Escape to cancel");
 	}
 }
 
 impl Edit {
-	pub(crate) fn new() -> Self {
+	pub(crate) fn new(exec_history: ExecHistory) -> Self {
 		Self {
-			content: String::from(""),
-			cursor_position: 0,
+			draft: String::from(""),
+			editor: LineEditor::new(),
+			exec_history,
+			history: vec![],
+			history_index: None,
 			state: EditState::Active,
+			target: EditTarget::Command,
+		}
+	}
+
+	/// Shows the previous exec command in the history, keeping the current content to return to
+	fn show_previous_history_entry(&mut self) {
+		let index = match self.history_index {
+			Some(index) => index.saturating_sub(1),
+			None if self.history.is_empty() => return,
+			None => {
+				self.draft = String::from(self.editor.get_content());
+				self.history.len() - 1
+			},
+		};
+		self.history_index = Some(index);
+		self.editor.set_content(self.history[index].as_str());
+	}
+
+	/// Shows the next exec command in the history, or the content from before browsing the history
+	fn show_next_history_entry(&mut self) {
+		match self.history_index {
+			Some(index) if index + 1 < self.history.len() => {
+				self.history_index = Some(index + 1);
+				self.editor.set_content(self.history[index + 1].as_str());
+			},
+			Some(_) => {
+				self.history_index = None;
+				self.editor.set_content(self.draft.as_str());
+			},
+			None => {},
 		}
 	}
 }
@@ -0,0 +1,220 @@
+use crate::display::display_color::DisplayColor;
+use crate::edit::line_editor::LineEditor;
+use crate::input::Input;
+use crate::view::line_segment::LineSegment;
+use crate::view::view_line::ViewLine;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// A multiple line editor, the row with the cursor is edited with a line editor
+pub(super) struct MessageEditor {
+	editor: LineEditor,
+	lines: Vec<String>,
+	row: usize,
+	top: usize,
+}
+
+impl MessageEditor {
+	pub(super) fn new() -> Self {
+		Self {
+			editor: LineEditor::new(),
+			lines: vec![String::from("")],
+			row: 0,
+			top: 0,
+		}
+	}
+
+	/// Replaces the content, placing the cursor at the start of the first line
+	pub(super) fn set_content(&mut self, content: &str) {
+		self.lines = content.trim_end().lines().map(String::from).collect();
+		if self.lines.is_empty() {
+			self.lines.push(String::from(""));
+		}
+		self.row = 0;
+		self.top = 0;
+		self.editor.set_content(self.lines[0].as_str());
+		self.editor.set_cursor_position(0);
+	}
+
+	pub(super) fn get_content(&self) -> String {
+		self.lines.join("\n")
+	}
+
+	/// Applies an editing input, returning false for inputs the editor does not handle
+	pub(super) fn handle_input(&mut self, input: Input) -> bool {
+		let cursor_position = self.editor.get_cursor_position();
+		let length = self.editor.get_graphemes().len();
+		match input {
+			Input::Enter => {
+				let graphemes = self.editor.get_graphemes();
+				let before = graphemes[..cursor_position].concat();
+				let after = graphemes[cursor_position..].concat();
+				self.lines[self.row] = before;
+				self.lines.insert(self.row + 1, after);
+				self.set_row(self.row + 1, 0);
+			},
+			Input::Backspace if cursor_position == 0 && self.row > 0 => {
+				let line = self.lines.remove(self.row);
+				self.row -= 1;
+				let previous_length = self.get_line_length(self.row);
+				self.lines[self.row].push_str(line.as_str());
+				self.set_row(self.row, previous_length);
+			},
+			Input::Delete if cursor_position == length && self.row + 1 < self.lines.len() => {
+				let line = self.lines.remove(self.row + 1);
+				self.lines[self.row].push_str(line.as_str());
+				self.set_row(self.row, cursor_position);
+			},
+			Input::MoveCursorUp => {
+				if self.row > 0 {
+					self.set_row(self.row - 1, cursor_position);
+				}
+			},
+			Input::MoveCursorDown => {
+				if self.row + 1 < self.lines.len() {
+					self.set_row(self.row + 1, cursor_position);
+				}
+			},
+			_ => {
+				if !self.editor.handle_input(input) {
+					return false;
+				}
+				self.lines[self.row] = String::from(self.editor.get_content());
+			},
+		}
+		true
+	}
+
+	/// Scrolls so the cursor is shown within the view, the width excludes the scroll bar when one is shown
+	pub(super) fn update_scroll_position(&mut self, view_width: usize, view_height: usize) {
+		let view_width = if self.lines.len() > view_height {
+			view_width - 1
+		}
+		else {
+			view_width
+		};
+		self.editor.update_scroll_position(view_width);
+		if self.row < self.top {
+			self.top = self.row;
+		}
+		else if view_height > 0 && self.row >= self.top + view_height {
+			self.top = self.row + 1 - view_height;
+		}
+	}
+
+	pub(super) fn get_top_position(&self) -> usize {
+		self.top
+	}
+
+	pub(super) fn get_view_lines(&self) -> Vec<ViewLine> {
+		self.lines
+			.iter()
+			.enumerate()
+			.map(|(index, line)| {
+				if index == self.row {
+					self.get_cursor_view_line()
+				}
+				else {
+					ViewLine::new(vec![LineSegment::new(line.as_str())])
+				}
+			})
+			.collect()
+	}
+
+	fn get_cursor_view_line(&self) -> ViewLine {
+		let graphemes = self.editor.get_graphemes();
+		let start = self.editor.get_scroll_position();
+		let pointer = self.editor.get_cursor_position();
+		// the cursor is drawn as an underlined space after the end of the line
+		let (cursor, after) = match graphemes.get(pointer) {
+			Some(grapheme) => (*grapheme, graphemes[pointer + 1..].concat()),
+			None => (" ", String::from("")),
+		};
+		ViewLine::new(vec![
+			LineSegment::new(graphemes[start..pointer].concat().as_str()),
+			LineSegment::new_with_color_and_style(cursor, DisplayColor::Normal, false, true, false),
+			LineSegment::new(after.as_str()),
+		])
+	}
+
+	fn get_line_length(&self, row: usize) -> usize {
+		UnicodeSegmentation::graphemes(self.lines[row].as_str(), true).count()
+	}
+
+	fn set_row(&mut self, row: usize, cursor_position: usize) {
+		self.row = row;
+		self.editor.set_content(self.lines[row].as_str());
+		self.editor.set_cursor_position(cursor_position);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::MessageEditor;
+	use crate::input::Input;
+
+	fn create_editor(content: &str) -> MessageEditor {
+		let mut editor = MessageEditor::new();
+		editor.set_content(content);
+		editor
+	}
+
+	fn apply(editor: &mut MessageEditor, inputs: &[Input]) {
+		for input in inputs {
+			assert!(editor.handle_input(*input));
+		}
+	}
+
+	#[test]
+	fn message_editor_set_content() {
+		let editor = create_editor("Title\n\nBody\n");
+		assert_eq!(editor.get_content(), "Title\n\nBody");
+		assert_eq!(create_editor("").get_content(), "");
+	}
+
+	#[test]
+	fn message_editor_split_line() {
+		let mut editor = create_editor("Title body");
+		apply(&mut editor, &[Input::MoveCursorWordRight, Input::Enter, Input::Enter]);
+		assert_eq!(editor.get_content(), "Title\n\n body");
+		assert_eq!(editor.row, 2);
+	}
+
+	#[test]
+	fn message_editor_join_lines() {
+		let mut editor = create_editor("Title\nbody\nmore");
+		apply(&mut editor, &[Input::MoveCursorDown, Input::Backspace]);
+		assert_eq!(editor.get_content(), "Titlebody\nmore");
+		assert_eq!(editor.editor.get_cursor_position(), 5);
+		apply(&mut editor, &[Input::MoveCursorEnd, Input::Delete]);
+		assert_eq!(editor.get_content(), "Titlebodymore");
+	}
+
+	#[test]
+	fn message_editor_move_between_lines() {
+		let mut editor = create_editor("Title\n\nA longer body");
+		apply(&mut editor, &[Input::MoveCursorEnd, Input::MoveCursorDown, Input::MoveCursorDown]);
+		assert_eq!(editor.row, 2);
+		assert_eq!(editor.editor.get_cursor_position(), 0);
+		apply(&mut editor, &[Input::MoveCursorDown, Input::Character('X')]);
+		assert_eq!(editor.get_content(), "Title\n\nXA longer body");
+	}
+
+	#[test]
+	fn message_editor_edit_line() {
+		let mut editor = create_editor("Title\nbody");
+		apply(&mut editor, &[Input::MoveCursorDown, Input::KillToEnd, Input::Character('x')]);
+		assert_eq!(editor.get_content(), "Title\nx");
+		assert!(!editor.handle_input(Input::Escape));
+	}
+
+	#[test]
+	fn message_editor_scroll() {
+		let mut editor = create_editor("1\n2\n3\n4\n5");
+		apply(&mut editor, &[Input::MoveCursorDown, Input::MoveCursorDown, Input::MoveCursorDown]);
+		editor.update_scroll_position(10, 2);
+		assert_eq!(editor.get_top_position(), 2);
+		apply(&mut editor, &[Input::MoveCursorUp, Input::MoveCursorUp]);
+		editor.update_scroll_position(10, 2);
+		assert_eq!(editor.get_top_position(), 1);
+	}
+}
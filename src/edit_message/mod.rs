@@ -0,0 +1,130 @@
+mod message_editor;
+pub(crate) mod utils;
+
+use crate::config::Config;
+use crate::display::display_color::DisplayColor;
+use crate::edit_message::message_editor::MessageEditor;
+use crate::edit_message::utils::{load_message, save_message};
+use crate::git_interactive::GitInteractive;
+use crate::input::input_handler::{InputHandler, InputMode};
+use crate::input::Input;
+use crate::process::handle_input_result::HandleInputResult;
+use crate::process::process_module::ProcessModule;
+use crate::process::process_result::{ProcessResult, ProcessResultBuilder};
+use crate::process::state::State;
+use crate::view::View;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum EditMessageState {
+	Active,
+	Cancel,
+	Finish,
+}
+
+pub(crate) struct EditMessage {
+	comment_char: String,
+	editor: MessageEditor,
+	error: Option<String>,
+	state: EditMessageState,
+}
+
+impl ProcessModule for EditMessage {
+	fn activate(&mut self, _state: State, git_interactive: &GitInteractive) {
+		self.state = EditMessageState::Active;
+		match load_message(git_interactive, self.comment_char.as_str()) {
+			Ok(message) => self.editor.set_content(message.as_str()),
+			Err(message) => self.error = Some(message),
+		}
+	}
+
+	fn deactivate(&mut self) {
+		self.editor.set_content("");
+		self.error = None;
+	}
+
+	fn process(&mut self, git_interactive: &mut GitInteractive, view: &View) -> ProcessResult {
+		let mut result = ProcessResultBuilder::new();
+		if let Some(message) = self.error.take() {
+			return result.error(message.as_str(), State::List(false)).build();
+		}
+		match self.state {
+			EditMessageState::Active => {
+				let (view_width, view_height) = view.get_view_size();
+				self.editor.update_scroll_position(view_width, view_height - 2);
+			},
+			EditMessageState::Cancel => {
+				result = result.state(State::List(false));
+			},
+			EditMessageState::Finish => {
+				result = match save_message(
+					git_interactive,
+					self.editor.get_content().as_str(),
+					self.comment_char.as_str(),
+				) {
+					Ok(_) => result.state(State::List(false)),
+					Err(message) => result.error(message.as_str(), State::List(false)),
+				};
+			},
+		}
+		result.build()
+	}
+
+	fn handle_input(
+		&mut self,
+		input_handler: &InputHandler,
+		_git_interactive: &mut GitInteractive,
+		_view: &View,
+	) -> HandleInputResult
+	{
+		if self.state != EditMessageState::Active {
+			return HandleInputResult::new(Input::Enter);
+		}
+		let mut input;
+		loop {
+			input = input_handler.get_input(InputMode::Raw);
+			match input {
+				Input::Submit => self.state = EditMessageState::Finish,
+				Input::Escape => self.state = EditMessageState::Cancel,
+				Input::Resize => {},
+				_ => {
+					if !self.editor.handle_input(input) {
+						continue;
+					}
+				},
+			}
+			break;
+		}
+		HandleInputResult::new(input)
+	}
+
+	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
+		let (_, view_height) = view.get_view_size();
+
+		view.draw_title(false);
+		view.draw_view_lines(
+			&self.editor.get_view_lines(),
+			self.editor.get_top_position(),
+			0,
+			view_height - 2,
+		);
+		view.set_color(DisplayColor::IndicatorColor, false);
+		view.draw_str("Control+d to save, Escape to cancel");
+	}
+}
+
+impl EditMessage {
+	pub(crate) fn new(config: &Config) -> Self {
+		Self {
+			// like the todo, a comment character that git chooses for each message is treated as the default
+			comment_char: if config.comment_char == "auto" {
+				String::from("#")
+			}
+			else {
+				config.comment_char.clone()
+			},
+			editor: MessageEditor::new(),
+			error: None,
+			state: EditMessageState::Active,
+		}
+	}
+}
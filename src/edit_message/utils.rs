@@ -0,0 +1,210 @@
+use crate::commit::Commit;
+use crate::git_interactive::GitInteractive;
+use crate::list::action::Action;
+use crate::list::chains::{get_chain, get_chains};
+use crate::list::line::Line;
+use crate::list::message::{get_message_filepath, is_message_action};
+use std::fs;
+use std::path::PathBuf;
+
+/// Gets the first and last index of the lines that are combined into the commit of the selected line
+fn get_group(git_interactive: &GitInteractive) -> (usize, usize) {
+	let index = *git_interactive.get_selected_line_index() - 1;
+	match get_chain(&get_chains(git_interactive.get_lines()), index) {
+		Some(chain) => (chain.get_start(), chain.get_end()),
+		None => (index, index),
+	}
+}
+
+fn is_squash_group(lines: &[Line], start: usize, end: usize) -> bool {
+	lines[start + 1..=end].iter().any(|l| *l.get_action() == Action::Squash)
+}
+
+/// Gets the message file of the group, when its new message was already written and is applied after the group
+fn get_saved_message_filepath(git_interactive: &GitInteractive, start: usize) -> Option<PathBuf> {
+	let head = &git_interactive.get_lines()[start];
+	if is_message_action(*head.get_action()) && git_interactive.has_message(head.get_hash()) {
+		Some(get_message_filepath(git_interactive.get_filepath(), head.get_hash()))
+	}
+	else {
+		None
+	}
+}
+
+/// Combines the messages of a squash group in the same layout that git uses, the fixup messages are commented out
+fn get_combined_message(messages: &[(Action, String)], comment_char: &str) -> String {
+	let mut combined = format!("{} This is a combination of {} commits.\n", comment_char, messages.len());
+	for (index, (action, message)) in messages.iter().enumerate() {
+		let message = message.trim_end();
+		if index == 0 {
+			combined.push_str(format!("{} This is the 1st commit message:\n\n{}\n", comment_char, message).as_str());
+		}
+		else if *action == Action::Fixup {
+			let commented: Vec<String> = message
+				.lines()
+				.map(|l| {
+					if l.is_empty() {
+						String::from(comment_char)
+					}
+					else {
+						format!("{} {}", comment_char, l)
+					}
+				})
+				.collect();
+			combined.push_str(
+				format!(
+					"\n{} The commit message #{} will be skipped:\n\n{}\n",
+					comment_char,
+					index + 1,
+					commented.join("\n")
+				)
+				.as_str(),
+			);
+		}
+		else {
+			combined.push_str(
+				format!(
+					"\n{} This is the commit message #{}:\n\n{}\n",
+					comment_char,
+					index + 1,
+					message
+				)
+				.as_str(),
+			);
+		}
+	}
+	combined
+}
+
+/// Removes the comment lines, trailing whitespace and repeated blank lines, as git does for a message written in its
+/// editor
+fn clean_message(message: &str, comment_char: &str) -> String {
+	let mut lines: Vec<&str> = vec![];
+	for line in message.lines().filter(|l| !l.starts_with(comment_char)).map(str::trim_end) {
+		if !line.is_empty() || lines.last().map(|l| !l.is_empty()).unwrap_or(false) {
+			lines.push(line);
+		}
+	}
+	lines.join("\n").trim_end().to_string()
+}
+
+/// A reword line, a line of a group with a squash, or a line of a group with a message that was already written, can
+/// have its message edited
+pub(crate) fn can_edit_message(git_interactive: &GitInteractive) -> bool {
+	let (start, end) = get_group(git_interactive);
+	let lines = git_interactive.get_lines();
+	*lines[start].get_action() == Action::Reword
+		|| is_squash_group(lines, start, end)
+		|| get_saved_message_filepath(git_interactive, start).is_some()
+}
+
+/// Loads the message written earlier, or else the combined messages of a squash group, or the message of the commit
+pub(super) fn load_message(git_interactive: &GitInteractive, comment_char: &str) -> Result<String, String> {
+	let (start, end) = get_group(git_interactive);
+	if let Some(filepath) = get_saved_message_filepath(git_interactive, start) {
+		return fs::read_to_string(&filepath)
+			.map_err(|why| format!("Error reading file, {}\nReason: {}", filepath.display(), why));
+	}
+	let lines = git_interactive.get_lines();
+	if is_squash_group(lines, start, end) {
+		let messages = lines[start..=end]
+			.iter()
+			.map(|line| {
+				let commit = Commit::from_commit_hash(line.get_hash())?;
+				Ok((*line.get_action(), commit.get_body().clone().unwrap_or_default()))
+			})
+			.collect::<Result<Vec<(Action, String)>, String>>()?;
+		return Ok(get_combined_message(&messages, comment_char));
+	}
+	let commit = Commit::from_commit_hash(lines[start].get_hash())?;
+	Ok(commit.get_body().clone().unwrap_or_default())
+}
+
+/// Writes the message to a file and changes the group to a pick, followed by any fixups, the commit is then amended
+/// with the message by an exec that is written after the group, so git does not stop to ask for the message during the
+/// rebase
+pub(super) fn save_message(
+	git_interactive: &mut GitInteractive,
+	message: &str,
+	comment_char: &str,
+) -> Result<(), String>
+{
+	let message = clean_message(message, comment_char);
+	if message.is_empty() {
+		return Err(String::from("The commit message cannot be empty"));
+	}
+	let (start, end) = get_group(git_interactive);
+	let hash = git_interactive.get_lines()[start].get_hash().clone();
+	let filepath = get_message_filepath(git_interactive.get_filepath(), hash.as_str());
+	fs::write(&filepath, format!("{}\n", message))
+		.map_err(|why| format!("Error writing file, {}\nReason: {}", filepath.display(), why))?;
+	// git would still ask for the message of a reword or a squash, so they become a pick and fixups
+	if *git_interactive.get_lines()[start].get_action() == Action::Reword {
+		git_interactive.set_line_action(start, Action::Pick);
+	}
+	for index in start + 1..=end {
+		if *git_interactive.get_lines()[index].get_action() == Action::Squash {
+			git_interactive.set_line_action(index, Action::Fixup);
+		}
+	}
+	git_interactive.add_message_hash(hash.as_str());
+	Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{clean_message, get_combined_message};
+	use crate::list::action::Action;
+
+	#[test]
+	fn combined_message() {
+		let messages = vec![
+			(Action::Pick, String::from("First\n\nFirst body\n")),
+			(Action::Squash, String::from("Second\n")),
+			(Action::Fixup, String::from("fixup! First\n\nFix body\n")),
+		];
+		assert_eq!(
+			get_combined_message(&messages, "#"),
+			"# This is a combination of 3 commits.\n# This is the 1st commit message:\n\nFirst\n\nFirst body\n\n# This \
+			 is the commit message #2:\n\nSecond\n\n# The commit message #3 will be skipped:\n\n# fixup! First\n#\n# Fix \
+			 body\n"
+		);
+	}
+
+	#[test]
+	fn clean_message_removes_comments_and_blank_lines() {
+		assert_eq!(
+			clean_message(
+				"# This is a combination of 2 commits.\n\nFirst  \n\n# skipped\n\nSecond\n\n",
+				"#"
+			),
+			"First\n\nSecond"
+		);
+	}
+
+	#[test]
+	fn combined_message_comment_char() {
+		let messages = vec![
+			(Action::Pick, String::from("First\n")),
+			(Action::Fixup, String::from("fixup! First\n")),
+		];
+		assert_eq!(
+			get_combined_message(&messages, ";"),
+			"; This is a combination of 2 commits.\n; This is the 1st commit message:\n\nFirst\n\n; The commit message \
+			 #2 will be skipped:\n\n; fixup! First\n"
+		);
+	}
+
+	#[test]
+	fn clean_message_comment_char() {
+		assert_eq!(
+			clean_message("; This is a combination of 2 commits.\n\nFirst\n\n#123 fixed\n; skipped\n", ";"),
+			"First\n\n#123 fixed"
+		);
+	}
+
+	#[test]
+	fn clean_message_line_starting_with_comment_char() {
+		assert_eq!(clean_message("First\n\n#123 fixed\nbody\n", "#"), "First\n\nbody");
+	}
+}
@@ -9,7 +9,7 @@ enum State {
 	WhiteSpace,
 }
 
-pub(super) fn tolkenize(input: &str) -> Option<Vec<String>> {
+pub(crate) fn tolkenize(input: &str) -> Option<Vec<String>> {
 	let mut previous_state = State::Normal;
 	let mut state = State::Normal;
 	let mut token_start: usize = 0;
@@ -1,4 +1,4 @@
-mod argument_tolkenizer;
+pub(crate) mod argument_tolkenizer;
 
 use crate::config::Config;
 use crate::display::Display;
@@ -1,7 +1,13 @@
 use crate::commit::Commit;
 use crate::list::action::Action;
+use crate::list::autosquash::autosquash;
 use crate::list::line::Line;
+use crate::list::message::{add_message_lines, remove_message_lines};
+use crate::rebase_progress::RebaseProgress;
+use crate::session::data::SessionData;
+use crate::session::Session;
 use std::cmp;
+use std::collections::HashSet;
 use std::fs::File;
 use std::io::Read;
 use std::io::Write;
@@ -44,19 +50,40 @@ fn load_filepath(path: &PathBuf, config_comment_char: &str) -> Result<Vec<Line>,
 pub(crate) struct GitInteractive {
 	filepath: PathBuf,
 	lines: Vec<Line>,
+	message_hashes: HashSet<String>,
+	original_lines: Vec<Line>,
+	rebase_progress: Option<RebaseProgress>,
+	recoverable_session: Option<SessionData>,
 	selected_line_index: usize,
+	session: Session,
 	visual_index_start: usize,
 }
 
 impl GitInteractive {
 	pub(crate) fn new_from_filepath(filepath: &str, comment_char: &str) -> Result<Self, String> {
 		let path = PathBuf::from(filepath);
-		let lines = load_filepath(&path, comment_char)?;
+		// the exec lines that amend commits with their saved messages are added again when the todo is written
+		let (lines, message_hashes) = remove_message_lines(load_filepath(&path, comment_char)?);
+		let session = Session::new(
+			&path,
+			&SessionData::new(&lines, &add_message_lines(&path, &lines, &message_hashes), 1, 1),
+		);
+
+		// a session that cannot be read, or was for a different todo, is not recoverable
+		let recoverable_session = match session.load() {
+			Ok(Some(data)) if data.matches_original(&lines) => Some(data),
+			_ => None,
+		};
 
 		Ok(GitInteractive {
 			filepath: path,
+			original_lines: lines.clone(),
 			lines,
+			message_hashes,
+			rebase_progress: None,
+			recoverable_session,
 			selected_line_index: 1,
+			session,
 			visual_index_start: 1,
 		})
 	}
@@ -72,7 +99,7 @@ impl GitInteractive {
 				));
 			},
 		};
-		for line in self.lines.iter() {
+		for line in self.get_lines_with_messages() {
 			match writeln!(file, "{}", line.to_text()) {
 				Ok(_) => {},
 				Err(why) => {
@@ -83,10 +110,44 @@ impl GitInteractive {
 		Ok(())
 	}
 
+	pub(crate) fn save_session(&mut self) -> Result<(), String> {
+		self.session.save(&SessionData::new(
+			&self.original_lines,
+			&self.get_lines_with_messages(),
+			self.selected_line_index,
+			self.visual_index_start,
+		))
+	}
+
+	pub(crate) fn remove_session(&self) -> Result<(), String> {
+		self.session.remove()
+	}
+
+	pub(crate) fn has_recoverable_session(&self) -> bool {
+		self.recoverable_session.is_some()
+	}
+
+	pub(crate) fn restore_session(&mut self) {
+		if let Some(data) = self.recoverable_session.take() {
+			let (lines, message_hashes) = remove_message_lines(data.get_lines().clone());
+			self.lines = lines;
+			self.message_hashes = message_hashes;
+			let max_index = cmp::max(self.lines.len(), 1);
+			self.selected_line_index = cmp::min(cmp::max(data.get_selected_line_index(), 1), max_index);
+			self.visual_index_start = cmp::min(cmp::max(data.get_visual_index_start(), 1), max_index);
+		}
+	}
+
+	pub(crate) fn discard_session(&mut self) -> Result<(), String> {
+		self.recoverable_session = None;
+		self.session.remove()
+	}
+
 	pub(crate) fn reload_file(&mut self, comment_char: &str) -> Result<(), String> {
-		let lines = load_filepath(&self.filepath, comment_char)?;
+		let (lines, message_hashes) = remove_message_lines(load_filepath(&self.filepath, comment_char)?);
 
 		self.lines = lines;
+		self.message_hashes = message_hashes;
 		Ok(())
 	}
 
@@ -105,6 +166,10 @@ impl GitInteractive {
 		self.selected_line_index = cmp::min(self.selected_line_index + amount, self.lines.len());
 	}
 
+	pub(crate) fn set_selected_line_index(&mut self, selected_line_index: usize) {
+		self.selected_line_index = cmp::max(cmp::min(selected_line_index, self.lines.len()), 1);
+	}
+
 	pub(crate) fn start_visual_mode(&mut self) {
 		self.visual_index_start = self.selected_line_index;
 	}
@@ -129,15 +194,6 @@ impl GitInteractive {
 		self.move_cursor_up(1);
 	}
 
-	pub(crate) fn swap_selected_up(&mut self) {
-		if self.selected_line_index == 1 {
-			return;
-		}
-		self.lines
-			.swap(self.selected_line_index - 1, self.selected_line_index - 2);
-		self.move_cursor_up(1);
-	}
-
 	#[allow(clippy::range_plus_one)]
 	pub(crate) fn swap_visual_range_down(&mut self) {
 		if self.selected_line_index == self.lines.len() || self.visual_index_start == self.lines.len() {
@@ -158,12 +214,22 @@ impl GitInteractive {
 		self.move_cursor_down(1);
 	}
 
-	pub(crate) fn swap_selected_down(&mut self) {
-		if self.selected_line_index == self.lines.len() {
+	/// Moves the lines from start to end, inclusive, down past the next lines, keeping the selected line with them
+	pub(crate) fn move_lines_down(&mut self, start: usize, end: usize, amount: usize) {
+		if amount == 0 || end + amount >= self.lines.len() {
 			return;
 		}
-		self.lines.swap(self.selected_line_index - 1, self.selected_line_index);
-		self.move_cursor_down(1);
+		self.lines[start..=end + amount].rotate_right(amount);
+		self.selected_line_index += amount;
+	}
+
+	/// Moves the lines from start to end, inclusive, up past the previous lines, keeping the selected line with them
+	pub(crate) fn move_lines_up(&mut self, start: usize, end: usize, amount: usize) {
+		if amount == 0 || amount > start {
+			return;
+		}
+		self.lines[start - amount..=end].rotate_left(amount);
+		self.selected_line_index -= amount;
 	}
 
 	pub(crate) fn edit_selected_line(&mut self, content: &str) {
@@ -174,6 +240,10 @@ impl GitInteractive {
 		self.lines[self.selected_line_index - 1].get_edit_content()
 	}
 
+	pub(crate) fn set_selected_line_target(&mut self, hash: &str, comment: &str) {
+		self.lines[self.selected_line_index - 1].set_target(hash, comment);
+	}
+
 	#[allow(clippy::range_plus_one)]
 	pub(crate) fn set_visual_range_action(&mut self, action: Action) {
 		let range = if self.selected_line_index <= self.visual_index_start {
@@ -198,6 +268,13 @@ impl GitInteractive {
 		}
 	}
 
+	/// Sets the action of the line at the index, counting from zero, lines without a commit are not changed
+	pub(crate) fn set_line_action(&mut self, index: usize, action: Action) {
+		if self.lines[index].get_action().has_commit() {
+			self.lines[index].set_action(action);
+		}
+	}
+
 	pub(crate) fn toggle_break(&mut self) {
 		let selected_action = self.lines[self.selected_line_index - 1].get_action();
 		if *selected_action == Action::Break {
@@ -214,6 +291,42 @@ impl GitInteractive {
 		}
 	}
 
+	/// Moves fixup, squash and amend commits after their targets, returning the lines without a matching target
+	pub(crate) fn autosquash(&mut self) -> Vec<Line> {
+		let (lines, unmatched) = autosquash(&self.lines);
+		self.lines = lines;
+		unmatched
+	}
+
+	pub(crate) fn set_lines(&mut self, lines: Vec<Line>) {
+		self.lines = lines;
+		self.selected_line_index = 1;
+		self.visual_index_start = 1;
+	}
+
+	/// Sets the progress of a rebase in progress, the done lines are shown but cannot be edited
+	pub(crate) fn set_rebase_progress(&mut self, rebase_progress: RebaseProgress) {
+		self.rebase_progress = Some(rebase_progress);
+	}
+
+	pub(crate) fn insert_line_after_selected(&mut self, line: Line) {
+		self.lines.insert(self.selected_line_index, line);
+	}
+
+	/// Marks the commit as having a saved message, which is applied by an exec after the commit in the written todo
+	pub(crate) fn add_message_hash(&mut self, hash: &str) {
+		self.message_hashes.insert(String::from(hash));
+	}
+
+	pub(crate) fn has_message(&self, hash: &str) -> bool {
+		self.message_hashes.contains(hash)
+	}
+
+	/// Gets the lines as they are written to the todo, with the exec lines that apply the saved messages
+	pub(crate) fn get_lines_with_messages(&self) -> Vec<Line> {
+		add_message_lines(&self.filepath, &self.lines, &self.message_hashes)
+	}
+
 	pub(crate) fn load_commit_stats(&self) -> Result<Commit, String> {
 		let selected_action = self.lines[self.selected_line_index - 1].get_action();
 		if *selected_action != Action::Exec && *selected_action != Action::Break {
@@ -249,4 +362,21 @@ impl GitInteractive {
 	pub(crate) fn get_lines(&self) -> &Vec<Line> {
 		&self.lines
 	}
+
+	pub(crate) fn get_done_lines(&self) -> &[Line] {
+		self.rebase_progress
+			.as_ref()
+			.map_or(&[], |rebase_progress| rebase_progress.get_done_lines().as_slice())
+	}
+
+	/// Gets the title status of a rebase in progress, counting the lines currently in the todo as remaining
+	pub(crate) fn get_rebase_progress_title(&self) -> Option<String> {
+		self.rebase_progress
+			.as_ref()
+			.map(|rebase_progress| rebase_progress.get_title(self.lines.len()))
+	}
+
+	pub(crate) fn get_original_lines(&self) -> &Vec<Line> {
+		&self.original_lines
+	}
 }
@@ -16,11 +16,12 @@ use crate::process::process_module::ProcessModule;
 use crate::process::state::State;
 use crate::view::line_segment::LineSegment;
 use crate::view::scroll_position::ScrollPosition;
+use crate::view::utils::get_display_width;
 use crate::view::view_line::ViewLine;
 use crate::view::View;
 
 pub(crate) struct Help<'h> {
-	normal_mode_help_lines: [(&'h str, &'h str); 22],
+	normal_mode_help_lines: Vec<(&'h str, &'h str)>,
 	normal_mode_max_help_line_desc_length: usize,
 	normal_mode_max_help_line_key_length: usize,
 	return_state: State,
@@ -97,7 +98,13 @@ impl<'h> ProcessModule for Help<'h> {
 			view_lines.push(ViewLine::new_with_pinned_segments(
 				vec![
 					LineSegment::new_with_color(
-						format!(" {0:width$} ", line.0, width = self.get_max_help_key_length()).as_str(),
+						// padded by the display width, since formatting pads by the number of characters
+						format!(
+							" {}{} ",
+							line.0,
+							" ".repeat(self.get_max_help_key_length() - get_display_width(line.0))
+						)
+						.as_str(),
 						DisplayColor::IndicatorColor,
 					),
 					LineSegment::new(line.1),
@@ -1,8 +1,8 @@
+use crate::view::utils::get_display_width;
 use crate::Config;
-use unicode_segmentation::UnicodeSegmentation;
 
-pub(super) fn get_list_normal_mode_help_lines(config: &Config) -> [(&str, &str); 22] {
-	[
+pub(super) fn get_list_normal_mode_help_lines(config: &Config) -> Vec<(&str, &str)> {
+	let mut help_lines = vec![
 		(config.input_move_up.as_str(), "Move selection up"),
 		(config.input_move_down.as_str(), "Move selection down"),
 		(config.input_move_up_step.as_str(), "Move selection up 5 lines"),
@@ -35,12 +35,30 @@ pub(super) fn get_list_normal_mode_help_lines(config: &Config) -> [(&str, &str);
 		),
 		(config.input_action_fixup.as_str(), "Set selected commit to be fixed-up"),
 		(config.input_action_drop.as_str(), "Set selected commit to be dropped"),
-		(config.input_edit.as_str(), "Edit an exec action's command"),
+		(config.input_edit.as_str(), "Edit an exec action's command or a reword or squash message"),
+		(config.input_edit_hash.as_str(), "Change the target commit of the selected line"),
+		(
+			config.input_autosquash.as_str(),
+			"Move fixup, squash and amend commits after their targets",
+		),
+		(config.input_export_json.as_str(), "Export the todo as JSON"),
+		(
+			config.input_toggle_collapse.as_str(),
+			"Collapse or expand a fixup and squash chain",
+		),
+		(
+			config.input_toggle_focus.as_str(),
+			"Switch focus between the todo list and commit details, when split",
+		),
 		(
 			config.input_open_in_external_editor.as_str(),
 			"Open the todo file in the default editor",
 		),
-	]
+	];
+	for custom_command in &config.custom_commands {
+		help_lines.push((custom_command.key.as_str(), custom_command.command.as_str()));
+	}
+	help_lines
 }
 
 pub(super) fn get_list_visual_mode_help_lines(config: &Config) -> [(&str, &str); 14] {
@@ -74,7 +92,7 @@ pub(super) fn get_list_visual_mode_help_lines(config: &Config) -> [(&str, &str);
 pub(super) fn get_max_help_key_length(lines: &[(&str, &str)]) -> usize {
 	let mut max_length = 0;
 	for (key, _) in lines {
-		let len = UnicodeSegmentation::graphemes(*key, true).count();
+		let len = get_display_width(key);
 		if len > max_length {
 			max_length = len;
 		}
@@ -85,10 +103,25 @@ pub(super) fn get_max_help_key_length(lines: &[(&str, &str)]) -> usize {
 pub(super) fn get_max_help_description_length(lines: &[(&str, &str)]) -> usize {
 	let mut max_length = 0;
 	for (_, desc) in lines {
-		let len = UnicodeSegmentation::graphemes(*desc, true).count();
+		let len = get_display_width(desc);
 		if len > max_length {
 			max_length = len;
 		}
 	}
 	max_length
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{get_max_help_description_length, get_max_help_key_length};
+
+	#[test]
+	fn help_max_key_length_wide_characters() {
+		assert_eq!(get_max_help_key_length(&[("a", "first"), ("日本", "second")]), 4);
+	}
+
+	#[test]
+	fn help_max_description_length_wide_characters() {
+		assert_eq!(get_max_help_description_length(&[("a", "abc"), ("b", "日本")]), 4);
+	}
+}
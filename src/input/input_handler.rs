@@ -1,91 +1,319 @@
+use crate::constants::{INPUT_MODIFIER_TIMEOUT, INPUT_SEQUENCE_TIMEOUT};
 use crate::display::Display;
-use crate::input::utils::curses_input_to_string;
+use crate::input::recording::{RecordedEvent, Recorder};
+use crate::input::utils::{curses_input_to_string, parse_key_bindings};
 use crate::input::Input;
 use crate::Config;
-use pancurses::Input as PancursesInput;
+use pancurses::{
+	mmask_t,
+	Input as PancursesInput,
+	BUTTON1_CLICKED,
+	BUTTON1_DOUBLE_CLICKED,
+	BUTTON1_PRESSED,
+	BUTTON4_PRESSED,
+	BUTTON5_PRESSED,
+	BUTTON_SHIFT,
+};
+use std::cell::RefCell;
+use std::collections::VecDeque;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum InputMode {
 	Default,
 	Confirm,
+	ConfirmRebase,
 	List,
 	Raw,
 }
 
+type KeyBindings = Vec<(Vec<String>, Input)>;
+
+fn add_key_bindings(bindings: &mut KeyBindings, value: &str, input: Input) {
+	// the config values are validated when loaded, so this will not normally fail
+	if let Ok(sequences) = parse_key_bindings(value) {
+		for sequence in sequences {
+			bindings.push((sequence, input));
+		}
+	}
+}
+
+fn add_movement_key_bindings(bindings: &mut KeyBindings, config: &Config) {
+	add_key_bindings(bindings, config.input_move_up.as_str(), Input::MoveCursorUp);
+	add_key_bindings(bindings, config.input_move_down.as_str(), Input::MoveCursorDown);
+	add_key_bindings(bindings, config.input_move_left.as_str(), Input::MoveCursorLeft);
+	add_key_bindings(bindings, config.input_move_right.as_str(), Input::MoveCursorRight);
+	add_key_bindings(bindings, config.input_move_up_step.as_str(), Input::MoveCursorPageUp);
+	add_key_bindings(bindings, config.input_move_down_step.as_str(), Input::MoveCursorPageDown);
+}
+
+fn get_default_key_bindings(config: &Config) -> KeyBindings {
+	let mut bindings = vec![];
+	add_movement_key_bindings(&mut bindings, config);
+	add_key_bindings(&mut bindings, config.input_toggle_focus.as_str(), Input::ToggleFocus);
+	bindings
+}
+
+fn get_confirm_key_bindings(config: &Config) -> KeyBindings {
+	let mut bindings = vec![];
+	// confirm yes responds to both the uppercase and lowercase variant of the key
+	add_key_bindings(
+		&mut bindings,
+		config.input_confirm_yes.to_lowercase().as_str(),
+		Input::Yes,
+	);
+	add_key_bindings(
+		&mut bindings,
+		config.input_confirm_yes.to_uppercase().as_str(),
+		Input::Yes,
+	);
+	bindings
+}
+
+/// The confirm bindings, with the movement keys to scroll the summary of the rebase instead of answering no
+fn get_confirm_rebase_key_bindings(config: &Config) -> KeyBindings {
+	let mut bindings = get_confirm_key_bindings(config);
+	add_movement_key_bindings(&mut bindings, config);
+	bindings
+}
+
+fn get_list_key_bindings(config: &Config) -> KeyBindings {
+	let mut bindings = vec![];
+	add_key_bindings(&mut bindings, config.input_abort.as_str(), Input::Abort);
+	add_key_bindings(&mut bindings, config.input_rebase.as_str(), Input::Rebase);
+	add_key_bindings(&mut bindings, config.input_force_abort.as_str(), Input::ForceAbort);
+	add_key_bindings(&mut bindings, config.input_force_rebase.as_str(), Input::ForceRebase);
+	add_key_bindings(
+		&mut bindings,
+		config.input_open_in_external_editor.as_str(),
+		Input::OpenInEditor,
+	);
+	add_key_bindings(&mut bindings, config.input_show_commit.as_str(), Input::ShowCommit);
+	add_key_bindings(&mut bindings, config.input_edit.as_str(), Input::Edit);
+	add_key_bindings(&mut bindings, config.input_edit_hash.as_str(), Input::EditHash);
+	add_key_bindings(&mut bindings, config.input_help.as_str(), Input::Help);
+	add_key_bindings(
+		&mut bindings,
+		config.input_toggle_visual_mode.as_str(),
+		Input::ToggleVisualMode,
+	);
+	add_key_bindings(&mut bindings, config.input_action_break.as_str(), Input::ActionBreak);
+	add_key_bindings(&mut bindings, config.input_action_drop.as_str(), Input::ActionDrop);
+	add_key_bindings(&mut bindings, config.input_action_edit.as_str(), Input::ActionEdit);
+	add_key_bindings(&mut bindings, config.input_action_fixup.as_str(), Input::ActionFixup);
+	add_key_bindings(&mut bindings, config.input_action_pick.as_str(), Input::ActionPick);
+	add_key_bindings(&mut bindings, config.input_action_reword.as_str(), Input::ActionReword);
+	add_key_bindings(&mut bindings, config.input_action_squash.as_str(), Input::ActionSquash);
+	add_key_bindings(&mut bindings, config.input_autosquash.as_str(), Input::Autosquash);
+	add_key_bindings(&mut bindings, config.input_export_json.as_str(), Input::ExportJson);
+	add_key_bindings(&mut bindings, config.input_toggle_collapse.as_str(), Input::ToggleCollapse);
+	add_key_bindings(&mut bindings, config.input_toggle_focus.as_str(), Input::ToggleFocus);
+	add_movement_key_bindings(&mut bindings, config);
+	add_key_bindings(
+		&mut bindings,
+		config.input_move_selection_down.as_str(),
+		Input::SwapSelectedDown,
+	);
+	add_key_bindings(
+		&mut bindings,
+		config.input_move_selection_up.as_str(),
+		Input::SwapSelectedUp,
+	);
+	// custom commands are added last, so the built in bindings take precedence when conflicts are allowed
+	for (index, custom_command) in config.custom_commands.iter().enumerate() {
+		add_key_bindings(&mut bindings, custom_command.key.as_str(), Input::CustomCommand(index));
+	}
+	bindings
+}
+
+/// Finds the input for a sequence of keys, and if a longer binding could still match the sequence
+fn match_key_sequence(bindings: &[(Vec<String>, Input)], keys: &[String]) -> (Option<Input>, bool) {
+	let mut matched = None;
+	let mut partial = false;
+	for (sequence, input) in bindings {
+		if sequence.as_slice() == keys {
+			// the first binding wins, this matches the order that the bindings were checked historically
+			if matched.is_none() {
+				matched = Some(*input);
+			}
+		}
+		else if sequence.len() > keys.len() && sequence.starts_with(keys) {
+			partial = true;
+		}
+	}
+	(matched, partial)
+}
+
+/// Finds the longest sequence at the start of the keys that completes a binding, returning the input and the number of
+/// keys used, when no binding is completed only the first key is used
+fn get_longest_match(bindings: &[(Vec<String>, Input)], keys: &[String], unmatched: Input) -> (Input, usize) {
+	for length in (1..=keys.len()).rev() {
+		if let (Some(input), _) = match_key_sequence(bindings, &keys[..length]) {
+			return (input, length);
+		}
+	}
+	(unmatched, keys.len().min(1))
+}
+
+/// Maps a mouse event to an input, the scroll wheel maps to the cursor movement inputs so every view can scroll
+fn get_mouse_input(button_state: mmask_t, column: usize, row: usize) -> Input {
+	if button_state & BUTTON4_PRESSED != 0 {
+		Input::MoveCursorUp
+	}
+	else if button_state & BUTTON5_PRESSED != 0 {
+		Input::MoveCursorDown
+	}
+	else if button_state & BUTTON1_DOUBLE_CLICKED != 0 {
+		Input::MouseDoubleClick(column, row)
+	}
+	else if button_state & (BUTTON1_CLICKED | BUTTON1_PRESSED) != 0 {
+		if button_state & BUTTON_SHIFT != 0 {
+			Input::MouseShiftClick(column, row)
+		}
+		else {
+			Input::MouseClick(column, row)
+		}
+	}
+	else {
+		Input::Other
+	}
+}
+
 pub(crate) struct InputHandler<'i> {
-	config: &'i Config,
+	confirm_key_bindings: KeyBindings,
+	confirm_rebase_key_bindings: KeyBindings,
+	default_key_bindings: KeyBindings,
 	display: &'i Display<'i>,
+	list_key_bindings: KeyBindings,
+	pending_keys: RefCell<VecDeque<String>>,
+	recorder: Option<Recorder>,
+	replay: RefCell<Option<VecDeque<RecordedEvent>>>,
 }
 
 impl<'i> InputHandler<'i> {
 	pub(crate) fn new(display: &'i Display, config: &'i Config) -> Self {
-		Self { config, display }
+		Self {
+			confirm_key_bindings: get_confirm_key_bindings(config),
+			confirm_rebase_key_bindings: get_confirm_rebase_key_bindings(config),
+			default_key_bindings: get_default_key_bindings(config),
+			display,
+			list_key_bindings: get_list_key_bindings(config),
+			pending_keys: RefCell::new(VecDeque::new()),
+			recorder: None,
+			replay: RefCell::new(None),
+		}
 	}
 
-	pub(crate) fn get_input(&self, mode: InputMode) -> Input {
-		let c = self.get_next_input();
+	/// Records the window size and each input, starting with the current window size
+	pub(crate) fn record(&mut self, recorder: Recorder) {
+		let (width, height) = self.display.get_window_size();
+		recorder.record(RecordedEvent::Size(width, height));
+		self.recorder = Some(recorder);
+	}
 
-		let input = curses_input_to_string(c);
+	/// Replays recorded events before reading from the terminal, applying the recorded initial window size
+	pub(crate) fn replay(&mut self, mut events: VecDeque<RecordedEvent>) {
+		while let Some(RecordedEvent::Size(width, height)) = events.front().cloned() {
+			self.display.set_window_size(width, height);
+			events.pop_front();
+		}
+		self.replay.replace(Some(events));
+	}
+
+	pub(crate) fn get_input(&self, mode: InputMode) -> Input {
+		let input = match self.get_replay_input() {
+			Some(input) => input,
+			None => {
+				match mode {
+					InputMode::Raw => {
+						let key = self.get_next_key(None).unwrap_or_else(|| String::from("Other"));
+						self.get_raw_input(key.as_str())
+					},
+					InputMode::List => self.get_key_binding_input(&self.list_key_bindings, Input::Other),
+					InputMode::Confirm => self.get_key_binding_input(&self.confirm_key_bindings, Input::No),
+					InputMode::ConfirmRebase => {
+						self.get_key_binding_input(&self.confirm_rebase_key_bindings, Input::No)
+					},
+					InputMode::Default => self.get_key_binding_input(&self.default_key_bindings, Input::Other),
+				}
+			},
+		};
 
-		match mode {
-			InputMode::Raw => self.get_character(input.as_str()),
-			InputMode::List => self.get_list_input(input.as_str()),
-			InputMode::Confirm => self.get_confirm(input.as_str()),
-			InputMode::Default => self.get_default_input(input.as_str()),
+		if let Some(ref recorder) = self.recorder {
+			if input == Input::Resize {
+				let (width, height) = self.display.get_window_size();
+				recorder.record(RecordedEvent::Size(width, height));
+			}
+			recorder.record(RecordedEvent::Input(input));
 		}
+		input
 	}
 
-	fn get_default_input(self: &Self, input: &str) -> Input {
-		match input {
-			i if i == self.config.input_move_up.as_str() => Input::MoveCursorUp,
-			i if i == self.config.input_move_down.as_str() => Input::MoveCursorDown,
-			i if i == self.config.input_move_left.as_str() => Input::MoveCursorLeft,
-			i if i == self.config.input_move_right.as_str() => Input::MoveCursorRight,
-			i if i == self.config.input_move_up_step.as_str() => Input::MoveCursorPageUp,
-			i if i == self.config.input_move_down_step.as_str() => Input::MoveCursorPageDown,
-			"Resize" => Input::Resize,
-			_ => Input::Other,
+	/// Gets the next replayed input, applying any recorded window sizes before it
+	fn get_replay_input(&self) -> Option<Input> {
+		let mut replay = self.replay.borrow_mut();
+		let events = replay.as_mut()?;
+		while let Some(event) = events.pop_front() {
+			match event {
+				RecordedEvent::Input(input) => return Some(input),
+				RecordedEvent::Size(width, height) => self.display.set_window_size(width, height),
+			}
 		}
+		// once the replay ends, input continues from the terminal, so resize to its actual size
+		replay.take();
+		self.display.reset_window_size();
+		Some(Input::Resize)
 	}
 
-	#[allow(clippy::cognitive_complexity)]
-	fn get_list_input(self: &Self, input: &str) -> Input {
-		match input {
-			i if i == self.config.input_abort.as_str() => Input::Abort,
-			i if i == self.config.input_rebase.as_str() => Input::Rebase,
-			i if i == self.config.input_force_abort.as_str() => Input::ForceAbort,
-			i if i == self.config.input_force_rebase.as_str() => Input::ForceRebase,
-			i if i == self.config.input_open_in_external_editor.as_str() => Input::OpenInEditor,
-			i if i == self.config.input_show_commit.as_str() => Input::ShowCommit,
-			i if i == self.config.input_edit.as_str() => Input::Edit,
-			i if i == self.config.input_help.as_str() => Input::Help,
-			i if i == self.config.input_toggle_visual_mode.as_str() => Input::ToggleVisualMode,
-			i if i == self.config.input_action_break.as_str() => Input::ActionBreak,
-			i if i == self.config.input_action_drop.as_str() => Input::ActionDrop,
-			i if i == self.config.input_action_edit.as_str() => Input::ActionEdit,
-			i if i == self.config.input_action_fixup.as_str() => Input::ActionFixup,
-			i if i == self.config.input_action_pick.as_str() => Input::ActionPick,
-			i if i == self.config.input_action_reword.as_str() => Input::ActionReword,
-			i if i == self.config.input_action_squash.as_str() => Input::ActionSquash,
-			i if i == self.config.input_move_up.as_str() => Input::MoveCursorUp,
-			i if i == self.config.input_move_down.as_str() => Input::MoveCursorDown,
-			i if i == self.config.input_move_left.as_str() => Input::MoveCursorLeft,
-			i if i == self.config.input_move_right.as_str() => Input::MoveCursorRight,
-			i if i == self.config.input_move_up_step.as_str() => Input::MoveCursorPageUp,
-			i if i == self.config.input_move_down_step.as_str() => Input::MoveCursorPageDown,
-			i if i == self.config.input_move_selection_down.as_str() => Input::SwapSelectedDown,
-			i if i == self.config.input_move_selection_up.as_str() => Input::SwapSelectedUp,
-			"Resize" => Input::Resize,
-			_ => Input::Other,
+	/// Reads keys until they no longer continue a binding, or the sequence times out, and returns the longest complete
+	/// match, the keys read after that match are handled as the next input
+	fn get_key_binding_input(&self, bindings: &[(Vec<String>, Input)], unmatched: Input) -> Input {
+		let mut keys: Vec<String> = vec![];
+		loop {
+			let timeout = if keys.is_empty() {
+				None
+			}
+			else {
+				Some(INPUT_SEQUENCE_TIMEOUT)
+			};
+			let key = match self.get_next_key(timeout) {
+				Some(k) => k,
+				None => break,
+			};
+
+			if key == "Resize" || key == "Mouse" {
+				if keys.is_empty() {
+					return if key == "Resize" {
+						Input::Resize
+					}
+					else {
+						self.get_mouse_input()
+					};
+				}
+				// the event ends the sequence, and is handled after it
+				self.pending_keys.borrow_mut().push_front(key);
+				break;
+			}
+
+			keys.push(key);
+			if !match_key_sequence(bindings, &keys).1 {
+				break;
+			}
+		}
+
+		let (input, length) = get_longest_match(bindings, &keys, unmatched);
+		let mut pending_keys = self.pending_keys.borrow_mut();
+		for key in keys.drain(length..).rev() {
+			pending_keys.push_front(key);
 		}
+		input
 	}
 
-	fn get_confirm(&self, input: &str) -> Input {
-		let input = input.to_lowercase();
-		match input.as_str() {
-			i if i == self.config.input_confirm_yes.to_lowercase() => Input::Yes,
-			"resize" => Input::Resize,
-			_ => Input::No,
+	fn get_mouse_input(&self) -> Input {
+		match self.display.get_mouse_event() {
+			Some(event) if event.x >= 0 && event.y >= 0 => {
+				get_mouse_input(event.bstate, event.x as usize, event.y as usize)
+			},
+			_ => Input::Other,
 		}
 	}
 
@@ -97,12 +325,56 @@ impl<'i> InputHandler<'i> {
 			c if c == "Delete" => Input::Delete,
 			c if c == "Right" => Input::MoveCursorRight,
 			c if c == "Left" => Input::MoveCursorLeft,
+			"Up" => Input::MoveCursorUp,
+			"Down" => Input::MoveCursorDown,
+			"Home" | "Control+a" => Input::MoveCursorHome,
+			"End" | "Control+e" => Input::MoveCursorEnd,
+			"ControlLeft" | "Alt+b" => Input::MoveCursorWordLeft,
+			"ControlRight" | "Alt+f" => Input::MoveCursorWordRight,
+			"Control+w" => Input::KillWordBefore,
+			"Control+u" => Input::KillToStart,
+			"Control+k" => Input::KillToEnd,
+			"Control+y" => Input::Yank,
+			"Control+d" => Input::Submit,
+			"Escape" => Input::Escape,
 			c if c == "Resize" => Input::Resize,
-			c if c == "Other" => Input::Other,
-			c => Input::Character(c.chars().next().unwrap()),
+			c if c.chars().count() == 1 => Input::Character(c.chars().next().unwrap()),
+			_ => Input::Other,
 		}
 	}
 
+	/// Maps a key to an editing input, an escape that was combined with a key that has no editing input is handled as
+	/// an escape followed by that key
+	fn get_raw_input(&self, key: &str) -> Input {
+		let input = self.get_character(key);
+		if input == Input::Other && key.starts_with("Alt+") {
+			self.pending_keys.borrow_mut().push_front(String::from(&key["Alt+".len()..]));
+			return Input::Escape;
+		}
+		input
+	}
+
+	/// Gets the name of the next key, combining an escape followed by a character into an Alt key
+	fn get_next_key(&self, timeout: Option<i32>) -> Option<String> {
+		if let Some(key) = self.pending_keys.borrow_mut().pop_front() {
+			return Some(key);
+		}
+		let input = match timeout {
+			Some(milliseconds) => self.display.getch_with_timeout(milliseconds)?,
+			None => self.get_next_input(),
+		};
+
+		let key = curses_input_to_string(input);
+		if key == "Escape" {
+			match self.display.getch_with_timeout(INPUT_MODIFIER_TIMEOUT) {
+				Some(PancursesInput::Character(c)) if c != '\u{1b}' => return Some(format!("Alt+{}", c)),
+				Some(next_input) => self.display.ungetch(&next_input),
+				None => {},
+			}
+		}
+		Some(key)
+	}
+
 	fn get_next_input(&self) -> PancursesInput {
 		loop {
 			let c = self.display.getch();
@@ -113,3 +385,184 @@ impl<'i> InputHandler<'i> {
 		}
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{get_longest_match, get_mouse_input, match_key_sequence, InputHandler, InputMode};
+	use crate::display::virtual_terminal::VirtualTerminal;
+	use crate::display::Display;
+	use crate::input::Input;
+	use crate::Config;
+	use pancurses::{
+		Input as PancursesInput,
+		BUTTON1_CLICKED,
+		BUTTON1_DOUBLE_CLICKED,
+		BUTTON1_RELEASED,
+		BUTTON4_PRESSED,
+		BUTTON5_PRESSED,
+		BUTTON_SHIFT,
+	};
+
+	fn create_bindings() -> Vec<(Vec<String>, Input)> {
+		vec![
+			(vec![String::from("d")], Input::ActionDrop),
+			(vec![String::from("g"), String::from("g")], Input::MoveCursorUp),
+			(vec![String::from("Control+d")], Input::MoveCursorPageDown),
+			(vec![String::from("d"), String::from("d")], Input::ForceAbort),
+			(vec![String::from("Control+d")], Input::Abort),
+		]
+	}
+
+	fn keys(keys: &[&str]) -> Vec<String> {
+		keys.iter().map(|k| String::from(*k)).collect()
+	}
+
+	#[test]
+	fn match_key_sequence_single_key() {
+		assert_eq!(
+			match_key_sequence(&create_bindings(), &keys(&["Control+d"])),
+			(Some(Input::MoveCursorPageDown), false)
+		);
+	}
+
+	#[test]
+	fn match_key_sequence_partial_match() {
+		assert_eq!(match_key_sequence(&create_bindings(), &keys(&["g"])), (None, true));
+	}
+
+	#[test]
+	fn match_key_sequence_full_sequence() {
+		assert_eq!(
+			match_key_sequence(&create_bindings(), &keys(&["g", "g"])),
+			(Some(Input::MoveCursorUp), false)
+		);
+	}
+
+	#[test]
+	fn match_key_sequence_match_with_partial() {
+		assert_eq!(
+			match_key_sequence(&create_bindings(), &keys(&["d"])),
+			(Some(Input::ActionDrop), true)
+		);
+	}
+
+	#[test]
+	fn match_key_sequence_no_match() {
+		assert_eq!(match_key_sequence(&create_bindings(), &keys(&["g", "d"])), (None, false));
+	}
+
+	#[test]
+	fn get_longest_match_prefix_then_mismatch() {
+		assert_eq!(
+			get_longest_match(&create_bindings(), &keys(&["d", "j"]), Input::Other),
+			(Input::ActionDrop, 1)
+		);
+	}
+
+	#[test]
+	fn get_longest_match_unmatched() {
+		assert_eq!(
+			get_longest_match(&create_bindings(), &keys(&["g", "d"]), Input::Other),
+			(Input::Other, 1)
+		);
+		assert_eq!(get_longest_match(&create_bindings(), &keys(&[]), Input::Other), (Input::Other, 0));
+	}
+
+	fn create_config() -> Config {
+		let mut config = Config::new_from_config(&git2::Config::new().unwrap()).unwrap();
+		config.input_force_abort = String::from("dd");
+		config.input_help = String::from("gg");
+		config
+	}
+
+	fn get_inputs(keys: &str, mode: InputMode, count: usize) -> Vec<Input> {
+		let config = create_config();
+		let mut terminal = VirtualTerminal::new(10, 10);
+		terminal.push_str(keys);
+		let display = Display::new(&mut terminal, &config);
+		let input_handler = InputHandler::new(&display, &config);
+		(0..count).map(|_| input_handler.get_input(mode)).collect()
+	}
+
+	#[test]
+	fn get_input_sequence() {
+		assert_eq!(get_inputs("dd", InputMode::List, 1), vec![Input::ForceAbort]);
+	}
+
+	#[test]
+	fn get_input_prefix_then_mismatch() {
+		assert_eq!(get_inputs("dj", InputMode::List, 2), vec![
+			Input::ActionDrop,
+			Input::SwapSelectedDown
+		]);
+	}
+
+	#[test]
+	fn get_input_partial_then_mismatch() {
+		assert_eq!(get_inputs("gd", InputMode::List, 2), vec![Input::Other, Input::ActionDrop]);
+	}
+
+	#[test]
+	fn get_input_sequence_timeout() {
+		// the virtual terminal has no more input, so the wait for the next key of the sequence times out
+		assert_eq!(get_inputs("d", InputMode::List, 1), vec![Input::ActionDrop]);
+	}
+
+	#[test]
+	fn get_input_confirm_movement() {
+		let config = create_config();
+		let mut terminal = VirtualTerminal::new(10, 10);
+		terminal.push_input(PancursesInput::KeyDown);
+		terminal.push_input(PancursesInput::KeyDown);
+		let display = Display::new(&mut terminal, &config);
+		let input_handler = InputHandler::new(&display, &config);
+		assert_eq!(input_handler.get_input(InputMode::Confirm), Input::No);
+		assert_eq!(input_handler.get_input(InputMode::ConfirmRebase), Input::MoveCursorDown);
+	}
+
+	#[test]
+	fn get_input_raw_escape_then_character() {
+		assert_eq!(get_inputs("\u{1b}x", InputMode::Raw, 2), vec![
+			Input::Escape,
+			Input::Character('x')
+		]);
+	}
+
+	#[test]
+	fn get_input_raw_alt_editing_key() {
+		assert_eq!(get_inputs("\u{1b}b", InputMode::Raw, 1), vec![Input::MoveCursorWordLeft]);
+	}
+
+	#[test]
+	fn get_mouse_input_scroll_wheel_up() {
+		assert_eq!(get_mouse_input(BUTTON4_PRESSED, 2, 3), Input::MoveCursorUp);
+	}
+
+	#[test]
+	fn get_mouse_input_scroll_wheel_down() {
+		assert_eq!(get_mouse_input(BUTTON5_PRESSED, 2, 3), Input::MoveCursorDown);
+	}
+
+	#[test]
+	fn get_mouse_input_click() {
+		assert_eq!(get_mouse_input(BUTTON1_CLICKED, 2, 3), Input::MouseClick(2, 3));
+	}
+
+	#[test]
+	fn get_mouse_input_double_click() {
+		assert_eq!(get_mouse_input(BUTTON1_DOUBLE_CLICKED, 2, 4), Input::MouseDoubleClick(2, 4));
+	}
+
+	#[test]
+	fn get_mouse_input_shift_click() {
+		assert_eq!(
+			get_mouse_input(BUTTON1_CLICKED | BUTTON_SHIFT, 2, 5),
+			Input::MouseShiftClick(2, 5)
+		);
+	}
+
+	#[test]
+	fn get_mouse_input_other() {
+		assert_eq!(get_mouse_input(BUTTON1_RELEASED, 2, 5), Input::Other);
+	}
+}
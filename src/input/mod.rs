@@ -1,4 +1,5 @@
 pub(crate) mod input_handler;
+pub(crate) mod recording;
 pub(crate) mod utils;
 
 #[derive(Clone, Copy, Debug, PartialEq)]
@@ -11,28 +12,47 @@ pub(crate) enum Input {
 	ActionPick,
 	ActionReword,
 	ActionSquash,
+	Autosquash,
 	Backspace,
 	Character(char),
+	CustomCommand(usize),
 	Delete,
 	Edit,
+	EditHash,
 	Enter,
+	Escape,
+	ExportJson,
 	ForceAbort,
 	ForceRebase,
 	Help,
+	KillToEnd,
+	KillToStart,
+	KillWordBefore,
 	MoveCursorDown,
+	MoveCursorEnd,
+	MoveCursorHome,
 	MoveCursorLeft,
 	MoveCursorPageDown,
 	MoveCursorPageUp,
 	MoveCursorRight,
 	MoveCursorUp,
+	MoveCursorWordLeft,
+	MoveCursorWordRight,
+	MouseClick(usize, usize),
+	MouseDoubleClick(usize, usize),
+	MouseShiftClick(usize, usize),
 	No,
 	OpenInEditor,
 	Other,
 	Rebase,
 	Resize,
 	ShowCommit,
+	Submit,
 	SwapSelectedDown,
 	SwapSelectedUp,
+	ToggleCollapse,
+	ToggleFocus,
 	ToggleVisualMode,
+	Yank,
 	Yes,
 }
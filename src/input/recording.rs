@@ -0,0 +1,261 @@
+use crate::input::Input;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{Read, Write};
+
+const RECORDING_HEADER: &str = "# interactive-rebase-tool input recording";
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum RecordedEvent {
+	Input(Input),
+	Size(i32, i32),
+}
+
+fn format_character(c: char) -> String {
+	// a space would be lost to trailing whitespace, so it is always escaped
+	if c == ' ' {
+		return String::from("\\u{20}");
+	}
+	c.escape_default().to_string()
+}
+
+fn parse_character(value: &str) -> Option<char> {
+	let mut chars = value.chars();
+	match (chars.next(), chars.next()) {
+		(Some(c), None) => Some(c),
+		(Some('\\'), Some('u')) if value.starts_with("\\u{") && value.ends_with('}') => {
+			std::char::from_u32(u32::from_str_radix(&value[3..value.len() - 1], 16).ok()?)
+		},
+		(Some('\\'), Some(c)) if value.len() == 2 => {
+			match c {
+				't' => Some('\t'),
+				'r' => Some('\r'),
+				'n' => Some('\n'),
+				'\'' | '"' | '\\' => Some(c),
+				_ => None,
+			}
+		},
+		_ => None,
+	}
+}
+
+fn format_input(input: Input) -> String {
+	match input {
+		Input::Character(c) => format!("Character {}", format_character(c)),
+		Input::CustomCommand(index) => format!("CustomCommand {}", index),
+		Input::MouseClick(column, row) => format!("MouseClick {} {}", column, row),
+		Input::MouseDoubleClick(column, row) => format!("MouseDoubleClick {} {}", column, row),
+		Input::MouseShiftClick(column, row) => format!("MouseShiftClick {} {}", column, row),
+		_ => format!("{:?}", input),
+	}
+}
+
+/// Parses the column and row of a mouse input
+fn parse_position(value: &str) -> Option<(usize, usize)> {
+	let mut position = value.split(' ').map(|s| s.parse::<usize>().ok());
+	match (position.next(), position.next(), position.next()) {
+		(Some(Some(column)), Some(Some(row)), None) => Some((column, row)),
+		_ => None,
+	}
+}
+
+fn parse_input(value: &str) -> Option<Input> {
+	let (name, argument) = match value.find(' ') {
+		Some(index) => (&value[..index], Some(&value[index + 1..])),
+		None => (value, None),
+	};
+
+	let input = match (name, argument) {
+		("Abort", None) => Input::Abort,
+		("ActionBreak", None) => Input::ActionBreak,
+		("ActionDrop", None) => Input::ActionDrop,
+		("ActionEdit", None) => Input::ActionEdit,
+		("ActionFixup", None) => Input::ActionFixup,
+		("ActionPick", None) => Input::ActionPick,
+		("ActionReword", None) => Input::ActionReword,
+		("ActionSquash", None) => Input::ActionSquash,
+		("Autosquash", None) => Input::Autosquash,
+		("Backspace", None) => Input::Backspace,
+		("Character", Some(c)) => Input::Character(parse_character(c)?),
+		("CustomCommand", Some(index)) => Input::CustomCommand(index.parse().ok()?),
+		("Delete", None) => Input::Delete,
+		("Edit", None) => Input::Edit,
+		("EditHash", None) => Input::EditHash,
+		("Enter", None) => Input::Enter,
+		("Escape", None) => Input::Escape,
+		("ExportJson", None) => Input::ExportJson,
+		("ForceAbort", None) => Input::ForceAbort,
+		("ForceRebase", None) => Input::ForceRebase,
+		("Help", None) => Input::Help,
+		("KillToEnd", None) => Input::KillToEnd,
+		("KillToStart", None) => Input::KillToStart,
+		("KillWordBefore", None) => Input::KillWordBefore,
+		("MoveCursorDown", None) => Input::MoveCursorDown,
+		("MoveCursorEnd", None) => Input::MoveCursorEnd,
+		("MoveCursorHome", None) => Input::MoveCursorHome,
+		("MoveCursorLeft", None) => Input::MoveCursorLeft,
+		("MoveCursorPageDown", None) => Input::MoveCursorPageDown,
+		("MoveCursorPageUp", None) => Input::MoveCursorPageUp,
+		("MoveCursorRight", None) => Input::MoveCursorRight,
+		("MoveCursorUp", None) => Input::MoveCursorUp,
+		("MoveCursorWordLeft", None) => Input::MoveCursorWordLeft,
+		("MoveCursorWordRight", None) => Input::MoveCursorWordRight,
+		("MouseClick", Some(position)) => {
+			let (column, row) = parse_position(position)?;
+			Input::MouseClick(column, row)
+		},
+		("MouseDoubleClick", Some(position)) => {
+			let (column, row) = parse_position(position)?;
+			Input::MouseDoubleClick(column, row)
+		},
+		("MouseShiftClick", Some(position)) => {
+			let (column, row) = parse_position(position)?;
+			Input::MouseShiftClick(column, row)
+		},
+		("No", None) => Input::No,
+		("OpenInEditor", None) => Input::OpenInEditor,
+		("Other", None) => Input::Other,
+		("Rebase", None) => Input::Rebase,
+		("Resize", None) => Input::Resize,
+		("ShowCommit", None) => Input::ShowCommit,
+		("Submit", None) => Input::Submit,
+		("SwapSelectedDown", None) => Input::SwapSelectedDown,
+		("SwapSelectedUp", None) => Input::SwapSelectedUp,
+		("ToggleCollapse", None) => Input::ToggleCollapse,
+		("ToggleFocus", None) => Input::ToggleFocus,
+		("ToggleVisualMode", None) => Input::ToggleVisualMode,
+		("Yank", None) => Input::Yank,
+		("Yes", None) => Input::Yes,
+		_ => return None,
+	};
+	Some(input)
+}
+
+fn format_event(event: RecordedEvent) -> String {
+	match event {
+		RecordedEvent::Input(input) => format!("input {}", format_input(input)),
+		RecordedEvent::Size(width, height) => format!("size {} {}", width, height),
+	}
+}
+
+fn parse_event(line: &str) -> Option<RecordedEvent> {
+	if let Some(input) = line.strip_prefix("input ") {
+		return parse_input(input).map(RecordedEvent::Input);
+	}
+	if let Some(size) = line.strip_prefix("size ") {
+		let mut size = size.split(' ').map(|s| s.parse::<i32>().ok());
+		if let (Some(Some(width)), Some(Some(height)), None) = (size.next(), size.next(), size.next()) {
+			return Some(RecordedEvent::Size(width, height));
+		}
+	}
+	None
+}
+
+/// Parses a recording, ignoring blank lines and comments
+fn parse_recording(recording: &str) -> Result<VecDeque<RecordedEvent>, String> {
+	recording
+		.lines()
+		.enumerate()
+		.filter(|(_, line)| !line.trim().is_empty() && !line.starts_with('#'))
+		.map(|(index, line)| {
+			parse_event(line).ok_or_else(|| format!("Invalid event on line {}: {}", index + 1, line))
+		})
+		.collect()
+}
+
+pub(crate) fn load_recording(filepath: &str) -> Result<VecDeque<RecordedEvent>, String> {
+	let mut recording = String::new();
+	File::open(filepath)
+		.and_then(|mut file| file.read_to_string(&mut recording))
+		.map_err(|why| format!("Error reading recording, {}\nReason: {}", filepath, why))?;
+	parse_recording(recording.as_str()).map_err(|e| format!("Error reading recording, {}\n{}", filepath, e))
+}
+
+pub(crate) struct Recorder {
+	file: RefCell<File>,
+}
+
+impl Recorder {
+	pub(crate) fn new(filepath: &str) -> Result<Self, String> {
+		let mut file =
+			File::create(filepath).map_err(|why| format!("Error opening file, {}\nReason: {}", filepath, why))?;
+		writeln!(file, "{}", RECORDING_HEADER).map_err(|why| format!("Error writing to file, {}", why))?;
+		Ok(Self {
+			file: RefCell::new(file),
+		})
+	}
+
+	pub(crate) fn record(&self, event: RecordedEvent) {
+		// recording is best effort, a failure to write should not interrupt editing
+		let _ = writeln!(self.file.borrow_mut(), "{}", format_event(event));
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{format_event, parse_event, parse_recording, RecordedEvent};
+	use crate::input::Input;
+
+	#[test]
+	fn recording_round_trip() {
+		let events = vec![
+			RecordedEvent::Size(80, 24),
+			RecordedEvent::Input(Input::ActionDrop),
+			RecordedEvent::Input(Input::Character('a')),
+			RecordedEvent::Input(Input::Character(' ')),
+			RecordedEvent::Input(Input::Character('\n')),
+			RecordedEvent::Input(Input::Character('\\')),
+			RecordedEvent::Input(Input::Character('\u{1b}')),
+			RecordedEvent::Input(Input::Character('é')),
+			RecordedEvent::Input(Input::CustomCommand(2)),
+			RecordedEvent::Input(Input::MouseShiftClick(3, 5)),
+			RecordedEvent::Input(Input::Resize),
+		];
+		for event in events {
+			assert_eq!(parse_event(format_event(event).as_str()), Some(event));
+		}
+	}
+
+	#[test]
+	fn recording_format() {
+		assert_eq!(format_event(RecordedEvent::Size(80, 24)), "size 80 24");
+		assert_eq!(
+			format_event(RecordedEvent::Input(Input::MoveCursorDown)),
+			"input MoveCursorDown"
+		);
+		assert_eq!(
+			format_event(RecordedEvent::Input(Input::Character(' '))),
+			"input Character \\u{20}"
+		);
+	}
+
+	#[test]
+	fn recording_parse_ignores_comments_and_blank_lines() {
+		assert_eq!(
+			parse_recording("# comment\n\nsize 10 5\ninput Yes\n").unwrap(),
+			vec![RecordedEvent::Size(10, 5), RecordedEvent::Input(Input::Yes)]
+		);
+	}
+
+	#[test]
+	fn recording_parse_invalid_event() {
+		assert_eq!(
+			parse_recording("size 10 5\ninput Unknown").unwrap_err(),
+			"Invalid event on line 2: input Unknown"
+		);
+	}
+
+	#[test]
+	fn recording_parse_invalid_size() {
+		assert_eq!(parse_event("size 10"), None);
+		assert_eq!(parse_event("size 10 5 1"), None);
+	}
+
+	#[test]
+	fn recording_parse_missing_argument() {
+		assert_eq!(parse_event("input MouseClick"), None);
+		assert_eq!(parse_event("input MouseClick 5"), None);
+		assert_eq!(parse_event("input Yes 1"), None);
+	}
+}
@@ -3,11 +3,14 @@ use pancurses::Input;
 pub(super) fn curses_input_to_string(input: Input) -> String {
 	match input {
 		Input::Character(c) => {
-			if c == '\t' {
-				String::from("Tab")
-			}
-			else {
-				c.to_string()
+			match c {
+				'\t' => String::from("Tab"),
+				'\u{1b}' => String::from("Escape"),
+				// control characters, excluding tab, line feed and carriage return, map to Control+letter
+				'\u{1}'..='\u{8}' | '\u{b}' | '\u{c}' | '\u{e}'..='\u{1a}' => {
+					format!("Control+{}", (b'a' + c as u8 - 1) as char)
+				},
+				_ => c.to_string(),
 			}
 		},
 		Input::KeyBackspace => String::from("Backspace"),
@@ -37,6 +40,7 @@ pub(super) fn curses_input_to_string(input: Input) -> String {
 		Input::KeyLeft => String::from("Left"),
 		Input::KeyNPage => String::from("PageDown"),
 		Input::KeyPPage => String::from("PageUp"),
+		Input::KeyMouse => String::from("Mouse"),
 		Input::KeyResize => String::from("Resize"),
 		Input::KeyRight => String::from("Right"),
 		Input::KeySDC => String::from("ShiftDelete"),
@@ -47,17 +51,191 @@ pub(super) fn curses_input_to_string(input: Input) -> String {
 		Input::KeySR => String::from("ShiftUp"),
 		Input::KeySRight => String::from("ShiftRight"),
 		Input::KeyUp => String::from("Up"),
+		Input::Unknown(code) => {
+			pancurses::keyname(code)
+				.and_then(|name| get_extended_key_name(name.as_str()))
+				.map(String::from)
+				.unwrap_or_else(|| String::from("Other"))
+		},
 		_ => String::from("Other"),
 	}
 }
 
+/// Gets the name of a key that curses only reports by its terminfo name, such as `kLFT5` for Control+Left
+fn get_extended_key_name(name: &str) -> Option<&'static str> {
+	match name {
+		"kLFT5" => Some("ControlLeft"),
+		"kRIT5" => Some("ControlRight"),
+		_ => None,
+	}
+}
+
+fn get_special_key_name(key: &str) -> Option<&'static str> {
+	match key.to_lowercase().as_ref() {
+		"backspace" => Some("Backspace"),
+		"control+left" => Some("ControlLeft"),
+		"control+right" => Some("ControlRight"),
+		"delete" => Some("Delete"),
+		"down" => Some("Down"),
+		"end" => Some("End"),
+		"enter" => Some("Enter"),
+		"escape" => Some("Escape"),
+		"f0" => Some("F0"),
+		"f1" => Some("F1"),
+		"f2" => Some("F2"),
+		"f3" => Some("F3"),
+		"f4" => Some("F4"),
+		"f5" => Some("F5"),
+		"f6" => Some("F6"),
+		"f7" => Some("F7"),
+		"f8" => Some("F8"),
+		"f9" => Some("F9"),
+		"f10" => Some("F10"),
+		"f11" => Some("F11"),
+		"f12" => Some("F12"),
+		"f13" => Some("F13"),
+		"f14" => Some("F14"),
+		"f15" => Some("F15"),
+		"home" => Some("Home"),
+		"insert" => Some("Insert"),
+		"left" => Some("Left"),
+		"pagedown" => Some("PageDown"),
+		"pageup" => Some("PageUp"),
+		"right" => Some("Right"),
+		"shift+delete" => Some("ShiftDelete"),
+		"shift+down" => Some("ShiftDown"),
+		"shift+end" => Some("ShiftEnd"),
+		"shift+home" => Some("ShiftHome"),
+		"shift+left" => Some("ShiftLeft"),
+		"shift+right" => Some("ShiftRight"),
+		"shift+tab" => Some("ShiftTab"),
+		"shift+up" => Some("ShiftUp"),
+		"tab" => Some("Tab"),
+		"up" => Some("Up"),
+		_ => None,
+	}
+}
+
+fn get_modifier_key_name(key: &str) -> Option<String> {
+	let lowercase_key = key.to_lowercase();
+	for (prefix, is_control) in &[("control+", true), ("ctrl+", true), ("alt+", false)] {
+		if lowercase_key.starts_with(prefix) {
+			let mut characters = key[prefix.len()..].chars();
+			return match (characters.next(), characters.next()) {
+				(Some(c), None) if *is_control && c.is_ascii_alphabetic() => {
+					Some(format!("Control+{}", c.to_ascii_lowercase()))
+				},
+				(Some(c), None) if !*is_control => Some(format!("Alt+{}", c)),
+				_ => None,
+			};
+		}
+	}
+	None
+}
+
+/// Gets the normalized name of a single key, or `None` if the value is not a single key
+pub(crate) fn get_key_name(key: &str) -> Option<String> {
+	if let Some(name) = get_special_key_name(key) {
+		return Some(String::from(name));
+	}
+	if let Some(name) = get_modifier_key_name(key) {
+		return Some(name);
+	}
+	if key.chars().count() == 1 {
+		return Some(String::from(key));
+	}
+	None
+}
+
+fn parse_key_binding(binding: &str) -> Result<Vec<String>, String> {
+	let mut keys = vec![];
+	for key in binding.split_whitespace() {
+		match get_key_name(key) {
+			Some(name) => keys.push(name),
+			None => {
+				let lowercase_key = key.to_lowercase();
+				if lowercase_key.starts_with("control+")
+					|| lowercase_key.starts_with("ctrl+")
+					|| lowercase_key.starts_with("alt+")
+				{
+					return Err(format!("Invalid key binding: {}", key));
+				}
+				keys.extend(key.chars().map(|c| c.to_string()));
+			},
+		}
+	}
+	Ok(keys)
+}
+
+/// Parses a list of key bindings into lists of key sequences
+///
+/// Each binding is either a single key, such as `d`, `Down` or `Control+d`, or a sequence of keys that must be pressed
+/// one after another. Without a comma, whitespace separates the bindings, and a sequence of printable characters is
+/// written without spaces, such as `gg`. With a comma, commas separate the bindings, and whitespace separates the keys
+/// of a sequence, so `g g, d d` is the `gg` and `dd` sequences.
+pub(crate) fn parse_key_bindings(value: &str) -> Result<Vec<Vec<String>>, String> {
+	// a value of only whitespace is a binding to the whitespace key itself
+	if !value.is_empty() && value.trim().is_empty() {
+		return Ok(vec![vec![value.chars().take(1).collect()]]);
+	}
+
+	// a comma that does not separate two bindings, such as in `d ,`, is a binding to the comma key
+	let bindings = if value.contains(',') && value.split(',').all(|binding| !binding.trim().is_empty()) {
+		value.split(',').map(parse_key_binding).collect::<Result<Vec<_>, _>>()?
+	}
+	else {
+		value
+			.split_whitespace()
+			.map(parse_key_binding)
+			.collect::<Result<Vec<_>, _>>()?
+	};
+
+	if bindings.is_empty() {
+		return Err(String::from("No key binding provided"));
+	}
+	Ok(bindings)
+}
+
+/// Creates the display value of the keys of a single binding
+pub(crate) fn format_key_binding(keys: &[String]) -> String {
+	keys.join(" ")
+}
+
+/// Creates the display value for a list of key bindings, using the comma separated form when there is a sequence
+pub(crate) fn format_key_bindings(bindings: &[Vec<String>]) -> String {
+	let separator = if bindings.iter().any(|keys| keys.len() > 1) { ", " } else { " " };
+	bindings
+		.iter()
+		.map(|keys| format_key_binding(keys))
+		.collect::<Vec<String>>()
+		.join(separator)
+}
+
 pub(crate) fn get_input_short_name(input: &str) -> String {
+	if input.contains(',') && input.trim() != "," {
+		return input
+			.split(',')
+			.map(|binding| get_input_short_name(binding.trim()))
+			.collect::<Vec<String>>()
+			.join(",");
+	}
+	if input.trim().contains(char::is_whitespace) {
+		return input
+			.split_whitespace()
+			.map(get_input_short_name)
+			.collect::<Vec<String>>()
+			.join(" ");
+	}
+
 	match input {
 		"Backspace" => String::from("bs"),
+		"ControlLeft" => String::from("clf"),
+		"ControlRight" => String::from("crt"),
 		"Delete" => String::from("dl"),
 		"Down" => String::from("dn"),
 		"End" => String::from("end"),
 		"Enter" => String::from("ent"),
+		"Escape" => String::from("esc"),
 		"Home" => String::from("hm"),
 		"Insert" => String::from("ins"),
 		"Left" => String::from("lf"),
@@ -78,3 +256,120 @@ pub(crate) fn get_input_short_name(input: &str) -> String {
 		_ => String::from(input),
 	}
 }
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		curses_input_to_string,
+		format_key_bindings,
+		get_extended_key_name,
+		get_input_short_name,
+		parse_key_bindings,
+	};
+	use pancurses::Input;
+
+	fn bindings(bindings: &[&[&str]]) -> Vec<Vec<String>> {
+		bindings
+			.iter()
+			.map(|keys| keys.iter().map(|k| String::from(*k)).collect())
+			.collect()
+	}
+
+	#[test]
+	fn parse_key_bindings_single_character() {
+		assert_eq!(parse_key_bindings("d").unwrap(), bindings(&[&["d"]]));
+	}
+
+	#[test]
+	fn parse_key_bindings_special_key() {
+		assert_eq!(parse_key_bindings("pagedown").unwrap(), bindings(&[&["PageDown"]]));
+	}
+
+	#[test]
+	fn parse_key_bindings_multiple() {
+		assert_eq!(
+			parse_key_bindings("d  Down\tx").unwrap(),
+			bindings(&[&["d"], &["Down"], &["x"]])
+		);
+	}
+
+	#[test]
+	fn parse_key_bindings_modifiers() {
+		assert_eq!(
+			parse_key_bindings("control+D Ctrl+u Alt+j Alt+J").unwrap(),
+			bindings(&[&["Control+d"], &["Control+u"], &["Alt+j"], &["Alt+J"]])
+		);
+	}
+
+	#[test]
+	fn parse_key_bindings_sequence() {
+		assert_eq!(
+			parse_key_bindings("gg dd").unwrap(),
+			bindings(&[&["g", "g"], &["d", "d"]])
+		);
+		assert_eq!(
+			parse_key_bindings("g g, d d").unwrap(),
+			bindings(&[&["g", "g"], &["d", "d"]])
+		);
+		assert_eq!(
+			parse_key_bindings("g Down,Control+d").unwrap(),
+			bindings(&[&["g", "Down"], &["Control+d"]])
+		);
+	}
+
+	#[test]
+	fn parse_key_bindings_comma_key() {
+		assert_eq!(parse_key_bindings(",").unwrap(), bindings(&[&[","]]));
+		assert_eq!(parse_key_bindings("d ,").unwrap(), bindings(&[&["d"], &[","]]));
+	}
+
+	#[test]
+	fn parse_key_bindings_whitespace_key() {
+		assert_eq!(parse_key_bindings(" ").unwrap(), bindings(&[&[" "]]));
+	}
+
+	#[test]
+	fn parse_key_bindings_invalid_modifier() {
+		assert_eq!(
+			parse_key_bindings("Control+1").unwrap_err(),
+			"Invalid key binding: Control+1"
+		);
+		assert_eq!(parse_key_bindings("Alt+jk").unwrap_err(), "Invalid key binding: Alt+jk");
+	}
+
+	#[test]
+	fn parse_key_bindings_empty() {
+		assert_eq!(parse_key_bindings("").unwrap_err(), "No key binding provided");
+	}
+
+	#[test]
+	fn format_key_bindings_round_trip() {
+		let value = format_key_bindings(&parse_key_bindings("d control+d gg Down").unwrap());
+		assert_eq!(value, "d, Control+d, g g, Down");
+		assert_eq!(format_key_bindings(&parse_key_bindings(value.as_str()).unwrap()), value);
+		let value = format_key_bindings(&parse_key_bindings("d control+d Down").unwrap());
+		assert_eq!(value, "d Control+d Down");
+		assert_eq!(format_key_bindings(&parse_key_bindings(value.as_str()).unwrap()), value);
+	}
+
+	#[test]
+	fn curses_input_to_string_control_character() {
+		assert_eq!(curses_input_to_string(Input::Character('\u{4}')), "Control+d");
+		assert_eq!(curses_input_to_string(Input::Character('\t')), "Tab");
+		assert_eq!(curses_input_to_string(Input::Character('\n')), "\n");
+		assert_eq!(curses_input_to_string(Input::Character('\u{1b}')), "Escape");
+	}
+
+	#[test]
+	fn get_extended_key_name_control_arrows() {
+		assert_eq!(get_extended_key_name("kLFT5"), Some("ControlLeft"));
+		assert_eq!(get_extended_key_name("kRIT5"), Some("ControlRight"));
+		assert_eq!(get_extended_key_name("kLFT3"), None);
+	}
+
+	#[test]
+	fn get_input_short_name_multiple_bindings() {
+		assert_eq!(get_input_short_name("Down j"), "dn j");
+		assert_eq!(get_input_short_name("g g, Down"), "g g,dn");
+	}
+}
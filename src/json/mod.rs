@@ -0,0 +1,291 @@
+use crate::commit::status::Status;
+use crate::commit::Commit;
+use crate::git_interactive::GitInteractive;
+use crate::list::action::Action;
+use crate::list::line::Line;
+use serde_json::{json, Value};
+use std::convert::TryFrom;
+use std::fs::File;
+use std::io::{Read, Write};
+
+/// Matches each line to the first unused original line with the same hash, or the same text for lines without a hash
+fn get_original_indexes(original_lines: &[Line], lines: &[Line]) -> Vec<Option<usize>> {
+	let mut used = vec![false; original_lines.len()];
+	lines
+		.iter()
+		.map(|line| {
+			let index = original_lines.iter().enumerate().position(|(index, original)| {
+				!used[index]
+					&& if line.get_action().has_commit() {
+						original.get_hash() == line.get_hash()
+					}
+					else {
+						original.to_text() == line.to_text()
+					}
+			});
+			if let Some(i) = index {
+				used[i] = true;
+			}
+			index
+		})
+		.collect()
+}
+
+fn status_to_string(status: &Status) -> &str {
+	match status {
+		Status::Added => "added",
+		Status::Deleted => "deleted",
+		Status::Modified => "modified",
+		Status::Renamed => "renamed",
+		Status::Copied => "copied",
+		Status::Typechange => "typechange",
+		Status::Other => "other",
+	}
+}
+
+fn commit_to_json(commit: &Commit) -> Value {
+	json!({
+		"hash": commit.get_hash(),
+		"author": commit.get_author().to_string(),
+		"committer": commit.get_committer().to_string(),
+		"date": commit.get_date().to_rfc3339(),
+		"body": commit.get_body(),
+		"files": commit.get_file_stats().as_ref().map(|file_stats| {
+			file_stats
+				.iter()
+				.map(|file_stat| {
+					json!({
+						"status": status_to_string(file_stat.get_status()),
+						"from": file_stat.get_from_name(),
+						"to": file_stat.get_to_name(),
+					})
+				})
+				.collect::<Vec<Value>>()
+		}),
+	})
+}
+
+fn line_to_json(line: &Line, original_line: Option<(usize, &Line)>, commit: Option<&Commit>) -> Value {
+	let has_commit = line.get_action().has_commit();
+	json!({
+		"action": line.get_action().as_string(),
+		"hash": Some(line.get_hash()).filter(|_| has_commit),
+		"subject": Some(line.get_comment()).filter(|_| has_commit),
+		"command": Some(line.get_command()).filter(|_| *line.get_action() == Action::Exec),
+		"mutated": match original_line {
+			Some((_, original)) => original.to_text() != line.to_text(),
+			None => true,
+		},
+		"originalIndex": original_line.map(|(index, _)| index),
+		"commit": commit.map(commit_to_json),
+	})
+}
+
+/// Serializes the todo lines, loading the commit information for each line with a commit
+pub(crate) fn export_todo(git_interactive: &GitInteractive) -> String {
+	let original_lines = git_interactive.get_original_lines();
+	let lines = git_interactive.get_lines();
+	let original_indexes = get_original_indexes(original_lines, lines);
+
+	let json_lines = lines
+		.iter()
+		.zip(original_indexes)
+		.map(|(line, original_index)| {
+			let commit = if line.get_action().has_commit() {
+				Commit::from_commit_hash(line.get_hash()).ok()
+			}
+			else {
+				None
+			};
+			line_to_json(
+				line,
+				original_index.map(|index| (index, &original_lines[index])),
+				commit.as_ref(),
+			)
+		})
+		.collect::<Vec<Value>>();
+
+	serde_json::to_string_pretty(&json!({ "lines": json_lines })).unwrap_or_else(|_| String::from("{}"))
+}
+
+/// Writes the exported todo to a file, or to standard output when the path is `-`
+pub(crate) fn export_todo_to_file(git_interactive: &GitInteractive, filepath: &str) -> Result<(), String> {
+	let json = export_todo(git_interactive);
+	if filepath == "-" {
+		println!("{}", json);
+		return Ok(());
+	}
+	let mut file =
+		File::create(filepath).map_err(|why| format!("Error opening file, {}\nReason: {}", filepath, why))?;
+	writeln!(file, "{}", json).map_err(|why| format!("Error writing to file, {}", why))
+}
+
+fn get_string_field<'j>(value: &'j Value, key: &str) -> Result<Option<&'j str>, String> {
+	match value.get(key) {
+		None | Some(Value::Null) => Ok(None),
+		Some(v) => {
+			v.as_str()
+				.map(Some)
+				.ok_or_else(|| format!("Invalid {}, expected a string", key))
+		},
+	}
+}
+
+fn json_to_line(value: &Value) -> Result<Line, String> {
+	let action = get_string_field(value, "action")?.ok_or_else(|| String::from("Missing action"))?;
+	match Action::try_from(action)? {
+		Action::Break => Ok(Line::new_break()),
+		Action::Exec => {
+			let command = get_string_field(value, "command")?.ok_or_else(|| String::from("Missing command"))?;
+			Line::new(format!("exec {}", command).as_str())
+		},
+		Action::Noop => Err(String::from("Invalid action: noop")),
+		action => {
+			let hash = get_string_field(value, "hash")?.ok_or_else(|| String::from("Missing hash"))?;
+			if hash.is_empty() || hash.contains(' ') {
+				return Err(format!("Invalid hash: {}", hash));
+			}
+			let subject = get_string_field(value, "subject")?.unwrap_or("");
+			Line::new(format!("{} {} {}", action.as_string(), hash, subject).as_str())
+		},
+	}
+}
+
+/// Builds todo lines from an exported document, only the action, hash, subject and command of each line are used
+pub(crate) fn import_todo(json: &str) -> Result<Vec<Line>, String> {
+	let document: Value = serde_json::from_str(json).map_err(|e| format!("Invalid JSON: {}", e))?;
+	let lines = document
+		.get("lines")
+		.and_then(Value::as_array)
+		.ok_or_else(|| String::from("Missing lines array"))?;
+	lines
+		.iter()
+		.enumerate()
+		.map(|(index, line)| json_to_line(line).map_err(|e| format!("Invalid line {}: {}", index + 1, e)))
+		.collect()
+}
+
+pub(crate) fn import_todo_from_file(filepath: &str) -> Result<Vec<Line>, String> {
+	let mut json = String::new();
+	File::open(filepath)
+		.and_then(|mut file| file.read_to_string(&mut json))
+		.map_err(|why| format!("Error reading file, {}\nReason: {}", filepath, why))?;
+	import_todo(json.as_str()).map_err(|e| format!("Error importing JSON, {}\n{}", filepath, e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_original_indexes, import_todo, line_to_json};
+	use crate::list::action::Action;
+	use crate::list::line::Line;
+	use serde_json::Value;
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	#[test]
+	fn get_original_indexes_moved_and_added() {
+		let original = create_lines(&["pick aaa first", "exec make", "pick bbb second"]);
+		let lines = create_lines(&["pick bbb second", "break", "pick aaa first", "exec make", "exec make"]);
+		assert_eq!(get_original_indexes(&original, &lines), vec![
+			Some(2),
+			None,
+			Some(0),
+			Some(1),
+			None
+		]);
+	}
+
+	#[test]
+	fn line_to_json_commit_line() {
+		let original = Line::new("pick aaa first").unwrap();
+		let mut line = original.clone();
+		line.set_action(Action::Fixup);
+		assert_eq!(
+			serde_json::to_string_pretty(&line_to_json(&line, Some((3, &original)), None)).unwrap(),
+			"{\n  \"action\": \"fixup\",\n  \"hash\": \"aaa\",\n  \"subject\": \"first\",\n  \"command\": null,\n  \
+			 \"mutated\": true,\n  \"originalIndex\": 3,\n  \"commit\": null\n}"
+		);
+	}
+
+	#[test]
+	fn line_to_json_exec_line() {
+		let line = Line::new("exec make test").unwrap();
+		let json = line_to_json(&line, None, None);
+		assert_eq!(json.get("hash"), Some(&Value::Null));
+		assert_eq!(json.get("command").and_then(Value::as_str), Some("make test"));
+		assert_eq!(json.get("mutated"), Some(&Value::Bool(true)));
+		assert_eq!(json.get("originalIndex"), Some(&Value::Null));
+	}
+
+	#[test]
+	fn import_todo_lines() {
+		let lines = import_todo(
+			r#"{"lines": [
+				{"action": "pick", "hash": "aaa", "subject": "first", "commit": {"hash": "aaa123"}},
+				{"action": "break"},
+				{"action": "exec", "command": "make test"},
+				{"action": "f", "hash": "bbb"}
+			]}"#,
+		)
+		.unwrap();
+		assert_eq!(lines.iter().map(Line::to_text).collect::<Vec<String>>(), vec![
+			"pick aaa first",
+			"break",
+			"exec make test",
+			"fixup bbb "
+		]);
+	}
+
+	#[test]
+	fn import_todo_missing_lines() {
+		assert_eq!(import_todo("{}").unwrap_err(), "Missing lines array");
+	}
+
+	#[test]
+	fn import_todo_invalid_action() {
+		assert_eq!(
+			import_todo(r#"{"lines": [{"action": "pick", "hash": "aaa"}, {"action": "foo"}]}"#).unwrap_err(),
+			"Invalid line 2: Invalid action: foo"
+		);
+	}
+
+	#[test]
+	fn import_todo_missing_hash() {
+		assert_eq!(
+			import_todo(r#"{"lines": [{"action": "pick", "subject": "first"}]}"#).unwrap_err(),
+			"Invalid line 1: Missing hash"
+		);
+	}
+
+	#[test]
+	fn import_todo_missing_command() {
+		assert_eq!(
+			import_todo(r#"{"lines": [{"action": "exec"}]}"#).unwrap_err(),
+			"Invalid line 1: Missing command"
+		);
+	}
+
+	#[test]
+	fn import_todo_invalid_field_type() {
+		assert_eq!(
+			import_todo(r#"{"lines": [{"action": "pick", "hash": 1}]}"#).unwrap_err(),
+			"Invalid line 1: Invalid hash, expected a string"
+		);
+	}
+
+	#[test]
+	fn import_todo_invalid_json() {
+		for json in &[
+			r#"{"lines": [}"#,
+			r#"{"lines": 1-2}"#,
+			r#"{"lines": +1}"#,
+			r#"{"lines": 01}"#,
+			r#"{"lines": ["\ud800\u0041"]}"#,
+			r#"{"lines": []} []"#,
+		] {
+			assert!(import_todo(json).unwrap_err().starts_with("Invalid JSON: "), "{}", json);
+		}
+	}
+}
@@ -0,0 +1,428 @@
+use crate::constants::MESSAGE_FILE_PREFIX;
+use crate::list::action::Action;
+use crate::list::message::is_message_command;
+use crate::setup::get_tool_command;
+use crate::shell::shell_quote;
+use git2::{Commit, Oid, Repository, RepositoryState, Sort};
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+const PREPARED_TODO_FILE_NAME: &str = "interactive-rebase-tool-todo";
+const UPDATE_REF_PREFIX: &str = "update-ref ";
+
+/// A rebase with a todo file prepared from a range of commits, before git has been started
+pub(crate) struct Rebase {
+	branch: Option<String>,
+	todo_filepath: PathBuf,
+	upstream: String,
+}
+
+/// Splits a range into the upstream and the branch, where a branch of `HEAD` is the current branch
+fn parse_range(range: &str) -> Result<(String, Option<String>), String> {
+	if range.contains("...") {
+		return Err(format!("Invalid range, {}, symmetric ranges are not supported", range));
+	}
+	let index = range
+		.find("..")
+		.ok_or_else(|| format!("Invalid range, {}, expected <upstream>..<branch>", range))?;
+	let (upstream, branch) = (&range[..index], &range[index + 2..]);
+	if upstream.is_empty() {
+		return Err(format!("Invalid range, {}, the upstream is required", range));
+	}
+	let branch = if branch.is_empty() || branch == "HEAD" {
+		None
+	}
+	else {
+		Some(String::from(branch))
+	};
+	Ok((String::from(upstream), branch))
+}
+
+/// Gets the patch id of a commit, which is the same for commits that make the same change, none for merges, root
+/// commits and empty commits, which git never considers to be the same as another commit
+fn get_patch_id(repository: &Repository, commit: &Commit<'_>) -> Result<Option<Oid>, git2::Error> {
+	if commit.parent_count() != 1 {
+		return Ok(None);
+	}
+	let diff = repository.diff_tree_to_tree(Some(&commit.parent(0)?.tree()?), Some(&commit.tree()?), None)?;
+	if diff.deltas().len() == 0 {
+		return Ok(None);
+	}
+	diff.patchid(None).map(Some)
+}
+
+/// Gets the patch ids of the commits in the upstream that are not in the branch
+fn get_upstream_patch_ids(repository: &Repository, upstream: &str, branch: &str) -> Result<HashSet<Oid>, git2::Error> {
+	let mut revwalk = repository.revwalk()?;
+	revwalk.push_range(format!("{}..{}", branch, upstream).as_str())?;
+	let mut patch_ids = HashSet::new();
+	for oid in revwalk {
+		if let Some(patch_id) = get_patch_id(repository, &repository.find_commit(oid?)?)? {
+			patch_ids.insert(patch_id);
+		}
+	}
+	Ok(patch_ids)
+}
+
+/// Gets the todo lines for the commits in the range, oldest first, skipping merges and commits with the same
+/// change as a commit in the upstream, like `git rebase` does
+fn get_todo_lines(repository: &Repository, upstream: &str, branch: &str) -> Result<Vec<String>, String> {
+	let error = |e: git2::Error| format!("Error reading commits, {}..{}: {}", upstream, branch, e);
+	let upstream_patch_ids = get_upstream_patch_ids(repository, upstream, branch).map_err(error)?;
+	let mut revwalk = repository.revwalk().map_err(error)?;
+	revwalk.set_sorting(Sort::TOPOLOGICAL | Sort::REVERSE).map_err(error)?;
+	revwalk
+		.push_range(format!("{}..{}", upstream, branch).as_str())
+		.map_err(error)?;
+
+	let mut lines = vec![];
+	for oid in revwalk {
+		let commit = repository.find_commit(oid.map_err(error)?).map_err(error)?;
+		if commit.parent_count() > 1 {
+			continue;
+		}
+		if !upstream_patch_ids.is_empty() {
+			if let Some(patch_id) = get_patch_id(repository, &commit).map_err(error)? {
+				if upstream_patch_ids.contains(&patch_id) {
+					continue;
+				}
+			}
+		}
+		let hash = commit.as_object().short_id().map_err(error)?;
+		lines.push(format!(
+			"pick {} {}",
+			hash.as_str().unwrap_or(""),
+			commit.summary().unwrap_or("")
+		));
+	}
+	Ok(lines)
+}
+
+/// Removes the files with the new messages of commits, which are written to the directory of the todo
+fn remove_message_files(todo_filepath: &Path) {
+	let directory = todo_filepath.parent().unwrap_or_else(|| Path::new(""));
+	if let Ok(entries) = fs::read_dir(directory) {
+		for entry in entries.filter_map(Result::ok) {
+			if entry.file_name().to_string_lossy().starts_with(MESSAGE_FILE_PREFIX) {
+				let _ = fs::remove_file(entry.path());
+			}
+		}
+	}
+}
+
+/// Gets the hash of a todo line with a commit, in the full or abbreviated form of the action
+fn get_line_hash(line: &str) -> Option<&str> {
+	let mut words = line.split_whitespace();
+	match (words.next().map(Action::try_from), words.next()) {
+		(Some(Ok(action)), Some(hash)) if action.has_commit() => Some(hash),
+		_ => None,
+	}
+}
+
+/// Checks if a todo line belongs to the commit before it, as a fixup, a squash, the exec that amends its message, or
+/// a reference that is updated to it
+fn is_following_line(line: &str) -> bool {
+	if line.starts_with(UPDATE_REF_PREFIX) {
+		return true;
+	}
+	if let Some(command) = line.strip_prefix("exec ") {
+		return is_message_command(command);
+	}
+	match line.split_whitespace().next().map(Action::try_from) {
+		Some(Ok(action)) => action == Action::Fixup || action == Action::Squash,
+		_ => false,
+	}
+}
+
+/// Adds the `update-ref` lines of the todo generated by git, such as for `rebase.updateRefs`, to the prepared todo
+///
+/// Each line is added after the nearest commit before it in the generated todo that is in the prepared todo, and the
+/// lines that follow that commit, so the reference is updated to the rewritten commit. Hashes are compared by prefix,
+/// since git and the prepared todo may abbreviate them to different lengths.
+fn add_update_ref_lines(prepared: &str, generated: &str) -> String {
+	let mut lines: Vec<&str> = prepared.lines().collect();
+	let mut hashes: Vec<&str> = vec![];
+	for line in generated.lines() {
+		if let Some(hash) = get_line_hash(line) {
+			hashes.push(hash);
+			continue;
+		}
+		if !line.starts_with(UPDATE_REF_PREFIX) {
+			continue;
+		}
+		let commit_index = hashes.iter().rev().find_map(|hash| {
+			lines.iter().position(|l| {
+				match get_line_hash(l) {
+					Some(h) => h.starts_with(hash) || hash.starts_with(h),
+					None => false,
+				}
+			})
+		});
+		let mut index = commit_index.map_or(0, |i| i + 1);
+		while index < lines.len() && is_following_line(lines[index]) {
+			index += 1;
+		}
+		lines.insert(index, line);
+	}
+	lines.iter().map(|l| format!("{}\n", l)).collect()
+}
+
+/// Replaces the todo generated by git with the prepared todo, keeping the `update-ref` lines of the generated todo
+pub(crate) fn write_prepared_todo(prepared_filepath: &str, todo_filepath: &str) -> Result<(), String> {
+	let prepared = fs::read_to_string(prepared_filepath)
+		.map_err(|why| format!("Error reading file, {}\nReason: {}", prepared_filepath, why))?;
+	let generated = fs::read_to_string(todo_filepath)
+		.map_err(|why| format!("Error reading file, {}\nReason: {}", todo_filepath, why))?;
+	fs::write(todo_filepath, add_update_ref_lines(prepared.as_str(), generated.as_str()))
+		.map_err(|why| format!("Error writing to file, {}\nReason: {}", todo_filepath, why))
+}
+
+impl Rebase {
+	/// Writes a todo file for the commits after the upstream, returns none when there are no commits to rebase
+	pub(crate) fn prepare(upstream: Option<&str>, range: Option<&str>) -> Result<Option<Self>, String> {
+		let (upstream, branch) = match (upstream, range) {
+			(_, Some(range)) => parse_range(range)?,
+			(Some(upstream), None) if upstream.contains("..") => parse_range(upstream)?,
+			(Some(upstream), None) => (String::from(upstream), None),
+			(None, None) => return Err(String::from("Either an upstream or a range is required")),
+		};
+
+		let repository = Repository::open_from_env().map_err(|e| format!("Error opening repository: {}", e))?;
+		if repository.state() != RepositoryState::Clean {
+			return Err(String::from(
+				"A rebase, merge or other operation is already in progress in the repository",
+			));
+		}
+
+		let lines = get_todo_lines(&repository, upstream.as_str(), branch.as_deref().unwrap_or("HEAD"))?;
+		if lines.is_empty() {
+			return Ok(None);
+		}
+
+		let todo_filepath = repository.path().join(PREPARED_TODO_FILE_NAME);
+		let mut contents = lines.join("\n");
+		contents.push('\n');
+		fs::write(&todo_filepath, contents)
+			.map_err(|why| format!("Error writing to file, {}\nReason: {}", todo_filepath.display(), why))?;
+
+		Ok(Some(Self {
+			branch,
+			todo_filepath,
+			upstream,
+		}))
+	}
+
+	pub(crate) fn get_todo_filepath(&self) -> &PathBuf {
+		&self.todo_filepath
+	}
+
+	/// Checks if the edited todo still has lines, the todo is emptied when the rebase is aborted
+	pub(crate) fn has_todo_lines(&self) -> Result<bool, String> {
+		let contents = fs::read_to_string(&self.todo_filepath).map_err(|why| {
+			format!(
+				"Error reading file, {}\nReason: {}",
+				self.todo_filepath.display(),
+				why
+			)
+		})?;
+		Ok(contents.lines().any(|l| !l.trim().is_empty() && !l.starts_with('#')))
+	}
+
+	/// Starts `git rebase -i` with this tool as the sequence editor, replacing the todo git generates with the
+	/// prepared todo, returning if git was successful
+	pub(crate) fn start(&self) -> Result<bool, String> {
+		let editor = format!(
+			"{} --prepared-todo {}",
+			get_tool_command()?,
+			shell_quote(self.todo_filepath.to_string_lossy().as_ref())
+		);
+		let mut command = Command::new("git");
+		command
+			.arg("rebase")
+			.arg("--interactive")
+			.arg(self.upstream.as_str())
+			.env("GIT_SEQUENCE_EDITOR", editor);
+		if let Some(ref branch) = self.branch {
+			command.arg(branch.as_str());
+		}
+		let status = command.status().map_err(|e| format!("Error starting git rebase: {}", e));
+		let _ = fs::remove_file(&self.todo_filepath);
+		// the exec lines of a rebase that stopped, such as for an edit, still need the message files when continued
+		if let Ok(RepositoryState::Clean) = Repository::open_from_env().map(|repository| repository.state()) {
+			remove_message_files(&self.todo_filepath);
+		}
+		Ok(status?.success())
+	}
+
+	/// Removes the prepared todo file and message files, failures are ignored since the files are only left in the
+	/// git directory
+	pub(crate) fn remove(&self) {
+		let _ = fs::remove_file(&self.todo_filepath);
+		remove_message_files(&self.todo_filepath);
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{add_update_ref_lines, get_todo_lines, parse_range, remove_message_files};
+	use crate::temp_path::TempPath;
+	use git2::{Oid, Repository, Signature};
+	use std::fs;
+
+	fn commit(repository: &Repository, message: &str, parents: &[Oid]) -> Oid {
+		let signature = Signature::now("Tester", "tester@example.com").unwrap();
+		let tree = repository
+			.find_tree(repository.index().unwrap().write_tree().unwrap())
+			.unwrap();
+		let parents: Vec<git2::Commit<'_>> = parents.iter().map(|p| repository.find_commit(*p).unwrap()).collect();
+		let parents: Vec<&git2::Commit<'_>> = parents.iter().collect();
+		repository
+			.commit(None, &signature, &signature, message, &tree, parents.as_slice())
+			.unwrap()
+	}
+
+	/// Commits a file with the content on top of the tree of the parent
+	fn commit_file(repository: &Repository, name: &str, content: &str, message: &str, parent: Oid) -> Oid {
+		let signature = Signature::now("Tester", "tester@example.com").unwrap();
+		let parent = repository.find_commit(parent).unwrap();
+		let parent_tree = parent.tree().unwrap();
+		let mut builder = repository.treebuilder(Some(&parent_tree)).unwrap();
+		let blob = repository.blob(content.as_bytes()).unwrap();
+		builder.insert(name, blob, 0o100_644).unwrap();
+		let tree = repository.find_tree(builder.write().unwrap()).unwrap();
+		repository
+			.commit(None, &signature, &signature, message, &tree, &[&parent])
+			.unwrap()
+	}
+
+	#[test]
+	fn launcher_parse_range() {
+		assert_eq!(parse_range("main..HEAD").unwrap(), (String::from("main"), None));
+		assert_eq!(parse_range("main..").unwrap(), (String::from("main"), None));
+		assert_eq!(
+			parse_range("main..feature").unwrap(),
+			(String::from("main"), Some(String::from("feature")))
+		);
+	}
+
+	#[test]
+	fn launcher_parse_range_invalid() {
+		assert_eq!(
+			parse_range("main").unwrap_err(),
+			"Invalid range, main, expected <upstream>..<branch>"
+		);
+		assert_eq!(parse_range("..HEAD").unwrap_err(), "Invalid range, ..HEAD, the upstream is required");
+		assert_eq!(
+			parse_range("main...HEAD").unwrap_err(),
+			"Invalid range, main...HEAD, symmetric ranges are not supported"
+		);
+	}
+
+	#[test]
+	fn launcher_get_todo_lines() {
+		let directory = TempPath::new("launcher");
+		let repository = Repository::init(directory.get_path()).unwrap();
+		let base = commit(&repository, "base", &[]);
+		let first = commit(&repository, "first\n\nbody", &[base]);
+		let side = commit(&repository, "side", &[base]);
+		let merge = commit(&repository, "merge", &[first, side]);
+		let second = commit(&repository, "second", &[merge]);
+
+		let short = |oid: Oid| {
+			String::from(
+				repository
+					.find_object(oid, None)
+					.unwrap()
+					.short_id()
+					.unwrap()
+					.as_str()
+					.unwrap(),
+			)
+		};
+		let lines = get_todo_lines(&repository, base.to_string().as_str(), second.to_string().as_str()).unwrap();
+		assert_eq!(lines.len(), 3);
+		assert_eq!(lines[lines.len() - 1], format!("pick {} second", short(second)));
+		assert!(lines.contains(&format!("pick {} first", short(first))));
+		assert!(lines.contains(&format!("pick {} side", short(side))));
+	}
+
+	#[test]
+	fn launcher_get_todo_lines_skips_upstream_changes() {
+		let directory = TempPath::new("launcher-cherry-pick");
+		let repository = Repository::init(directory.get_path()).unwrap();
+		let base = commit(&repository, "base", &[]);
+		let upstream = commit_file(&repository, "a", "a\n", "upstream", base);
+		let picked = commit_file(&repository, "a", "a\n", "picked", base);
+		let empty = commit(&repository, "empty", &[picked]);
+		let changed = commit_file(&repository, "a", "b\n", "changed", base);
+		let merge = commit(&repository, "merge", &[empty, changed]);
+		let other = commit_file(&repository, "b", "b\n", "other", merge);
+
+		let lines = get_todo_lines(&repository, upstream.to_string().as_str(), other.to_string().as_str()).unwrap();
+		let subjects: Vec<&str> = lines.iter().map(|l| l.splitn(3, ' ').nth(2).unwrap()).collect();
+		assert_eq!(subjects.len(), 3);
+		assert!(!subjects.contains(&"picked"));
+		assert!(subjects.contains(&"empty"));
+		assert!(subjects.contains(&"changed"));
+		assert_eq!(subjects[2], "other");
+	}
+
+	#[test]
+	fn launcher_remove_message_files() {
+		let directory = TempPath::new_directory("launcher-messages");
+		let todo_filepath = directory.join("interactive-rebase-tool-todo");
+		fs::write(&todo_filepath, "pick aaa first\n").unwrap();
+		fs::write(directory.join("interactive-rebase-tool-message-aaa"), "first\n").unwrap();
+		fs::write(directory.join("interactive-rebase-tool-message-bbb"), "second\n").unwrap();
+		fs::write(directory.join("config"), "").unwrap();
+
+		remove_message_files(&todo_filepath);
+		let mut names: Vec<String> = fs::read_dir(directory.get_path())
+			.unwrap()
+			.map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+			.collect();
+		names.sort();
+		assert_eq!(names, vec!["config", "interactive-rebase-tool-todo"]);
+	}
+
+	#[test]
+	fn launcher_add_update_ref_lines() {
+		let generated = "pick aaaaaaa first\nupdate-ref refs/heads/one\n\npick bbbbbbb second\npick ccccccc \
+		                 third\nupdate-ref refs/heads/two\nupdate-ref refs/heads/three\n\n# Rebase onto\n";
+		let prepared = "pick ccccccccc third\nfixup bbbbbbbbb second\nexec git commit --amend --allow-empty -F \
+		                '/repo/.git/interactive-rebase-tool-message-ccccccccc'\npick aaaaaaaaa first\n";
+		assert_eq!(
+			add_update_ref_lines(prepared, generated),
+			"pick ccccccccc third\nfixup bbbbbbbbb second\nexec git commit --amend --allow-empty -F \
+			 '/repo/.git/interactive-rebase-tool-message-ccccccccc'\nupdate-ref refs/heads/two\nupdate-ref \
+			 refs/heads/three\npick aaaaaaaaa first\nupdate-ref refs/heads/one\n"
+		);
+	}
+
+	#[test]
+	fn launcher_add_update_ref_lines_removed_commit() {
+		// a reference after a commit that is no longer in the todo follows the nearest commit before it
+		assert_eq!(
+			add_update_ref_lines(
+				"pick aaa first\nexec make\n",
+				"pick aaa first\npick bbb second\nupdate-ref refs/heads/two\n"
+			),
+			"pick aaa first\nupdate-ref refs/heads/two\nexec make\n"
+		);
+		assert_eq!(
+			add_update_ref_lines("pick bbb second\n", "update-ref refs/heads/zero\npick bbb second\n"),
+			"update-ref refs/heads/zero\npick bbb second\n"
+		);
+	}
+
+	#[test]
+	fn launcher_add_update_ref_lines_without_references() {
+		assert_eq!(
+			add_update_ref_lines("drop aaa first\npick bbb second\n", "pick aaa first\npick bbb second\n"),
+			"drop aaa first\npick bbb second\n"
+		);
+	}
+}
@@ -28,6 +28,11 @@ impl Action {
 		})
 	}
 
+	/// Checks if the action refers to a commit, break, exec and noop lines do not have a commit
+	pub(crate) fn has_commit(self) -> bool {
+		self != Action::Break && self != Action::Exec && self != Action::Noop
+	}
+
 	pub(super) fn to_abbreviation(self) -> String {
 		String::from(match self {
 			Action::Break => "b",
@@ -251,4 +256,14 @@ mod tests {
 	fn action_to_abbreviation_squash() {
 		assert_eq!(Action::Squash.to_abbreviation(), "s");
 	}
+
+	#[test]
+	fn action_has_commit() {
+		assert!(!Action::Break.has_commit());
+		assert!(!Action::Exec.has_commit());
+		assert!(!Action::Noop.has_commit());
+		assert!(Action::Drop.has_commit());
+		assert!(Action::Pick.has_commit());
+		assert!(Action::Fixup.has_commit());
+	}
 }
@@ -0,0 +1,227 @@
+use crate::list::action::Action;
+use crate::list::line::Line;
+
+const MINIMUM_HASH_PREFIX_LENGTH: usize = 4;
+
+/// Checks if a line has a commit that is kept by the rebase
+fn is_commit_line(line: &Line) -> bool {
+	line.get_action().has_commit() && *line.get_action() != Action::Drop
+}
+
+/// Strips all of the fixup, squash and amend prefixes from a subject, returning the action for the first prefix
+fn parse_subject(subject: &str) -> Option<(Action, &str)> {
+	let mut action = None;
+	let mut rest = subject;
+	loop {
+		let (prefix_action, prefix) = if rest.starts_with("fixup! ") {
+			(Action::Fixup, "fixup! ")
+		}
+		else if rest.starts_with("squash! ") {
+			(Action::Squash, "squash! ")
+		}
+		else if rest.starts_with("amend! ") {
+			// the amended message replaces the original, so squash to allow the message to be edited
+			(Action::Squash, "amend! ")
+		}
+		else {
+			break;
+		};
+		if action.is_none() {
+			action = Some(prefix_action);
+		}
+		rest = rest[prefix.len()..].trim_start();
+	}
+	action.map(|a| (a, rest))
+}
+
+fn is_hash_prefix(value: &str, hash: &str) -> bool {
+	value.len() >= MINIMUM_HASH_PREFIX_LENGTH
+		&& !hash.is_empty()
+		&& value.chars().all(|c| c.is_ascii_hexdigit())
+		&& (hash.starts_with(value) || value.starts_with(hash))
+}
+
+/// Finds the target line by exact subject, then by hash prefix, then by subject prefix, preferring earlier lines
+fn find_target(lines: &[Line], index: usize, target: &str) -> Option<usize> {
+	let candidates: Vec<usize> = (0..index)
+		.rev()
+		.chain(index + 1..lines.len())
+		.filter(|i| is_commit_line(&lines[*i]) && parse_subject(lines[*i].get_comment()).is_none())
+		.collect();
+
+	candidates
+		.iter()
+		.find(|i| lines[**i].get_comment() == target)
+		.or_else(|| candidates.iter().find(|i| is_hash_prefix(target, lines[**i].get_hash())))
+		.or_else(|| {
+			candidates
+				.iter()
+				.find(|i| !target.is_empty() && lines[**i].get_comment().starts_with(target))
+		})
+		.cloned()
+}
+
+/// Moves each fixup, squash and amend commit directly after its target, returning the lines that could not be matched
+pub(crate) fn autosquash(lines: &[Line]) -> (Vec<Line>, Vec<Line>) {
+	let mut targets: Vec<Option<(usize, Action)>> = vec![None; lines.len()];
+	let mut unmatched = vec![];
+
+	for (index, line) in lines.iter().enumerate() {
+		if !is_commit_line(line) {
+			continue;
+		}
+		if let Some((action, target)) = parse_subject(line.get_comment()) {
+			match find_target(lines, index, target) {
+				Some(target_index) => targets[index] = Some((target_index, action)),
+				None => unmatched.push(line.clone()),
+			}
+		}
+	}
+
+	let mut result = vec![];
+	for (index, line) in lines.iter().enumerate() {
+		if targets[index].is_some() {
+			continue;
+		}
+		result.push(line.clone());
+		for (fixup_index, target) in targets.iter().enumerate() {
+			if let Some((target_index, action)) = *target {
+				if target_index == index {
+					let mut fixup_line = lines[fixup_index].clone();
+					fixup_line.set_action(action);
+					result.push(fixup_line);
+				}
+			}
+		}
+	}
+	(result, unmatched)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::autosquash;
+	use crate::list::line::Line;
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	fn to_text(lines: &[Line]) -> Vec<String> {
+		lines.iter().map(Line::to_text).collect()
+	}
+
+	#[test]
+	fn autosquash_no_fixups() {
+		let lines = create_lines(&["pick aaa first", "pick bbb second"]);
+		let (result, unmatched) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec!["pick aaa first", "pick bbb second"]);
+		assert!(unmatched.is_empty());
+	}
+
+	#[test]
+	fn autosquash_by_subject() {
+		let lines = create_lines(&[
+			"pick aaa first",
+			"pick bbb second",
+			"pick ccc fixup! first",
+			"pick ddd squash! second",
+			"pick eee amend! first",
+		]);
+		let (result, unmatched) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaa first",
+			"fixup ccc fixup! first",
+			"squash eee amend! first",
+			"pick bbb second",
+			"squash ddd squash! second",
+		]);
+		assert!(unmatched.is_empty());
+	}
+
+	#[test]
+	fn autosquash_by_hash() {
+		let lines = create_lines(&["pick aaaa1234 first", "pick bbb second", "pick ccc fixup! aaaa12"]);
+		let (result, _) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaaa1234 first",
+			"fixup ccc fixup! aaaa12",
+			"pick bbb second",
+		]);
+	}
+
+	#[test]
+	fn autosquash_by_subject_prefix() {
+		let lines = create_lines(&["pick aaa first commit", "pick bbb second", "pick ccc fixup! first"]);
+		let (result, _) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaa first commit",
+			"fixup ccc fixup! first",
+			"pick bbb second",
+		]);
+	}
+
+	#[test]
+	fn autosquash_exact_subject_preferred_over_prefix() {
+		let lines = create_lines(&["pick aaa first commit", "pick bbb first", "pick ccc fixup! first"]);
+		let (result, _) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaa first commit",
+			"pick bbb first",
+			"fixup ccc fixup! first",
+		]);
+	}
+
+	#[test]
+	fn autosquash_nested_chain() {
+		let lines = create_lines(&[
+			"pick aaa first",
+			"pick bbb second",
+			"pick ccc fixup! first",
+			"pick ddd fixup! fixup! first",
+		]);
+		let (result, _) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaa first",
+			"fixup ccc fixup! first",
+			"fixup ddd fixup! fixup! first",
+			"pick bbb second",
+		]);
+	}
+
+	#[test]
+	fn autosquash_target_after_fixup() {
+		let lines = create_lines(&["pick ccc fixup! second", "pick aaa first", "pick bbb second"]);
+		let (result, _) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"pick aaa first",
+			"pick bbb second",
+			"fixup ccc fixup! second",
+		]);
+	}
+
+	#[test]
+	fn autosquash_unmatched() {
+		let lines = create_lines(&["pick aaa first", "pick ccc fixup! missing"]);
+		let (result, unmatched) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec!["pick aaa first", "pick ccc fixup! missing"]);
+		assert_eq!(to_text(&unmatched), vec!["pick ccc fixup! missing"]);
+	}
+
+	#[test]
+	fn autosquash_ignores_dropped_and_exec_lines() {
+		let lines = create_lines(&[
+			"drop aaa first",
+			"exec make",
+			"pick bbb first",
+			"drop ccc fixup! first",
+		]);
+		let (result, unmatched) = autosquash(&lines);
+		assert_eq!(to_text(&result), vec![
+			"drop aaa first",
+			"exec make",
+			"pick bbb first",
+			"drop ccc fixup! first",
+		]);
+		assert!(unmatched.is_empty());
+	}
+}
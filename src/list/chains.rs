@@ -0,0 +1,163 @@
+use crate::list::action::Action;
+use crate::list::line::Line;
+use std::collections::HashSet;
+
+/// A commit followed by the fixup and squash lines that are folded into it, as indexes of the todo lines
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Chain {
+	start: usize,
+	end: usize,
+}
+
+impl Chain {
+	pub(crate) fn get_start(self) -> usize {
+		self.start
+	}
+
+	pub(crate) fn get_end(self) -> usize {
+		self.end
+	}
+
+	pub(super) fn contains(self, index: usize) -> bool {
+		index >= self.start && index <= self.end
+	}
+}
+
+fn is_chain_head(action: Action) -> bool {
+	action == Action::Pick || action == Action::Reword || action == Action::Edit
+}
+
+fn is_chain_member(action: Action) -> bool {
+	action == Action::Fixup || action == Action::Squash
+}
+
+/// Gets the chains of the lines, a commit without any fixup or squash lines after it is not a chain
+pub(crate) fn get_chains(lines: &[Line]) -> Vec<Chain> {
+	let mut chains = vec![];
+	let mut index = 0;
+	while index < lines.len() {
+		if !is_chain_head(*lines[index].get_action()) {
+			index += 1;
+			continue;
+		}
+		let mut end = index;
+		while end + 1 < lines.len() && is_chain_member(*lines[end + 1].get_action()) {
+			end += 1;
+		}
+		if end > index {
+			chains.push(Chain { start: index, end });
+		}
+		index = end + 1;
+	}
+	chains
+}
+
+pub(crate) fn get_chain(chains: &[Chain], index: usize) -> Option<Chain> {
+	chains.iter().find(|chain| chain.contains(index)).cloned()
+}
+
+/// Gets the gutter connecting the fixup and squash lines of a chain to the commit they fold into
+pub(super) fn get_gutter(chains: &[Chain], index: usize) -> &'static str {
+	match get_chain(chains, index) {
+		Some(chain) if chain.start == index => "/ ",
+		Some(chain) if chain.end == index => "\\ ",
+		Some(_) => "| ",
+		None => "  ",
+	}
+}
+
+/// Gets the badge shown on a collapsed chain, such as `+3 fixups, +1 squash`
+pub(super) fn get_badge(lines: &[Line], chain: Chain) -> String {
+	let members = &lines[chain.start + 1..=chain.end];
+	let fixups = members.iter().filter(|l| *l.get_action() == Action::Fixup).count();
+	let squashes = members.len() - fixups;
+	let mut parts = vec![];
+	if fixups > 0 {
+		parts.push(format!("+{} {}", fixups, if fixups == 1 { "fixup" } else { "fixups" }));
+	}
+	if squashes > 0 {
+		parts.push(format!("+{} {}", squashes, if squashes == 1 { "squash" } else { "squashes" }));
+	}
+	parts.join(", ")
+}
+
+pub(super) fn is_collapsed(lines: &[Line], chain: Chain, collapsed: &HashSet<String>) -> bool {
+	collapsed.contains(lines[chain.start].get_hash())
+}
+
+/// Gets the indexes of the lines that are shown, the fixup and squash lines of collapsed chains are hidden
+pub(super) fn get_visible_lines(lines: &[Line], chains: &[Chain], collapsed: &HashSet<String>) -> Vec<usize> {
+	(0..lines.len())
+		.filter(|index| {
+			get_chain(chains, *index)
+				.filter(|chain| chain.start != *index && is_collapsed(lines, *chain, collapsed))
+				.is_none()
+		})
+		.collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_badge, get_chains, get_gutter, get_visible_lines, Chain};
+	use crate::list::line::Line;
+	use std::collections::HashSet;
+
+	fn lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	#[test]
+	fn chains_get_chains() {
+		let lines = lines(&[
+			"fixup aaa orphan",
+			"pick bbb first",
+			"fixup ccc fix first",
+			"squash ddd more first",
+			"pick eee second",
+			"exec make",
+			"reword fff third",
+			"fixup ggg fix third",
+			"drop hhh fourth",
+			"fixup iii fix fourth",
+		]);
+		assert_eq!(get_chains(&lines), vec![Chain { start: 1, end: 3 }, Chain { start: 6, end: 7 }]);
+	}
+
+	#[test]
+	fn chains_get_gutter() {
+		let chains = vec![Chain { start: 1, end: 3 }];
+		let gutters: Vec<&str> = (0..5).map(|index| get_gutter(&chains, index)).collect();
+		assert_eq!(gutters, vec!["  ", "/ ", "| ", "\\ ", "  "]);
+	}
+
+	#[test]
+	fn chains_get_badge() {
+		let lines = lines(&[
+			"pick aaa first",
+			"fixup bbb fix",
+			"fixup ccc fix",
+			"squash ddd more",
+			"fixup eee fix",
+		]);
+		assert_eq!(get_badge(&lines, Chain { start: 0, end: 2 }), "+2 fixups");
+		assert_eq!(get_badge(&lines, Chain { start: 2, end: 3 }), "+1 squash");
+		assert_eq!(get_badge(&lines, Chain { start: 0, end: 4 }), "+3 fixups, +1 squash");
+	}
+
+	#[test]
+	fn chains_get_visible_lines() {
+		let lines = lines(&[
+			"pick aaa first",
+			"fixup bbb fix first",
+			"pick ccc second",
+			"fixup ddd fix second",
+			"squash eee more second",
+			"pick fff third",
+		]);
+		let chains = get_chains(&lines);
+		let mut collapsed = HashSet::new();
+		assert_eq!(get_visible_lines(&lines, &chains, &collapsed), vec![0, 1, 2, 3, 4, 5]);
+		collapsed.insert(String::from("ccc"));
+		assert_eq!(get_visible_lines(&lines, &chains, &collapsed), vec![0, 1, 2, 5]);
+	}
+}
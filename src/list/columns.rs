@@ -0,0 +1,342 @@
+use crate::config::list_format::{ListColumn, ListFormatItem};
+use crate::view::utils::{get_display_width, get_grapheme_width};
+use chrono::{DateTime, Local, TimeZone};
+use git2::Repository;
+use std::collections::HashMap;
+use unicode_segmentation::UnicodeSegmentation;
+
+const MAXIMUM_AUTHOR_LENGTH: usize = 20;
+const MINIMUM_SUBJECT_LENGTH: usize = 20;
+
+/// The details of a commit shown in the optional columns of the list
+struct CommitDetails {
+	author: String,
+	date: DateTime<Local>,
+	stats: Option<(usize, usize, usize)>,
+}
+
+fn get_initials(name: &str) -> String {
+	name.split_whitespace()
+		.filter_map(|word| word.chars().next())
+		.flat_map(char::to_uppercase)
+		.take(3)
+		.collect()
+}
+
+/// Gets the age of a date in the largest fitting unit, using the same steps as the relative dates of git
+fn get_relative_date(date: &DateTime<Local>, now: &DateTime<Local>) -> String {
+	let seconds = (*now - *date).num_seconds().max(0);
+	let (value, unit) = match seconds {
+		s if s < 60 => (s, "second"),
+		s if s < 60 * 60 => (s / 60, "minute"),
+		s if s < 60 * 60 * 24 => (s / (60 * 60), "hour"),
+		s if s < 60 * 60 * 24 * 14 => (s / (60 * 60 * 24), "day"),
+		s if s < 60 * 60 * 24 * 70 => (s / (60 * 60 * 24 * 7), "week"),
+		s if s < 60 * 60 * 24 * 365 => (s / (60 * 60 * 24 * 30), "month"),
+		s => (s / (60 * 60 * 24 * 365), "year"),
+	};
+	format!("{} {}{} ago", value, unit, if value == 1 { "" } else { "s" })
+}
+
+/// The optional columns of the list format, the details of each commit are read from the repository once
+pub(super) struct Columns {
+	details: HashMap<String, Option<CommitDetails>>,
+	format: Vec<ListFormatItem>,
+	now: DateTime<Local>,
+	repository: Option<Repository>,
+	widths: HashMap<ListColumn, usize>,
+}
+
+impl Columns {
+	pub(super) fn new(format: &[ListFormatItem]) -> Self {
+		Self {
+			details: HashMap::new(),
+			format: format.to_vec(),
+			now: Local::now(),
+			repository: None,
+			widths: HashMap::new(),
+		}
+	}
+
+	fn has_column<F>(&self, predicate: F) -> bool
+	where F: Fn(ListColumn) -> bool {
+		self.format.iter().any(|item| {
+			match item {
+				ListFormatItem::Column(column) => predicate(*column),
+				ListFormatItem::Text(_) => false,
+			}
+		})
+	}
+
+	fn load_details(&mut self, hash: &str, load_stats: bool) {
+		if self.details.contains_key(hash) {
+			return;
+		}
+		if self.repository.is_none() {
+			self.repository = Repository::open_from_env().ok();
+		}
+		// lines that cannot be found in the repository have empty columns
+		let details = self.repository.as_ref().and_then(|repository| {
+			let commit = repository.revparse_single(hash).ok()?.peel_to_commit().ok()?;
+			let stats = if load_stats {
+				let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+				repository
+					.diff_tree_to_tree(parent_tree.as_ref(), commit.tree().ok().as_ref(), None)
+					.and_then(|diff| diff.stats())
+					.ok()
+					.map(|stats| (stats.files_changed(), stats.insertions(), stats.deletions()))
+			}
+			else {
+				None
+			};
+			let author = commit.author();
+			Some(CommitDetails {
+				author: String::from(author.name().unwrap_or("")),
+				date: Local.timestamp(author.when().seconds(), 0),
+				stats,
+			})
+		});
+		self.details.insert(String::from(hash), details);
+	}
+
+	/// Loads the details of the commits, and sizes the optional columns to fit the longest value
+	pub(super) fn update(&mut self, hashes: &[&str]) {
+		if !self.has_column(ListColumn::is_optional) {
+			return;
+		}
+		let load_stats = self.has_column(|c| c == ListColumn::Files || c == ListColumn::Stats);
+		for hash in hashes {
+			self.load_details(hash, load_stats);
+		}
+		self.now = Local::now();
+
+		self.widths.clear();
+		for item in &self.format {
+			if let ListFormatItem::Column(column) = item {
+				if column.is_optional() {
+					let width = hashes
+						.iter()
+						.map(|hash| get_display_width(self.get_value(*column, hash).as_str()))
+						.max()
+						.unwrap_or(0);
+					self.widths.insert(*column, width);
+				}
+			}
+		}
+	}
+
+	/// Gets the value of an optional column, or an empty value when the commit could not be read
+	pub(super) fn get_value(&self, column: ListColumn, hash: &str) -> String {
+		let details = match self.details.get(hash) {
+			Some(Some(details)) => details,
+			_ => return String::new(),
+		};
+		match column {
+			ListColumn::Author => {
+				let mut width = 0;
+				UnicodeSegmentation::graphemes(details.author.as_str(), true)
+					.take_while(|grapheme| {
+						width += get_grapheme_width(grapheme);
+						width <= MAXIMUM_AUTHOR_LENGTH
+					})
+					.collect()
+			},
+			ListColumn::AuthorInitials => get_initials(details.author.as_str()),
+			ListColumn::Date => details.date.format("%Y-%m-%d").to_string(),
+			ListColumn::RelativeDate => get_relative_date(&details.date, &self.now),
+			ListColumn::Files => {
+				match details.stats {
+					Some((1, ..)) => String::from("1 file"),
+					Some((files, ..)) => format!("{} files", files),
+					None => String::new(),
+				}
+			},
+			ListColumn::Stats => {
+				match details.stats {
+					Some((_, insertions, deletions)) => format!("+{} -{}", insertions, deletions),
+					None => String::new(),
+				}
+			},
+			ListColumn::Action | ListColumn::Hash | ListColumn::Subject => String::new(),
+		}
+	}
+
+	/// Gets a value padded to the width of the column
+	pub(super) fn get_padded_value(&self, column: ListColumn, hash: &str) -> String {
+		let value = self.get_value(column, hash);
+		let padding = self.get_width(column, true).saturating_sub(get_display_width(value.as_str()));
+		format!("{}{}", value, " ".repeat(padding))
+	}
+
+	fn get_width(&self, column: ListColumn, is_full_width: bool) -> usize {
+		match column {
+			ListColumn::Action => if is_full_width { 6 } else { 1 },
+			ListColumn::Hash => if is_full_width { 8 } else { 3 },
+			ListColumn::Subject => 0,
+			_ => self.widths.get(&column).cloned().unwrap_or(0),
+		}
+	}
+
+	/// Gets the items of the format that fit in the view, optional columns are removed from the end, along with the
+	/// text that follows them, until the subject has room, and are never shown in the compact layout
+	pub(super) fn get_visible_format(
+		&self,
+		view_width: usize,
+		is_full_width: bool,
+		offset: usize,
+	) -> Vec<&ListFormatItem>
+	{
+		// columns without any values are hidden, along with the text that follows them
+		let is_empty = |item: &ListFormatItem| {
+			match item {
+				ListFormatItem::Column(column) => column.is_optional() && self.get_width(*column, true) == 0,
+				ListFormatItem::Text(_) => false,
+			}
+		};
+		let mut items: Vec<&ListFormatItem> = vec![];
+		for (index, item) in self.format.iter().enumerate() {
+			let follows_empty = index > 0 && is_empty(&self.format[index - 1]);
+			match item {
+				ListFormatItem::Text(_) if follows_empty => {},
+				_ if is_empty(item) => {},
+				_ => items.push(item),
+			}
+		}
+
+		loop {
+			let width: usize = items
+				.iter()
+				.map(|item| {
+					match item {
+						ListFormatItem::Column(column) => self.get_width(*column, is_full_width),
+						ListFormatItem::Text(text) => get_display_width(text.as_str()),
+					}
+				})
+				.sum();
+			if is_full_width && offset + width + MINIMUM_SUBJECT_LENGTH <= view_width {
+				break;
+			}
+			let last_optional = items.iter().rposition(|item| {
+				match item {
+					ListFormatItem::Column(column) => column.is_optional(),
+					ListFormatItem::Text(_) => false,
+				}
+			});
+			match last_optional {
+				Some(index) => {
+					if let Some(ListFormatItem::Text(_)) = items.get(index + 1) {
+						items.remove(index + 1);
+					}
+					items.remove(index);
+				},
+				None => break,
+			}
+		}
+		items
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_initials, get_relative_date, Columns, CommitDetails};
+	use crate::config::list_format::{parse_list_format, ListColumn, ListFormatItem};
+	use chrono::{Duration, Local, TimeZone};
+
+	fn create_columns(format: &str) -> Columns {
+		let mut columns = Columns::new(parse_list_format(format).unwrap().as_slice());
+		columns.now = Local.timestamp(1_600_000_000, 0);
+		columns.details.insert(String::from("aaa"), Some(CommitDetails {
+			author: String::from("Tim Oram"),
+			date: Local.timestamp(1_600_000_000, 0) - Duration::days(3),
+			stats: Some((2, 10, 4)),
+		}));
+		columns.details.insert(String::from("bbb"), None);
+		for column in &[
+			ListColumn::Author,
+			ListColumn::AuthorInitials,
+			ListColumn::Date,
+			ListColumn::RelativeDate,
+			ListColumn::Files,
+			ListColumn::Stats,
+		] {
+			let width = columns.get_value(*column, "aaa").len();
+			columns.widths.insert(*column, width);
+		}
+		columns
+	}
+
+	fn get_visible_format(columns: &Columns, view_width: usize, is_full_width: bool) -> String {
+		columns
+			.get_visible_format(view_width, is_full_width, 3)
+			.iter()
+			.map(|item| {
+				match item {
+					ListFormatItem::Column(column) => format!("{:?}", column),
+					ListFormatItem::Text(text) => text.clone(),
+				}
+			})
+			.collect()
+	}
+
+	#[test]
+	fn columns_get_initials() {
+		assert_eq!(get_initials("Tim Oram"), "TO");
+		assert_eq!(get_initials("ada king lovelace byron"), "AKL");
+		assert_eq!(get_initials(""), "");
+	}
+
+	#[test]
+	fn columns_get_relative_date() {
+		let now = Local.timestamp(1_600_000_000, 0);
+		assert_eq!(get_relative_date(&(now - Duration::seconds(1)), &now), "1 second ago");
+		assert_eq!(get_relative_date(&(now - Duration::minutes(5)), &now), "5 minutes ago");
+		assert_eq!(get_relative_date(&(now - Duration::hours(3)), &now), "3 hours ago");
+		assert_eq!(get_relative_date(&(now - Duration::days(13)), &now), "13 days ago");
+		assert_eq!(get_relative_date(&(now - Duration::days(20)), &now), "2 weeks ago");
+		assert_eq!(get_relative_date(&(now - Duration::days(100)), &now), "3 months ago");
+		assert_eq!(get_relative_date(&(now - Duration::days(800)), &now), "2 years ago");
+		assert_eq!(get_relative_date(&(now + Duration::days(1)), &now), "0 seconds ago");
+	}
+
+	#[test]
+	fn columns_get_value() {
+		let columns = create_columns("{action} {author}");
+		assert_eq!(columns.get_value(ListColumn::Author, "aaa"), "Tim Oram");
+		assert_eq!(columns.get_value(ListColumn::AuthorInitials, "aaa"), "TO");
+		assert_eq!(columns.get_value(ListColumn::RelativeDate, "aaa"), "3 days ago");
+		assert_eq!(columns.get_value(ListColumn::Files, "aaa"), "2 files");
+		assert_eq!(columns.get_value(ListColumn::Stats, "aaa"), "+10 -4");
+		assert_eq!(columns.get_value(ListColumn::Author, "bbb"), "");
+		assert_eq!(columns.get_padded_value(ListColumn::Author, "bbb"), "        ");
+	}
+
+	#[test]
+	fn columns_visible_format_fits() {
+		let columns = create_columns("{action} {hash} {author_initials} {files} {subject}");
+		assert_eq!(
+			get_visible_format(&columns, 80, true),
+			"Action Hash AuthorInitials Files Subject"
+		);
+	}
+
+	#[test]
+	fn columns_visible_format_narrow() {
+		let columns = create_columns("{action} {hash} {author_initials} {files} {subject}");
+		// action, hash and initials with their spaces take 19 columns, plus 3 for the selector and 20 for the subject
+		assert_eq!(get_visible_format(&columns, 42, true), "Action Hash AuthorInitials Subject");
+		assert_eq!(get_visible_format(&columns, 41, true), "Action Hash Subject");
+	}
+
+	#[test]
+	fn columns_visible_format_compact() {
+		let columns = create_columns("{action} {hash} {author_initials} {files} {subject}");
+		assert_eq!(get_visible_format(&columns, 80, false), "Action Hash Subject");
+	}
+
+	#[test]
+	fn columns_visible_format_empty_column() {
+		let mut columns = create_columns("{action} {author} {hash} {subject}");
+		columns.widths.insert(ListColumn::Author, 0);
+		assert_eq!(get_visible_format(&columns, 80, true), "Action Hash Subject");
+	}
+}
@@ -0,0 +1,348 @@
+use crate::config::graph_style::GraphStyle;
+use crate::list::line::Line;
+use git2::{Oid, Repository};
+use std::collections::HashMap;
+
+/// A commit of the todo and the parents it was created with
+#[derive(Clone, Debug)]
+pub(super) struct GraphCommit {
+	id: Oid,
+	parents: Vec<Oid>,
+}
+
+/// The graph drawn beside a line of the list
+#[derive(Clone, Debug, PartialEq)]
+pub(super) struct GraphRow {
+	text: String,
+	out_of_order: bool,
+}
+
+impl GraphRow {
+	pub(super) fn get_text(&self) -> &str {
+		self.text.as_str()
+	}
+
+	/// Checks if a parent of the commit is listed after it, so the commit would be applied before its parent
+	pub(super) fn is_out_of_order(&self) -> bool {
+		self.out_of_order
+	}
+}
+
+struct Characters {
+	branch_left: char,
+	branch_right: char,
+	commit: char,
+	cross: char,
+	horizontal: char,
+	lane: char,
+	merge_left: char,
+	merge_right: char,
+}
+
+const ASCII_CHARACTERS: Characters = Characters {
+	branch_left: '+',
+	branch_right: '+',
+	commit: '*',
+	cross: '+',
+	horizontal: '-',
+	lane: '|',
+	merge_left: '+',
+	merge_right: '+',
+};
+
+const UNICODE_CHARACTERS: Characters = Characters {
+	branch_left: '├',
+	branch_right: '┤',
+	commit: '●',
+	cross: '┼',
+	horizontal: '─',
+	lane: '│',
+	merge_left: '└',
+	merge_right: '┘',
+};
+
+/// Gets the graph rows of the commits, in the order they are listed, where lines without a commit continue the lanes
+///
+/// A lane is drawn from a commit down to its last child, children that are listed before their parent are not
+/// connected and are marked as out of order.
+fn get_graph_rows(commits: &[Option<&GraphCommit>], style: GraphStyle) -> Vec<GraphRow> {
+	let characters = if style == GraphStyle::Unicode {
+		&UNICODE_CHARACTERS
+	}
+	else {
+		&ASCII_CHARACTERS
+	};
+
+	let mut positions: HashMap<Oid, usize> = HashMap::new();
+	for (index, commit) in commits.iter().enumerate() {
+		if let Some(commit) = commit {
+			positions.entry(commit.id).or_insert(index);
+		}
+	}
+	let parents_above = |index: usize, commit: &GraphCommit| -> Vec<Oid> {
+		commit
+			.parents
+			.iter()
+			.filter(|p| positions.get(p).filter(|position| **position < index).is_some())
+			.cloned()
+			.collect()
+	};
+
+	// the children listed after each commit, that still have to be connected to it
+	let mut pending: HashMap<Oid, usize> = HashMap::new();
+	for (index, commit) in commits.iter().enumerate() {
+		if let Some(commit) = commit {
+			for parent in parents_above(index, commit) {
+				*pending.entry(parent).or_insert(0) += 1;
+			}
+		}
+	}
+
+	// the number of connections still to be drawn from each lane and the last commit drawn in the lane
+	let mut lanes: Vec<usize> = vec![];
+	let mut tips: Vec<Option<Oid>> = vec![];
+	let mut lane_of: HashMap<Oid, usize> = HashMap::new();
+
+	let mut cell_rows: Vec<(Vec<char>, Vec<bool>, bool)> = vec![];
+	for (index, commit) in commits.iter().enumerate() {
+		let mut cells: Vec<char> = lanes
+			.iter()
+			.map(|l| if *l > 0 { characters.lane } else { ' ' })
+			.collect();
+		let commit = match commit {
+			Some(commit) => commit,
+			None => {
+				let gaps = vec![false; cells.len()];
+				cell_rows.push((cells, gaps, false));
+				continue;
+			},
+		};
+
+		let parents = parents_above(index, commit);
+		let mut sources: Vec<usize> = vec![];
+		for parent in &parents {
+			if let Some(lane) = lane_of.get(parent) {
+				lanes[*lane] -= 1;
+				if !sources.contains(lane) {
+					sources.push(*lane);
+				}
+			}
+		}
+
+		let commit_lane = match (0..tips.len()).find(|l| tips[*l].filter(|tip| parents.contains(tip)).is_some()) {
+			Some(lane) => lane,
+			None => {
+				match (0..lanes.len()).find(|l| lanes[*l] == 0 && !sources.contains(l)) {
+					Some(lane) => lane,
+					None => {
+						lanes.push(0);
+						tips.push(None);
+						cells.push(' ');
+						lanes.len() - 1
+					},
+				}
+			},
+		};
+		lanes[commit_lane] += pending.get(&commit.id).cloned().unwrap_or(0);
+		tips[commit_lane] = Some(commit.id);
+		lane_of.insert(commit.id, commit_lane);
+		cells[commit_lane] = characters.commit;
+
+		let mut gaps = vec![false; cells.len()];
+		for source in sources.into_iter().filter(|s| *s != commit_lane) {
+			let (start, end) = if source < commit_lane {
+				(source, commit_lane)
+			}
+			else {
+				(commit_lane, source)
+			};
+			for cell in cells.iter_mut().take(end).skip(start + 1) {
+				if *cell == characters.lane {
+					*cell = characters.cross;
+				}
+				else if *cell == ' ' {
+					*cell = characters.horizontal;
+				}
+			}
+			for gap in gaps.iter_mut().take(end).skip(start) {
+				*gap = true;
+			}
+			cells[source] = match (lanes[source] > 0, source < commit_lane) {
+				(true, true) => characters.branch_left,
+				(true, false) => characters.branch_right,
+				(false, true) => characters.merge_left,
+				(false, false) => characters.merge_right,
+			};
+		}
+
+		for lane in 0..lanes.len() {
+			if lanes[lane] == 0 {
+				tips[lane] = None;
+			}
+		}
+		let out_of_order = commit
+			.parents
+			.iter()
+			.any(|p| positions.get(p).filter(|position| **position > index).is_some());
+		cell_rows.push((cells, gaps, out_of_order));
+	}
+
+	let width = cell_rows.iter().map(|(cells, ..)| cells.len()).max().unwrap_or(0);
+	cell_rows
+		.into_iter()
+		.map(|(cells, gaps, out_of_order)| {
+			let mut text = String::new();
+			for lane in 0..width {
+				text.push(cells.get(lane).cloned().unwrap_or(' '));
+				text.push(if gaps.get(lane).cloned().unwrap_or(false) {
+					characters.horizontal
+				}
+				else {
+					' '
+				});
+			}
+			GraphRow { text, out_of_order }
+		})
+		.collect()
+}
+
+/// The commit graph column of the list, the parents of each commit are read from the repository once
+pub(super) struct Graph {
+	commits: HashMap<String, Option<GraphCommit>>,
+	repository: Option<Repository>,
+	rows: Vec<GraphRow>,
+	style: GraphStyle,
+}
+
+impl Graph {
+	pub(super) fn new(style: GraphStyle) -> Self {
+		Self {
+			commits: HashMap::new(),
+			repository: None,
+			rows: vec![],
+			style,
+		}
+	}
+
+	pub(super) fn is_enabled(&self) -> bool {
+		self.style != GraphStyle::None
+	}
+
+	fn load_commit(&mut self, hash: &str) {
+		if self.commits.contains_key(hash) {
+			return;
+		}
+		if self.repository.is_none() {
+			self.repository = Repository::open_from_env().ok();
+		}
+		// lines that cannot be found in the repository are drawn without a commit
+		let commit = self.repository.as_ref().and_then(|repository| {
+			let commit = repository.revparse_single(hash).ok()?.peel_to_commit().ok()?;
+			Some(GraphCommit {
+				id: commit.id(),
+				parents: commit.parent_ids().collect(),
+			})
+		});
+		self.commits.insert(String::from(hash), commit);
+	}
+
+	/// Updates the graph for the lines in the order they are listed
+	pub(super) fn update(&mut self, lines: &[&Line]) {
+		if !self.is_enabled() {
+			return;
+		}
+		for line in lines {
+			if line.get_action().has_commit() {
+				self.load_commit(line.get_hash().as_str());
+			}
+		}
+		let commits: Vec<Option<&GraphCommit>> = lines
+			.iter()
+			.map(|line| {
+				if line.get_action().has_commit() {
+					self.commits.get(line.get_hash()).and_then(Option::as_ref)
+				}
+				else {
+					None
+				}
+			})
+			.collect();
+		self.rows = get_graph_rows(commits.as_slice(), self.style);
+	}
+
+	pub(super) fn get_row(&self, index: usize) -> Option<&GraphRow> {
+		self.rows.get(index)
+	}
+
+	pub(super) fn get_width(&self) -> usize {
+		self.rows.first().map(|row| row.text.chars().count()).unwrap_or(0)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_graph_rows, GraphCommit};
+	use crate::config::graph_style::GraphStyle;
+	use git2::Oid;
+
+	fn oid(id: u8) -> Oid {
+		Oid::from_bytes(&[id; 20]).unwrap()
+	}
+
+	fn commit(id: u8, parents: &[u8]) -> GraphCommit {
+		GraphCommit {
+			id: oid(id),
+			parents: parents.iter().map(|p| oid(*p)).collect(),
+		}
+	}
+
+	fn render(commits: &[Option<GraphCommit>], style: GraphStyle) -> Vec<String> {
+		let commits: Vec<Option<&GraphCommit>> = commits.iter().map(Option::as_ref).collect();
+		get_graph_rows(commits.as_slice(), style)
+			.into_iter()
+			.map(|row| format!("{}{}", row.text, if row.out_of_order { "!" } else { "" }))
+			.collect()
+	}
+
+	#[test]
+	fn graph_linear() {
+		let commits = vec![Some(commit(1, &[0])), Some(commit(2, &[1])), None, Some(commit(3, &[2]))];
+		assert_eq!(render(&commits, GraphStyle::Ascii), vec!["* ", "* ", "| ", "* "]);
+	}
+
+	#[test]
+	fn graph_branch_and_merge() {
+		let commits = vec![
+			Some(commit(1, &[0])),
+			Some(commit(2, &[1])),
+			Some(commit(3, &[1])),
+			Some(commit(4, &[2, 3])),
+		];
+		assert_eq!(render(&commits, GraphStyle::Ascii), vec!["*   ", "*   ", "+-* ", "*-+ "]);
+		assert_eq!(render(&commits, GraphStyle::Unicode), vec!["●   ", "●   ", "├─● ", "●─┘ "]);
+	}
+
+	#[test]
+	fn graph_crossing_lane() {
+		let commits = vec![
+			Some(commit(1, &[0])),
+			Some(commit(2, &[1])),
+			Some(commit(3, &[0])),
+			Some(commit(4, &[1])),
+			Some(commit(5, &[3])),
+		];
+		assert_eq!(render(&commits, GraphStyle::Unicode), vec![
+			"●     ",
+			"●     ",
+			"│ ●   ",
+			"└─┼─● ",
+			"  ●   ",
+		]);
+	}
+
+	#[test]
+	fn graph_out_of_order() {
+		let commits = vec![Some(commit(2, &[1])), Some(commit(1, &[0]))];
+		assert_eq!(render(&commits, GraphStyle::Ascii), vec!["* !", "* "]);
+	}
+}
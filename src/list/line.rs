@@ -1,7 +1,7 @@
 use crate::list::action::Action;
 use std::convert::TryFrom;
 
-#[derive(Debug, PartialEq)]
+#[derive(Clone, Debug, PartialEq)]
 pub(crate) struct Line {
 	action: Action,
 	hash: String,
@@ -31,6 +31,16 @@ impl Line {
 		}
 	}
 
+	pub(crate) fn new_exec(command: &str) -> Self {
+		Self {
+			action: Action::Exec,
+			command: String::from(command),
+			comment: String::from(""),
+			hash: String::from(""),
+			mutated: false,
+		}
+	}
+
 	pub(crate) fn new(input_line: &str) -> Result<Self, String> {
 		if input_line.starts_with("noop") {
 			return Ok(Self::new_noop());
@@ -78,6 +88,14 @@ impl Line {
 		}
 	}
 
+	/// Points the line at another commit, the comment holds the subject of that commit. The caller checks that it is a
+	/// different commit, since the same commit can be written with a different abbreviation.
+	pub(crate) fn set_target(&mut self, hash: &str, comment: &str) {
+		self.mutated = true;
+		self.hash = String::from(hash);
+		self.comment = String::from(comment);
+	}
+
 	pub(crate) fn edit_content(&mut self, content: &str) {
 		if let Action::Exec = self.action {
 			self.command = String::from(content)
@@ -95,7 +113,7 @@ impl Line {
 		&self.action
 	}
 
-	pub(super) fn get_command(&self) -> &String {
+	pub(crate) fn get_command(&self) -> &String {
 		&self.command
 	}
 
@@ -103,7 +121,7 @@ impl Line {
 		&self.hash
 	}
 
-	pub(super) fn get_comment(&self) -> &String {
+	pub(crate) fn get_comment(&self) -> &String {
 		&self.comment
 	}
 
@@ -237,6 +255,14 @@ mod tests {
 		assert_eq!(line.mutated, true);
 	}
 
+	#[test]
+	fn set_target() {
+		let mut line = Line::new("pick aaa comment").unwrap();
+		line.set_target("bbb", "other");
+		assert_eq!(line.to_text(), "pick bbb other");
+		assert!(line.mutated);
+	}
+
 	#[test]
 	fn to_text_pick_action() {
 		let line = Line::new("pick aaa comment").unwrap();
@@ -0,0 +1,170 @@
+use crate::constants::MESSAGE_FILE_PREFIX;
+use crate::list::action::Action;
+use crate::list::chains::{get_chain, get_chains};
+use crate::list::line::Line;
+use crate::shell::shell_quote;
+use std::collections::HashSet;
+use std::env;
+use std::path::{Path, PathBuf};
+
+const MESSAGE_COMMAND_PREFIX: &str = "git commit --amend --allow-empty -F ";
+
+/// Gets the file for the new message of a commit, kept in the directory of the todo so it is removed with the rebase
+pub(crate) fn get_message_filepath(todo_filepath: &Path, hash: &str) -> PathBuf {
+	let name = format!("{}{}", MESSAGE_FILE_PREFIX, hash);
+	let directory = todo_filepath.parent().unwrap_or_else(|| Path::new(""));
+	// git may provide a relative path to the todo, but exec commands are not run from the same directory
+	match env::current_dir() {
+		Ok(current_directory) => current_directory.join(directory).join(name),
+		Err(_) => directory.join(name),
+	}
+}
+
+fn get_message_command(message_filepath: &Path) -> String {
+	format!(
+		"{}{}",
+		MESSAGE_COMMAND_PREFIX,
+		shell_quote(message_filepath.to_string_lossy().as_ref())
+	)
+}
+
+/// Gets the hash of the commit that an exec command amends with its message file, none for any other command
+fn get_message_command_hash(command: &str) -> Option<String> {
+	let filepath = command
+		.strip_prefix(MESSAGE_COMMAND_PREFIX)?
+		.strip_prefix('\'')?
+		.strip_suffix('\'')?;
+	let hash = Path::new(filepath).file_name()?.to_str()?.strip_prefix(MESSAGE_FILE_PREFIX)?;
+	if hash.is_empty() {
+		None
+	}
+	else {
+		Some(String::from(hash))
+	}
+}
+
+/// Checks if an exec command amends a commit with its saved message
+pub(crate) fn is_message_command(command: &str) -> bool {
+	get_message_command_hash(command).is_some()
+}
+
+/// A commit with one of these actions keeps its message, so a new message can be written for it
+pub(crate) fn is_message_action(action: Action) -> bool {
+	action == Action::Pick || action == Action::Reword || action == Action::Edit
+}
+
+/// Removes the exec lines that amend a commit with its saved message, returning the remaining lines and the hashes of
+/// the commits with a saved message
+pub(crate) fn remove_message_lines(lines: Vec<Line>) -> (Vec<Line>, HashSet<String>) {
+	let mut hashes = HashSet::new();
+	let lines = lines
+		.into_iter()
+		.filter(|line| {
+			match get_message_command_hash(line.get_command()).filter(|_| *line.get_action() == Action::Exec) {
+				Some(hash) => {
+					hashes.insert(hash);
+					false
+				},
+				None => true,
+			}
+		})
+		.collect();
+	(lines, hashes)
+}
+
+/// Adds an exec line that amends the commit with its saved message after each commit with a saved message, and after
+/// the fixup and squash lines that are folded into it, so the exec follows the commit wherever it is moved
+pub(crate) fn add_message_lines(todo_filepath: &Path, lines: &[Line], hashes: &HashSet<String>) -> Vec<Line> {
+	let chains = get_chains(lines);
+	let mut message_lines = vec![];
+	for (index, line) in lines.iter().enumerate() {
+		message_lines.push(line.clone());
+		let head = match get_chain(&chains, index) {
+			Some(chain) if chain.get_end() == index => &lines[chain.get_start()],
+			Some(_) => continue,
+			None => line,
+		};
+		if is_message_action(*head.get_action()) && hashes.contains(head.get_hash()) {
+			let filepath = get_message_filepath(todo_filepath, head.get_hash());
+			message_lines.push(Line::new_exec(get_message_command(&filepath).as_str()));
+		}
+	}
+	message_lines
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{
+		add_message_lines,
+		get_message_command,
+		get_message_command_hash,
+		get_message_filepath,
+		remove_message_lines,
+	};
+	use crate::list::line::Line;
+	use std::collections::HashSet;
+	use std::path::Path;
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	fn get_text(lines: &[Line]) -> Vec<String> {
+		lines.iter().map(Line::to_text).collect()
+	}
+
+	#[test]
+	fn message_command() {
+		let filepath = get_message_filepath(Path::new("/repo/.git/rebase-merge/git-rebase-todo"), "abc123");
+		assert_eq!(
+			get_message_command(&filepath),
+			"git commit --amend --allow-empty -F '/repo/.git/rebase-merge/interactive-rebase-tool-message-abc123'"
+		);
+	}
+
+	#[test]
+	fn message_command_hash() {
+		assert_eq!(
+			get_message_command_hash(
+				"git commit --amend --allow-empty -F '/repo/.git/rebase-merge/interactive-rebase-tool-message-abc123'"
+			),
+			Some(String::from("abc123"))
+		);
+		assert_eq!(
+			get_message_command_hash("git commit --amend --allow-empty -F '/repo/.git/rebase-merge/message'"),
+			None
+		);
+		assert_eq!(get_message_command_hash("make test"), None);
+	}
+
+	#[test]
+	fn add_message_lines_after_group() {
+		let lines = create_lines(&["pick aaa first", "fixup bbb second", "pick ccc third", "drop ddd fourth"]);
+		let hashes: HashSet<String> = ["aaa", "ccc", "ddd"].iter().map(|h| String::from(*h)).collect();
+		let todo_filepath = Path::new("/repo/.git/rebase-merge/git-rebase-todo");
+		let command = |hash: &str| {
+			format!(
+				"exec git commit --amend --allow-empty -F '/repo/.git/rebase-merge/interactive-rebase-tool-message-{}'",
+				hash
+			)
+		};
+		assert_eq!(get_text(&add_message_lines(todo_filepath, &lines, &hashes)), vec![
+			String::from("pick aaa first"),
+			String::from("fixup bbb second"),
+			command("aaa"),
+			String::from("pick ccc third"),
+			command("ccc"),
+			String::from("drop ddd fourth"),
+		]);
+	}
+
+	#[test]
+	fn remove_message_lines_round_trip() {
+		let lines = create_lines(&["pick aaa first", "fixup bbb second", "exec make", "pick ccc third"]);
+		let hashes: HashSet<String> = ["aaa"].iter().map(|h| String::from(*h)).collect();
+		let todo_filepath = Path::new("/repo/.git/rebase-merge/git-rebase-todo");
+		let (removed, removed_hashes) = remove_message_lines(add_message_lines(todo_filepath, &lines, &hashes));
+		assert_eq!(get_text(&removed), get_text(&lines));
+		assert_eq!(removed_hashes, hashes);
+	}
+}
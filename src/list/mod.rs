@@ -1,15 +1,28 @@
 pub(crate) mod action;
+pub(crate) mod autosquash;
+pub(crate) mod chains;
+mod columns;
+mod graph;
 pub(crate) mod line;
+pub(crate) mod message;
+mod status;
 mod utils;
 
+use crate::config::list_format::{ListColumn, ListFormatItem};
 use crate::config::Config;
-use crate::constants::MINIMUM_FULL_WINDOW_WIDTH;
+use crate::constants::{EXPORT_JSON_FILE_NAME, MINIMUM_FULL_WINDOW_WIDTH};
 use crate::display::display_color::DisplayColor;
+use crate::edit_message::utils::can_edit_message;
 use crate::git_interactive::GitInteractive;
 use crate::input::input_handler::{InputHandler, InputMode};
 use crate::input::Input;
+use crate::json::export_todo_to_file;
 use crate::list::action::Action;
+use crate::list::chains::{get_badge, get_chain, get_chains, get_gutter, get_visible_lines, is_collapsed, Chain};
+use crate::list::columns::Columns;
+use crate::list::graph::Graph;
 use crate::list::line::Line;
+use crate::list::status::Status;
 use crate::list::utils::{
 	get_action_color,
 	get_normal_footer_compact,
@@ -27,7 +40,7 @@ use crate::view::scroll_position::ScrollPosition;
 use crate::view::view_line::ViewLine;
 use crate::view::View;
 use std::cmp;
-use unicode_segmentation::UnicodeSegmentation;
+use std::collections::HashSet;
 
 #[derive(Debug, PartialEq)]
 enum ListState {
@@ -36,7 +49,12 @@ enum ListState {
 }
 
 pub(crate) struct List<'l> {
+	chains: Vec<Chain>,
+	collapsed: HashSet<String>,
+	columns: Columns,
 	config: &'l Config,
+	graph: Graph,
+	message: Option<String>,
 	normal_footer_compact: String,
 	normal_footer_full: String,
 	scroll_position: ScrollPosition,
@@ -45,45 +63,36 @@ pub(crate) struct List<'l> {
 	visual_footer_full: String,
 }
 
-fn get_maximum_line_length(is_full_width: bool, lines: &[Line]) -> usize {
-	let mut length = 0;
-	if is_full_width {
-		for line in lines {
-			let line_length = match *line.get_action() {
-				Action::Exec => UnicodeSegmentation::graphemes(line.get_command().as_str(), true).count(),
-				Action::Break => 0,
-				_ => 9 + UnicodeSegmentation::graphemes(line.get_comment().as_str(), true).count(),
-			} + 10;
-
-			if line_length > length {
-				length = line_length;
-			}
-		}
-	}
-	else {
-		for line in lines {
-			let line_length = match *line.get_action() {
-				Action::Exec => UnicodeSegmentation::graphemes(line.get_command().as_str(), true).count(),
-				Action::Break => 0,
-				_ => 4 + UnicodeSegmentation::graphemes(line.get_comment().as_str(), true).count(),
-			} + 3;
-
-			if line_length > length {
-				length = line_length;
-			}
-		}
-	}
-	length
-}
-
 impl<'l> ProcessModule for List<'l> {
 	fn process(&mut self, git_interactive: &mut GitInteractive, view: &View) -> ProcessResult {
 		let (_, view_height) = view.get_view_size();
 		let lines = git_interactive.get_lines();
+		let done_lines = git_interactive.get_done_lines();
 		let selected_index = *git_interactive.get_selected_line_index() - 1;
+
+		// chains that no longer exist are forgotten, so a new chain with the same head starts expanded
+		let chains = get_chains(lines);
+		self.collapsed
+			.retain(|hash| chains.iter().any(|chain| lines[chain.get_start()].get_hash() == hash));
+		self.chains = chains;
+		let visible_lines = get_visible_lines(lines, &self.chains, &self.collapsed);
+		let selected_position = visible_lines.iter().position(|i| *i == selected_index).unwrap_or(0);
 		// TODO move this to handle_input
-		self.scroll_position
-			.ensure_cursor_visible(selected_index, view_height, lines.len());
+		self.scroll_position.ensure_cursor_visible(
+			done_lines.len() + selected_position,
+			view_height,
+			done_lines.len() + visible_lines.len(),
+		);
+
+		// the graph is computed from the current order, so it follows any moved lines
+		let graph_lines: Vec<&Line> = done_lines.iter().chain(lines.iter()).collect();
+		self.graph.update(graph_lines.as_slice());
+		let hashes: Vec<&str> = graph_lines
+			.iter()
+			.filter(|line| line.get_action().has_commit())
+			.map(|line| line.get_hash().as_str())
+			.collect();
+		self.columns.update(hashes.as_slice());
 
 		ProcessResult::new()
 	}
@@ -112,16 +121,32 @@ impl<'l> ProcessModule for List<'l> {
 
 		let selected_index = *git_interactive.get_selected_line_index() - 1;
 
-		for (index, line) in git_interactive.get_lines().iter().enumerate() {
+		let done_length = git_interactive.get_done_lines().len();
+
+		let lines = git_interactive.get_lines();
+
+		for (index, line) in git_interactive.get_done_lines().iter().enumerate() {
+			let (segments, pinned) = self.get_done_line_segments(line, index, view_width);
+			let (segments, pinned) = self.add_chain_segments(lines, None, segments, pinned);
+			view_lines.push(ViewLine::new_with_pinned_segments(segments, pinned));
+		}
+
+		for index in get_visible_lines(lines, &self.chains, &self.collapsed) {
+			let line = &lines[index];
 			let selected_line = is_visual_mode
 				&& ((visual_index <= selected_index && index >= visual_index && index <= selected_index)
 					|| (visual_index > selected_index && index >= selected_index && index <= visual_index));
+			let (segments, pinned) = self.get_todo_line_segments(
+				line,
+				done_length + index,
+				selected_index == index,
+				selected_line,
+				view_width,
+			);
+			let (segments, pinned) = self.add_chain_segments(lines, Some(index), segments, pinned);
 			view_lines.push(
-				ViewLine::new_with_pinned_segments(
-					self.get_todo_line_segments(line, selected_index == index, selected_line, view_width),
-					if *line.get_action() == Action::Exec { 2 } else { 3 },
-				)
-				.set_selected(selected_index == index || selected_line),
+				ViewLine::new_with_pinned_segments(segments, pinned)
+					.set_selected(selected_index == index || selected_line),
 			);
 		}
 
@@ -131,9 +156,21 @@ impl<'l> ProcessModule for List<'l> {
 			&view_lines,
 			self.scroll_position.get_top_position(),
 			self.scroll_position.get_left_position(),
-			view_height - 2,
+			self.get_list_height(view_height),
 		);
 
+		if self.config.status_bar {
+			let status = Status::new(lines, git_interactive.get_original_lines(), selected_index + 1);
+			view.draw_view_lines(&[status.get_view_line(view_width)], 0, 0, 1);
+		}
+
+		// a message replaces the footer until the next input
+		if let Some(ref message) = self.message {
+			view.set_color(DisplayColor::IndicatorColor, false);
+			view.draw_str(message.as_str());
+			return;
+		}
+
 		view.set_color(DisplayColor::Normal, false);
 		view.set_style(true, false, false);
 		if is_visual_mode {
@@ -163,16 +200,123 @@ impl<'l> ProcessModule for List<'l> {
 impl<'l> List<'l> {
 	pub(crate) fn new(config: &'l Config) -> Self {
 		Self {
+			chains: vec![],
+			collapsed: HashSet::new(),
+			columns: Columns::new(config.list_format.as_slice()),
 			config,
+			graph: Graph::new(config.graph),
+			message: None,
 			normal_footer_compact: get_normal_footer_compact(config),
 			normal_footer_full: get_normal_footer_full(config),
-			scroll_position: ScrollPosition::new(2),
+			scroll_position: ScrollPosition::new(if config.status_bar { 3 } else { 2 }),
 			state: ListState::Normal,
 			visual_footer_compact: get_visual_footer_compact(config),
 			visual_footer_full: get_visual_footer_full(config),
 		}
 	}
 
+	/// Gets the number of rows for the lines, leaving rows for the title, the footer and the optional status bar
+	fn get_list_height(&self, view_height: usize) -> usize {
+		view_height - if self.config.status_bar { 3 } else { 2 }
+	}
+
+	/// Finds the index of the line drawn at a window position, the first row of the list viewport is the title and the
+	/// last row the footer, a position outside the viewport has no line
+	fn get_line_index_at_position(
+		&self,
+		column: usize,
+		row: usize,
+		view: &View,
+		git_interactive: &GitInteractive,
+	) -> Option<usize>
+	{
+		let (_, row) = view.get_viewport_position(column, row)?;
+		let (_, view_height) = view.get_view_size();
+		if row == 0 || row > self.get_list_height(view_height) {
+			return None;
+		}
+		let index = self.scroll_position.get_top_position() + row - 1;
+		// the done lines are shown above the todo lines, but cannot be selected
+		let done_length = git_interactive.get_done_lines().len();
+		if index < done_length {
+			return None;
+		}
+		let lines = git_interactive.get_lines();
+		get_visible_lines(lines, &get_chains(lines), &self.collapsed)
+			.get(index - done_length)
+			.cloned()
+	}
+
+	/// Collapses or expands the chain of the selected line, collapsing selects the head of the chain
+	fn toggle_collapse(&mut self, git_interactive: &mut GitInteractive) {
+		let lines = git_interactive.get_lines();
+		if let Some(chain) = get_chain(&get_chains(lines), *git_interactive.get_selected_line_index() - 1) {
+			let hash = lines[chain.get_start()].get_hash().clone();
+			if !self.collapsed.remove(&hash) {
+				self.collapsed.insert(hash);
+				git_interactive.set_selected_line_index(chain.get_start() + 1);
+			}
+		}
+	}
+
+	/// Moves the selection off a line hidden in a collapsed chain, past the chain when the selection moved down
+	fn select_visible_line(&self, git_interactive: &mut GitInteractive, previous_index: usize) {
+		let lines = git_interactive.get_lines();
+		let index = *git_interactive.get_selected_line_index() - 1;
+		let next_index = match get_chain(&get_chains(lines), index) {
+			Some(chain) if index != chain.get_start() && is_collapsed(lines, chain, &self.collapsed) => {
+				if index > previous_index && chain.get_end() + 1 < lines.len() {
+					chain.get_end() + 1
+				}
+				else {
+					chain.get_start()
+				}
+			},
+			_ => return,
+		};
+		git_interactive.set_selected_line_index(next_index + 1);
+	}
+
+	/// Moves the selected line down, a chain head is moved with its chain and a collapsed chain is moved past whole
+	fn swap_selected_down(&self, git_interactive: &mut GitInteractive) {
+		let lines = git_interactive.get_lines();
+		let chains = get_chains(lines);
+		let index = *git_interactive.get_selected_line_index() - 1;
+		let end = match get_chain(&chains, index) {
+			Some(chain) if chain.get_start() == index => chain.get_end(),
+			_ => index,
+		};
+		if end + 1 >= lines.len() {
+			return;
+		}
+		let amount = match get_chain(&chains, end + 1) {
+			Some(next) if end != index || is_collapsed(lines, next, &self.collapsed) => next.get_end() - end,
+			_ => 1,
+		};
+		git_interactive.move_lines_down(index, end, amount);
+	}
+
+	/// Moves the selected line up, a chain head is moved with its chain and a collapsed chain is moved past whole
+	fn swap_selected_up(&self, git_interactive: &mut GitInteractive) {
+		let lines = git_interactive.get_lines();
+		let chains = get_chains(lines);
+		let index = *git_interactive.get_selected_line_index() - 1;
+		if index == 0 {
+			return;
+		}
+		let end = match get_chain(&chains, index) {
+			Some(chain) if chain.get_start() == index => chain.get_end(),
+			_ => index,
+		};
+		let amount = match get_chain(&chains, index - 1) {
+			Some(previous) if end != index || is_collapsed(lines, previous, &self.collapsed) => {
+				index - previous.get_start()
+			},
+			_ => 1,
+		};
+		git_interactive.move_lines_up(index, end, amount);
+	}
+
 	fn set_selected_line_action(&self, git_interactive: &mut GitInteractive, action: Action) {
 		git_interactive.set_selected_line_action(action);
 		if self.config.auto_select_next {
@@ -188,8 +332,10 @@ impl<'l> List<'l> {
 	) -> HandleInputResult
 	{
 		let input = input_handler.get_input(InputMode::List);
+		self.message = None;
 		let mut result = HandleInputResultBuilder::new(input);
 		let (view_width, view_height) = view.get_view_size();
+		let previous_index = *git_interactive.get_selected_line_index() - 1;
 		match input {
 			Input::Help => {
 				result = result.help(State::List(false));
@@ -223,19 +369,26 @@ impl<'l> List<'l> {
 				if *git_interactive.get_selected_line_action() == Action::Exec {
 					result = result.state(State::Edit);
 				}
+				else if can_edit_message(git_interactive) {
+					result = result.state(State::EditMessage);
+				}
+			},
+			Input::EditHash if git_interactive.get_selected_line_action().has_commit() => {
+				result = result.state(State::EditHash);
 			},
-			Input::SwapSelectedDown => git_interactive.swap_selected_down(),
-			Input::SwapSelectedUp => git_interactive.swap_selected_up(),
+			Input::SwapSelectedDown => self.swap_selected_down(git_interactive),
+			Input::SwapSelectedUp => self.swap_selected_up(git_interactive),
+			Input::ToggleCollapse => self.toggle_collapse(git_interactive),
 			Input::MoveCursorLeft => {
 				self.scroll_position.scroll_left(
 					view_width,
-					get_maximum_line_length(view_width >= MINIMUM_FULL_WINDOW_WIDTH, git_interactive.get_lines()),
+					self.get_maximum_line_length(view_width, git_interactive),
 				)
 			},
 			Input::MoveCursorRight => {
 				self.scroll_position.scroll_right(
 					view_width,
-					get_maximum_line_length(view_width >= MINIMUM_FULL_WINDOW_WIDTH, git_interactive.get_lines()),
+					self.get_maximum_line_length(view_width, git_interactive),
 				)
 			},
 			Input::MoveCursorDown => git_interactive.move_cursor_down(1),
@@ -243,13 +396,65 @@ impl<'l> List<'l> {
 			Input::MoveCursorPageDown => git_interactive.move_cursor_down(view_height / 2),
 			Input::MoveCursorPageUp => git_interactive.move_cursor_up(view_height / 2),
 			Input::ToggleVisualMode => {
+				// visual mode works on the individual lines, so every chain is expanded
+				self.collapsed.clear();
 				git_interactive.start_visual_mode();
 				self.state = ListState::Visual;
 				result = result.state(State::List(true));
 			},
 			Input::OpenInEditor => result = result.state(State::ExternalEditor),
+			Input::Autosquash => {
+				let unmatched = git_interactive.autosquash();
+				if !unmatched.is_empty() {
+					result = result.error(
+						format!(
+							"Could not find the target commit for:\n{}",
+							unmatched
+								.iter()
+								.map(|l| format!("{} {}", l.get_hash(), l.get_comment()))
+								.collect::<Vec<String>>()
+								.join("\n")
+						)
+						.as_str(),
+						State::List(false),
+					);
+				}
+			},
+			Input::ExportJson => {
+				let filepath = git_interactive.get_filepath().with_file_name(EXPORT_JSON_FILE_NAME);
+				match export_todo_to_file(git_interactive, filepath.to_string_lossy().as_ref()) {
+					Ok(_) => self.message = Some(format!("Exported the todo to {}", filepath.display())),
+					Err(e) => result = result.error(e.as_str(), State::List(false)),
+				}
+			},
+			Input::MouseClick(column, row) => {
+				if let Some(index) = self.get_line_index_at_position(column, row, view, git_interactive) {
+					git_interactive.set_selected_line_index(index + 1);
+				}
+			},
+			Input::MouseDoubleClick(column, row) => {
+				if let Some(index) = self.get_line_index_at_position(column, row, view, git_interactive) {
+					git_interactive.set_selected_line_index(index + 1);
+					if !git_interactive.get_selected_line_hash().is_empty() {
+						result = result.state(State::ShowCommit);
+					}
+				}
+			},
+			Input::MouseShiftClick(column, row) => {
+				if let Some(index) = self.get_line_index_at_position(column, row, view, git_interactive) {
+					self.collapsed.clear();
+					git_interactive.start_visual_mode();
+					git_interactive.set_selected_line_index(index + 1);
+					self.state = ListState::Visual;
+					result = result.state(State::List(true));
+				}
+			},
+			Input::CustomCommand(index) => result = result.state(State::CustomCommand(index)),
 			_ => {},
 		}
+		if !git_interactive.get_lines().is_empty() {
+			self.select_visible_line(git_interactive, previous_index);
+		}
 		result.build()
 	}
 
@@ -283,13 +488,13 @@ impl<'l> List<'l> {
 			Input::MoveCursorLeft => {
 				self.scroll_position.scroll_left(
 					view_width,
-					get_maximum_line_length(view_width >= MINIMUM_FULL_WINDOW_WIDTH, git_interactive.get_lines()),
+					self.get_maximum_line_length(view_width, git_interactive),
 				)
 			},
 			Input::MoveCursorRight => {
 				self.scroll_position.scroll_right(
 					view_width,
-					get_maximum_line_length(view_width >= MINIMUM_FULL_WINDOW_WIDTH, git_interactive.get_lines()),
+					self.get_maximum_line_length(view_width, git_interactive),
 				)
 			},
 			Input::MoveCursorDown => {
@@ -316,82 +521,202 @@ impl<'l> List<'l> {
 				self.state = ListState::Normal;
 				result = result.state(State::List(false));
 			},
+			Input::MouseClick(column, row) | Input::MouseDoubleClick(column, row) => {
+				if let Some(index) = self.get_line_index_at_position(column, row, view, git_interactive) {
+					git_interactive.set_selected_line_index(index + 1);
+					self.state = ListState::Normal;
+					result = result.state(State::List(false));
+				}
+			},
+			Input::MouseShiftClick(column, row) => {
+				if let Some(index) = self.get_line_index_at_position(column, row, view, git_interactive) {
+					git_interactive.set_selected_line_index(index + 1);
+				}
+			},
 			_ => {},
 		}
 		result.build()
 	}
 
+	/// Gets the segments of a line, and the number of segments that are pinned when scrolling horizontally
 	fn get_todo_line_segments(
 		&self,
 		line: &Line,
+		row: usize,
 		is_cursor_line: bool,
 		selected: bool,
 		view_width: usize,
-	) -> Vec<LineSegment>
+	) -> (Vec<LineSegment>, usize)
 	{
 		let mut segments: Vec<LineSegment> = vec![];
 
 		let action = line.get_action();
+		let is_full_width = view_width >= MINIMUM_FULL_WINDOW_WIDTH;
 
-		if view_width >= MINIMUM_FULL_WINDOW_WIDTH {
-			segments.push(LineSegment::new_with_color_and_style(
-				if is_cursor_line || selected { " > " } else { "   " },
-				DisplayColor::Normal,
-				!is_cursor_line && selected,
-				false,
-				false,
-			));
+		let selector = if is_full_width {
+			if is_cursor_line || selected { " > " } else { "   " }
+		}
+		else if is_cursor_line || selected {
+			">"
+		}
+		else {
+			" "
+		};
+		segments.push(LineSegment::new_with_color_and_style(
+			selector,
+			DisplayColor::Normal,
+			!is_cursor_line && selected,
+			false,
+			false,
+		));
 
+		let mut pinned = if *action == Action::Exec || *action == Action::Break {
 			segments.push(LineSegment::new_with_color(
-				format!("{:6} ", action.as_string()).as_str(),
-				get_action_color(*action),
-			));
-
-			segments.push(LineSegment::new(
-				if *action == Action::Exec {
-					line.get_command().clone()
-				}
-				else if *action == Action::Break {
-					String::from("")
+				if is_full_width {
+					format!("{:6} ", action.as_string())
 				}
 				else {
-					let max_index = cmp::min(line.get_hash().len(), 8);
-					format!("{:8} ", line.get_hash()[0..max_index].to_string())
+					format!("{:1} ", action.to_abbreviation())
 				}
 				.as_str(),
+				get_action_color(*action),
 			));
+			if *action == Action::Exec {
+				segments.push(LineSegment::new(line.get_command().as_str()));
+				2
+			}
+			else {
+				segments.push(LineSegment::new(if is_full_width { "" } else { "    " }));
+				3
+			}
 		}
 		else {
-			segments.push(LineSegment::new_with_color_and_style(
-				if is_cursor_line || selected { ">" } else { " " },
-				DisplayColor::Normal,
-				!is_cursor_line && selected,
-				false,
-				false,
-			));
+			let offset = selector.len() + self.graph.get_width();
+			let mut parts: Vec<(String, DisplayColor)> = vec![];
+			let mut subject_index = None;
+			for item in self.columns.get_visible_format(view_width, is_full_width, offset) {
+				match item {
+					ListFormatItem::Text(text) => {
+						// text is joined to the column before it, so it scrolls with the column
+						match parts.last_mut() {
+							Some(part) => part.0.push_str(text.as_str()),
+							None => parts.push((text.clone(), DisplayColor::Normal)),
+						}
+					},
+					ListFormatItem::Column(ListColumn::Action) => {
+						parts.push((
+							if is_full_width {
+								format!("{:6}", action.as_string())
+							}
+							else {
+								format!("{:1}", action.to_abbreviation())
+							},
+							get_action_color(*action),
+						));
+					},
+					ListFormatItem::Column(ListColumn::Hash) => {
+						let width = if is_full_width { 8 } else { 3 };
+						let max_index = cmp::min(line.get_hash().len(), width);
+						parts.push((
+							format!("{:width$}", &line.get_hash()[0..max_index], width = width),
+							DisplayColor::Normal,
+						));
+					},
+					ListFormatItem::Column(ListColumn::Subject) => {
+						subject_index = Some(parts.len());
+						parts.push((line.get_comment().clone(), DisplayColor::Normal));
+					},
+					ListFormatItem::Column(column) => {
+						parts.push((
+							self.columns.get_padded_value(*column, line.get_hash().as_str()),
+							DisplayColor::Normal,
+						));
+					},
+				}
+			}
+			let pinned = 1 + subject_index.unwrap_or(parts.len());
+			for (content, color) in parts {
+				segments.push(LineSegment::new_with_color(content.as_str(), color));
+			}
+			pinned
+		};
 
-			segments.push(LineSegment::new_with_color(
-				format!("{:1} ", line.get_action().to_abbreviation()).as_str(),
-				get_action_color(*action),
-			));
+		if self.graph.is_enabled() {
+			segments.insert(1, self.get_graph_segment(row));
+			pinned += 1;
+		}
+		(segments, pinned)
+	}
 
-			segments.push(LineSegment::new(
-				if *action == Action::Exec {
-					line.get_command().clone()
-				}
-				else if *action == Action::Break {
-					String::from("    ")
-				}
-				else {
-					let max_index = cmp::min(line.get_hash().len(), 3);
-					format!("{:3} ", line.get_hash()[0..max_index].to_string())
-				}
-				.as_str(),
+	/// Gets the length of the longest line, used to limit scrolling to the right
+	fn get_maximum_line_length(&self, view_width: usize, git_interactive: &GitInteractive) -> usize {
+		let lines = git_interactive.get_lines();
+		lines
+			.iter()
+			.enumerate()
+			.map(|(index, line)| {
+				let (segments, pinned) = self.get_todo_line_segments(line, 0, false, false, view_width);
+				self.add_chain_segments(lines, Some(index), segments, pinned)
+					.0
+					.iter()
+					.map(LineSegment::get_length)
+					.sum()
+			})
+			.max()
+			.unwrap_or(0)
+	}
+
+	/// Adds the chain gutter after the selector and graph, and the badge of a collapsed chain after the line
+	fn add_chain_segments(
+		&self,
+		lines: &[Line],
+		index: Option<usize>,
+		mut segments: Vec<LineSegment>,
+		pinned: usize,
+	) -> (Vec<LineSegment>, usize)
+	{
+		if self.chains.is_empty() {
+			return (segments, pinned);
+		}
+		let position = if self.graph.is_enabled() { 2 } else { 1 };
+		let gutter = index.map(|i| get_gutter(&self.chains, i)).unwrap_or("  ");
+		segments.insert(
+			position,
+			LineSegment::new_with_color(gutter, get_action_color(Action::Fixup)),
+		);
+		let collapsed_chain = index
+			.and_then(|i| get_chain(&self.chains, i).filter(|chain| chain.get_start() == i))
+			.filter(|chain| is_collapsed(lines, *chain, &self.collapsed));
+		if let Some(chain) = collapsed_chain {
+			segments.push(LineSegment::new_with_color(
+				format!(" {}", get_badge(lines, chain)).as_str(),
+				get_action_color(Action::Fixup),
 			));
 		}
-		if *action != Action::Exec && *action != Action::Break {
-			segments.push(LineSegment::new(line.get_comment().as_str()));
+		(segments, pinned + 1)
+	}
+
+	/// Gets the graph column of a row, drawn in the drop color when the commit is listed before one of its parents
+	fn get_graph_segment(&self, row: usize) -> LineSegment {
+		match self.graph.get_row(row) {
+			Some(graph_row) => {
+				LineSegment::new_with_color(
+					graph_row.get_text(),
+					if graph_row.is_out_of_order() {
+						DisplayColor::ActionDrop
+					}
+					else {
+						DisplayColor::Normal
+					},
+				)
+			},
+			None => LineSegment::new(" ".repeat(self.graph.get_width()).as_str()),
 		}
-		segments
+	}
+
+	/// Gets the segments of a line already applied by the rebase, with the todo line layout but dimmed
+	fn get_done_line_segments(&self, line: &Line, row: usize, view_width: usize) -> (Vec<LineSegment>, usize) {
+		let (segments, pinned) = self.get_todo_line_segments(line, row, false, false, view_width);
+		(segments.into_iter().map(LineSegment::set_greyed).collect(), pinned)
 	}
 }
@@ -0,0 +1,168 @@
+use crate::display::display_color::DisplayColor;
+use crate::list::action::Action;
+use crate::list::line::Line;
+use crate::list::utils::get_action_color;
+use crate::view::line_segment::LineSegment;
+use crate::view::view_line::ViewLine;
+
+const TALLY_ACTIONS: [Action; 8] = [
+	Action::Pick,
+	Action::Reword,
+	Action::Edit,
+	Action::Squash,
+	Action::Fixup,
+	Action::Drop,
+	Action::Exec,
+	Action::Break,
+];
+
+/// A summary of the todo, shown in the status bar above the footer
+pub(super) struct Status {
+	commits: usize,
+	counts: Vec<(Action, usize)>,
+	modified: bool,
+	position: usize,
+	total: usize,
+}
+
+impl Status {
+	pub(super) fn new(lines: &[Line], original_lines: &[Line], selected_line_index: usize) -> Self {
+		let counts: Vec<(Action, usize)> = TALLY_ACTIONS
+			.iter()
+			.map(|action| (*action, lines.iter().filter(|l| l.get_action() == action).count()))
+			.filter(|(_, count)| *count > 0)
+			.collect();
+		// squashed and fixed up commits are folded into the commit before them, so only these remain as commits
+		let commits = counts
+			.iter()
+			.filter(|(action, _)| *action == Action::Pick || *action == Action::Reword || *action == Action::Edit)
+			.map(|(_, count)| *count)
+			.sum();
+		Self {
+			commits,
+			counts,
+			modified: lines != original_lines,
+			position: if lines.is_empty() { 0 } else { selected_line_index },
+			total: lines.len(),
+		}
+	}
+
+	fn get_full_parts(&self) -> Vec<(String, DisplayColor)> {
+		let mut parts = vec![];
+		for (action, count) in &self.counts {
+			parts.push((
+				String::from(if parts.is_empty() { " " } else { ", " }),
+				DisplayColor::Normal,
+			));
+			parts.push((format!("{} {}", action.as_string(), count), get_action_color(*action)));
+		}
+		parts.push((
+			format!(
+				"{}{} {} after rebase | {}/{} | {}",
+				if parts.is_empty() { " " } else { " | " },
+				self.commits,
+				if self.commits == 1 { "commit" } else { "commits" },
+				self.position,
+				self.total,
+				if self.modified { "modified" } else { "unmodified" }
+			),
+			DisplayColor::Normal,
+		));
+		parts
+	}
+
+	fn get_compact_parts(&self) -> Vec<(String, DisplayColor)> {
+		let mut parts = vec![];
+		for (action, count) in &self.counts {
+			if !parts.is_empty() {
+				parts.push((String::from(" "), DisplayColor::Normal));
+			}
+			parts.push((format!("{}{}", action.to_abbreviation(), count), get_action_color(*action)));
+		}
+		parts.push((
+			format!(
+				"{}={}|{}",
+				if parts.is_empty() { "" } else { "|" },
+				self.commits,
+				self.get_position()
+			),
+			DisplayColor::Normal,
+		));
+		parts
+	}
+
+	fn get_position(&self) -> String {
+		format!("{}/{}{}", self.position, self.total, if self.modified { "*" } else { "" })
+	}
+
+	/// Gets the parts that fit the view, using the compact layout and then only the position when it is too narrow
+	fn get_parts(&self, view_width: usize) -> Vec<(String, DisplayColor)> {
+		let full = self.get_full_parts();
+		if get_length(&full) <= view_width {
+			return full;
+		}
+		let compact = self.get_compact_parts();
+		if get_length(&compact) <= view_width {
+			return compact;
+		}
+		vec![(self.get_position(), DisplayColor::Normal)]
+	}
+
+	pub(super) fn get_view_line(&self, view_width: usize) -> ViewLine {
+		ViewLine::new(
+			self.get_parts(view_width)
+				.iter()
+				.map(|(text, color)| LineSegment::new_with_color(text.as_str(), *color))
+				.collect(),
+		)
+	}
+}
+
+fn get_length(parts: &[(String, DisplayColor)]) -> usize {
+	parts.iter().map(|(text, _)| text.chars().count()).sum()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Status;
+	use crate::list::line::Line;
+
+	fn lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	fn get_text(status: &Status, view_width: usize) -> String {
+		status
+			.get_parts(view_width)
+			.into_iter()
+			.map(|(text, _)| text)
+			.collect()
+	}
+
+	#[test]
+	fn status_unmodified() {
+		let original = lines(&["pick aaa first", "pick bbb second"]);
+		let status = Status::new(&original, &original, 1);
+		assert_eq!(get_text(&status, 80), " pick 2 | 2 commits after rebase | 1/2 | unmodified");
+	}
+
+	#[test]
+	fn status_modified() {
+		let original = lines(&["pick aaa first", "pick bbb second", "pick ccc third", "pick ddd fourth"]);
+		let edited = lines(&["pick aaa first", "fixup bbb second", "drop ccc third", "pick ddd fourth", "exec make"]);
+		let status = Status::new(&edited, &original, 3);
+		assert_eq!(
+			get_text(&status, 80),
+			" pick 2, fixup 1, drop 1, exec 1 | 2 commits after rebase | 3/5 | modified"
+		);
+		assert_eq!(get_text(&status, 40), "p2 f1 d1 x1|=2|3/5*");
+		assert_eq!(get_text(&status, 10), "3/5*");
+	}
+
+	#[test]
+	fn status_empty() {
+		let original = lines(&["pick aaa first"]);
+		let status = Status::new(&[], &original, 1);
+		assert_eq!(get_text(&status, 80), " 0 commits after rebase | 0/0 | modified");
+	}
+}
@@ -22,29 +22,50 @@ mod commit;
 mod config;
 mod confirm_abort;
 mod confirm_rebase;
+mod confirm_restore;
 mod constants;
+mod custom_command;
 mod display;
 mod edit;
+mod edit_message;
 mod error;
 mod exiting;
 mod external_editor;
 mod git_interactive;
 mod help;
 mod input;
+mod json;
+mod launcher;
 mod list;
 mod process;
+mod rebase_progress;
+mod script;
+mod session;
+mod setup;
+mod shell;
 mod show_commit;
+#[cfg(test)]
+mod temp_path;
 mod view;
 mod window_size_error;
 
 use crate::config::Config;
 use crate::display::curses::Curses;
 use crate::display::Display;
+use crate::edit::history::ExecHistory;
 use crate::git_interactive::GitInteractive;
 use crate::input::input_handler::InputHandler;
+use crate::input::recording::{load_recording, Recorder};
+use crate::json::{export_todo_to_file, import_todo_from_file};
+use crate::launcher::{write_prepared_todo, Rebase};
 use crate::process::exit_status::ExitStatus;
 use crate::process::Process;
+use crate::rebase_progress::RebaseProgress;
+use crate::script::run_script;
+use crate::setup::doctor::doctor;
+use crate::setup::{install, uninstall};
 use crate::view::View;
+use clap::ArgMatches;
 
 struct Exit {
 	message: String,
@@ -64,8 +85,112 @@ fn main() {
 fn try_main() -> Result<ExitStatus, Exit> {
 	let matches = cli::build_cli().get_matches();
 
+	match matches.subcommand() {
+		("install", Some(sub_matches)) | ("uninstall", Some(sub_matches)) => {
+			let local = sub_matches.is_present("local");
+			let result = if matches.subcommand_name() == Some("install") {
+				install(local)
+			}
+			else {
+				uninstall(local)
+			};
+			return match result {
+				Ok(report) => {
+					println!("{}", report);
+					Ok(ExitStatus::Good)
+				},
+				Err(message) => {
+					Err(Exit {
+						message,
+						status: ExitStatus::ConfigError,
+					})
+				},
+			};
+		},
+		("doctor", _) => {
+			let (report, has_problems) = doctor();
+			println!("{}", report);
+			return Ok(if has_problems { ExitStatus::ConfigError } else { ExitStatus::Good });
+		},
+		("rebase", Some(sub_matches)) => return rebase(sub_matches),
+		_ => {},
+	}
+
 	let filepath = matches.value_of("rebase-todo-filepath").unwrap();
 
+	// when started by the rebase subcommand, git's todo is replaced with the todo that was already edited
+	if let Some(prepared_filepath) = matches.value_of("prepared-todo") {
+		if let Err(message) = write_prepared_todo(prepared_filepath, filepath) {
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileWriteError,
+			});
+		}
+		return Ok(ExitStatus::Good);
+	}
+
+	edit_todo(&matches, filepath)
+}
+
+/// Prepares a todo from a range of commits, edits it and then starts the rebase with the edited todo
+fn rebase(matches: &ArgMatches<'_>) -> Result<ExitStatus, Exit> {
+	let rebase = match Rebase::prepare(matches.value_of("upstream"), matches.value_of("range")) {
+		Ok(Some(rebase)) => rebase,
+		Ok(None) => {
+			return Err(Exit {
+				message: String::from("There are no commits to rebase"),
+				status: ExitStatus::Good,
+			});
+		},
+		Err(message) => {
+			return Err(Exit {
+				message,
+				status: ExitStatus::RebaseError,
+			});
+		},
+	};
+
+	let result = edit_todo(matches, rebase.get_todo_filepath().to_string_lossy().as_ref());
+	match (result, rebase.has_todo_lines()) {
+		(Ok(ExitStatus::Good), Ok(true)) => {},
+		(Ok(ExitStatus::Good), Ok(false)) => {
+			rebase.remove();
+			return Err(Exit {
+				message: String::from("The rebase was aborted"),
+				status: ExitStatus::Good,
+			});
+		},
+		(Ok(ExitStatus::Good), Err(message)) => {
+			rebase.remove();
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileReadError,
+			});
+		},
+		(result, _) => {
+			rebase.remove();
+			return result;
+		},
+	}
+
+	match rebase.start() {
+		Ok(true) => Ok(ExitStatus::Good),
+		Ok(false) => {
+			Err(Exit {
+				message: String::from("git rebase did not complete"),
+				status: ExitStatus::RebaseError,
+			})
+		},
+		Err(message) => {
+			Err(Exit {
+				message,
+				status: ExitStatus::RebaseError,
+			})
+		},
+	}
+}
+
+fn edit_todo(matches: &ArgMatches<'_>, filepath: &str) -> Result<ExitStatus, Exit> {
 	let config = match Config::new() {
 		Ok(c) => c,
 		Err(message) => {
@@ -76,7 +201,7 @@ fn try_main() -> Result<ExitStatus, Exit> {
 		},
 	};
 
-	let git_interactive = match GitInteractive::new_from_filepath(filepath, config.comment_char.as_str()) {
+	let mut git_interactive = match GitInteractive::new_from_filepath(filepath, config.comment_char.as_str()) {
 		Ok(gi) => gi,
 		Err(message) => {
 			return Err(Exit {
@@ -100,14 +225,97 @@ fn try_main() -> Result<ExitStatus, Exit> {
 		});
 	}
 
+	if let Some(filepath) = matches.value_of("import-json") {
+		match import_todo_from_file(filepath) {
+			Ok(lines) => git_interactive.set_lines(lines),
+			Err(message) => {
+				return Err(Exit {
+					message,
+					status: ExitStatus::FileReadError,
+				});
+			},
+		}
+	}
+
+	if let Some(script) = matches.value_of("script") {
+		if let Err(message) = run_script(&mut git_interactive, script) {
+			return Err(Exit {
+				message,
+				status: ExitStatus::ScriptError,
+			});
+		}
+	}
+
+	if let Some(filepath) = matches.value_of("export-json") {
+		if let Err(message) = export_todo_to_file(&git_interactive, filepath) {
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileWriteError,
+			});
+		}
+	}
+
+	// the editor is not opened when the todo file is edited or exported from the command line
+	let edited = matches.is_present("import-json") || matches.is_present("script");
+	if edited {
+		if let Err(message) = git_interactive.write_file() {
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileWriteError,
+			});
+		}
+	}
+	if edited || matches.is_present("export-json") {
+		return Ok(ExitStatus::Good);
+	}
+
+	let replay = match matches.value_of("replay").map(load_recording) {
+		Some(Ok(events)) => Some(events),
+		Some(Err(message)) => {
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileReadError,
+			});
+		},
+		None => None,
+	};
+
+	let recorder = match matches.value_of("record").map(Recorder::new) {
+		Some(Ok(recorder)) => Some(recorder),
+		Some(Err(message)) => {
+			return Err(Exit {
+				message,
+				status: ExitStatus::FileWriteError,
+			});
+		},
+		None => None,
+	};
+
 	let mut curses = Curses::new();
 
 	let display = Display::new(&mut curses, &config);
 
-	let input_handler = InputHandler::new(&display, &config);
+	let mut input_handler = InputHandler::new(&display, &config);
+	if let Some(events) = replay {
+		input_handler.replay(events);
+	}
+	if let Some(recorder) = recorder {
+		input_handler.record(recorder);
+	}
 	let view = View::new(&display, &config);
 
-	let mut process = Process::new(git_interactive, &view, &display, &input_handler, &config);
+	if let Some(rebase_progress) = RebaseProgress::load(filepath) {
+		git_interactive.set_rebase_progress(rebase_progress);
+	}
+
+	let mut process = Process::new(
+		git_interactive,
+		ExecHistory::load(),
+		&view,
+		&display,
+		&input_handler,
+		&config,
+	);
 
 	let result = process.run();
 	display.end();
@@ -1,9 +1,11 @@
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub(crate) enum ExitStatus {
 	ConfigError,
 	FileReadError,
 	FileWriteError,
 	Good,
+	RebaseError,
+	ScriptError,
 	StateError,
 }
 
@@ -15,6 +17,8 @@ impl ExitStatus {
 			ExitStatus::FileWriteError => 3,
 			ExitStatus::Good => 0,
 			ExitStatus::StateError => 4,
+			ExitStatus::ScriptError => 5,
+			ExitStatus::RebaseError => 6,
 		}
 	}
 }
@@ -33,6 +33,14 @@ impl HandleInputResultBuilder {
 		}
 	}
 
+	pub(crate) fn error(mut self, message: &str, return_state: State) -> Self {
+		self.handle_input.state = Some(State::Error {
+			return_state: Box::new(return_state),
+			message: String::from(message),
+		});
+		self
+	}
+
 	pub(crate) fn exit_status(mut self, status: ExitStatus) -> Self {
 		self.handle_input.exit_status = Some(status);
 		self
@@ -4,31 +4,54 @@ pub(crate) mod process_module;
 pub(crate) mod process_result;
 pub(crate) mod state;
 
+use crate::config::split_layout::SplitLayout;
 use crate::config::Config;
 use crate::confirm_abort::ConfirmAbort;
 use crate::confirm_rebase::ConfirmRebase;
+use crate::confirm_restore::ConfirmRestore;
+use crate::constants::{MINIMUM_SPLIT_WINDOW_HEIGHT, MINIMUM_SPLIT_WINDOW_WIDTH};
+use crate::custom_command::CustomCommand;
 use crate::display::Display;
+use crate::edit::history::ExecHistory;
 use crate::edit::Edit;
+use crate::edit_message::EditMessage;
 use crate::error::Error;
 use crate::exiting::Exiting;
 use crate::external_editor::ExternalEditor;
 use crate::git_interactive::GitInteractive;
 use crate::help::Help;
-use crate::input::input_handler::InputHandler;
+use crate::input::input_handler::{InputHandler, InputMode};
 use crate::input::Input;
 use crate::list::List;
 use crate::process::exit_status::ExitStatus;
+use crate::process::handle_input_result::{HandleInputResult, HandleInputResultBuilder};
 use crate::process::process_module::ProcessModule;
+use crate::process::process_result::ProcessResult;
 use crate::process::state::State;
 use crate::show_commit::ShowCommit;
+use crate::view::viewport::Viewport;
 use crate::view::View;
 use crate::window_size_error::WindowSizeError;
 use std::cell::RefCell;
 
+/// Whether the input is a mouse click inside the viewport
+fn is_mouse_input_in(input: Input, viewport: &Viewport) -> bool {
+	match input {
+		Input::MouseClick(column, row) | Input::MouseDoubleClick(column, row) | Input::MouseShiftClick(column, row) => {
+			viewport.contains(column, row)
+		},
+		_ => false,
+	}
+}
+
 pub(crate) struct Process<'r> {
 	confirm_abort: ConfirmAbort,
 	confirm_rebase: ConfirmRebase,
+	confirm_restore: ConfirmRestore,
+	custom_command: CustomCommand<'r>,
+	details_focused: bool,
 	edit: Edit,
+	edit_message: EditMessage,
 	error: Error,
 	exit_status: Option<ExitStatus>,
 	exiting: Exiting,
@@ -38,6 +61,7 @@ pub(crate) struct Process<'r> {
 	input_handler: &'r InputHandler<'r>,
 	list: List<'r>,
 	show_commit: ShowCommit,
+	split_layout: SplitLayout,
 	state: RefCell<State>,
 	view: &'r View<'r>,
 	window_size_error: WindowSizeError,
@@ -46,6 +70,7 @@ pub(crate) struct Process<'r> {
 impl<'r> Process<'r> {
 	pub(crate) fn new(
 		git_interactive: GitInteractive,
+		exec_history: ExecHistory,
 		view: &'r View<'r>,
 		display: &'r Display<'r>,
 		input_handler: &'r InputHandler<'r>,
@@ -55,7 +80,11 @@ impl<'r> Process<'r> {
 		Self {
 			confirm_abort: ConfirmAbort::new(),
 			confirm_rebase: ConfirmRebase::new(),
-			edit: Edit::new(),
+			confirm_restore: ConfirmRestore::new(),
+			custom_command: CustomCommand::new(display, config),
+			details_focused: false,
+			edit: Edit::new(exec_history),
+			edit_message: EditMessage::new(config),
 			error: Error::new(),
 			exit_status: None,
 			exiting: Exiting::new(),
@@ -64,7 +93,8 @@ impl<'r> Process<'r> {
 			help: Help::new(config),
 			input_handler,
 			list: List::new(config),
-			show_commit: ShowCommit::new(),
+			show_commit: ShowCommit::new(config),
+			split_layout: config.split_layout,
 			state: RefCell::new(State::List(false)),
 			view,
 			window_size_error: WindowSizeError::new(),
@@ -72,11 +102,16 @@ impl<'r> Process<'r> {
 	}
 
 	pub(crate) fn run(&mut self) -> Result<Option<ExitStatus>, String> {
+		if self.git_interactive.has_recoverable_session() {
+			self.set_state(State::ConfirmRestore);
+		}
 		self.check_window_size();
 		while self.exit_status.is_none() {
 			self.process();
 			self.render();
 			self.handle_input();
+			// autosave is best effort, a failure to write should not interrupt editing
+			let _ = self.git_interactive.save_session();
 		}
 		self.exit_end()?;
 		Ok(self.exit_status)
@@ -87,7 +122,10 @@ impl<'r> Process<'r> {
 		match state {
 			State::ConfirmAbort => self.confirm_abort.activate(state, &self.git_interactive),
 			State::ConfirmRebase => self.confirm_rebase.activate(state, &self.git_interactive),
-			State::Edit => self.edit.activate(state, &self.git_interactive),
+			State::ConfirmRestore => self.confirm_restore.activate(state, &self.git_interactive),
+			State::CustomCommand(_) => self.custom_command.activate(state, &self.git_interactive),
+			State::Edit | State::EditHash => self.edit.activate(state, &self.git_interactive),
+			State::EditMessage => self.edit_message.activate(state, &self.git_interactive),
 			State::Error { .. } => self.error.activate(state, &self.git_interactive),
 			State::Exiting => self.exiting.activate(state, &self.git_interactive),
 			State::ExternalEditor => self.external_editor.activate(state, &self.git_interactive),
@@ -102,7 +140,10 @@ impl<'r> Process<'r> {
 		match self.get_state() {
 			State::ConfirmAbort => self.confirm_abort.deactivate(),
 			State::ConfirmRebase => self.confirm_rebase.deactivate(),
-			State::Edit => self.edit.deactivate(),
+			State::ConfirmRestore => self.confirm_restore.deactivate(),
+			State::CustomCommand(_) => self.custom_command.deactivate(),
+			State::Edit | State::EditHash => self.edit.deactivate(),
+			State::EditMessage => self.edit_message.deactivate(),
 			State::Error { .. } => self.error.deactivate(),
 			State::Exiting => self.exiting.deactivate(),
 			State::ExternalEditor => self.external_editor.deactivate(),
@@ -117,12 +158,15 @@ impl<'r> Process<'r> {
 		let result = match self.get_state() {
 			State::ConfirmAbort => self.confirm_abort.process(&mut self.git_interactive, &self.view),
 			State::ConfirmRebase => self.confirm_rebase.process(&mut self.git_interactive, &self.view),
-			State::Edit => self.edit.process(&mut self.git_interactive, &self.view),
+			State::ConfirmRestore => self.confirm_restore.process(&mut self.git_interactive, &self.view),
+			State::CustomCommand(_) => self.custom_command.process(&mut self.git_interactive, &self.view),
+			State::Edit | State::EditHash => self.edit.process(&mut self.git_interactive, &self.view),
+			State::EditMessage => self.edit_message.process(&mut self.git_interactive, self.view),
 			State::Error { .. } => self.error.process(&mut self.git_interactive, &self.view),
 			State::Exiting => self.exiting.process(&mut self.git_interactive, &self.view),
 			State::ExternalEditor => self.external_editor.process(&mut self.git_interactive, &self.view),
 			State::Help(_) => self.help.process(&mut self.git_interactive, &self.view),
-			State::List(_) => self.list.process(&mut self.git_interactive, &self.view),
+			State::List(_) => self.process_list(),
 			State::ShowCommit => self.show_commit.process(&mut self.git_interactive, &self.view),
 			State::WindowSizeError(_) => self.window_size_error.process(&mut self.git_interactive, &self.view),
 		};
@@ -141,16 +185,20 @@ impl<'r> Process<'r> {
 	}
 
 	fn render(&self) {
+		self.view.set_title_status(self.git_interactive.get_rebase_progress_title());
 		self.view.clear();
 		match self.get_state() {
 			State::ConfirmAbort => self.confirm_abort.render(&self.view, &self.git_interactive),
 			State::ConfirmRebase => self.confirm_rebase.render(&self.view, &self.git_interactive),
-			State::Edit => self.edit.render(&self.view, &self.git_interactive),
+			State::ConfirmRestore => self.confirm_restore.render(&self.view, &self.git_interactive),
+			State::CustomCommand(_) => self.custom_command.render(&self.view, &self.git_interactive),
+			State::Edit | State::EditHash => self.edit.render(&self.view, &self.git_interactive),
+			State::EditMessage => self.edit_message.render(self.view, &self.git_interactive),
 			State::Error { .. } => self.error.render(&self.view, &self.git_interactive),
 			State::Exiting => self.exiting.render(&self.view, &self.git_interactive),
 			State::ExternalEditor => self.external_editor.render(&self.view, &self.git_interactive),
 			State::Help(_) => self.help.render(&self.view, &self.git_interactive),
-			State::List(_) => self.list.render(&self.view, &self.git_interactive),
+			State::List(_) => self.render_list(),
 			State::ShowCommit => self.show_commit.render(&self.view, &self.git_interactive),
 			State::WindowSizeError(_) => self.window_size_error.render(&self.view, &self.git_interactive),
 		};
@@ -167,10 +215,22 @@ impl<'r> Process<'r> {
 				self.confirm_rebase
 					.handle_input(self.input_handler, &mut self.git_interactive, &self.view)
 			},
-			State::Edit => {
+			State::ConfirmRestore => {
+				self.confirm_restore
+					.handle_input(self.input_handler, &mut self.git_interactive, &self.view)
+			},
+			State::CustomCommand(_) => {
+				self.custom_command
+					.handle_input(self.input_handler, &mut self.git_interactive, &self.view)
+			},
+			State::Edit | State::EditHash => {
 				self.edit
 					.handle_input(&self.input_handler, &mut self.git_interactive, &self.view)
 			},
+			State::EditMessage => {
+				self.edit_message
+					.handle_input(self.input_handler, &mut self.git_interactive, self.view)
+			},
 			State::Error { .. } => {
 				self.error
 					.handle_input(&self.input_handler, &mut self.git_interactive, &self.view)
@@ -187,10 +247,7 @@ impl<'r> Process<'r> {
 				self.help
 					.handle_input(&self.input_handler, &mut self.git_interactive, &self.view)
 			},
-			State::List(_) => {
-				self.list
-					.handle_input(&self.input_handler, &mut self.git_interactive, &self.view)
-			},
+			State::List(_) => self.handle_list_input(),
 			State::ShowCommit => {
 				self.show_commit
 					.handle_input(&self.input_handler, &mut self.git_interactive, &self.view)
@@ -218,6 +275,90 @@ impl<'r> Process<'r> {
 		}
 	}
 
+	/// Gets the viewports of the list and the commit details, when the list is shown split and the window is large
+	/// enough for both
+	fn get_split_viewports(&self) -> Option<(Viewport, Viewport)> {
+		if let State::List(_) = self.get_state() {
+			let (width, height) = self.view.get_view_size();
+			match self.split_layout {
+				SplitLayout::Horizontal if width >= MINIMUM_SPLIT_WINDOW_WIDTH => {
+					// a blank column separates the panes
+					let list_width = (width - 1) / 2;
+					return Some((
+						Viewport::new(0, 0, list_width, height),
+						Viewport::new(list_width + 1, 0, width - list_width - 1, height),
+					));
+				},
+				SplitLayout::Vertical if height >= MINIMUM_SPLIT_WINDOW_HEIGHT => {
+					let list_height = height / 2;
+					return Some((
+						Viewport::new(0, 0, width, list_height),
+						Viewport::new(0, list_height, width, height - list_height),
+					));
+				},
+				_ => {},
+			}
+		}
+		None
+	}
+
+	fn process_list(&mut self) -> ProcessResult {
+		let viewports = self.get_split_viewports();
+		if viewports.is_none() {
+			self.details_focused = false;
+		}
+		self.view.set_viewport(viewports.map(|(list, _)| list));
+		let result = self.list.process(&mut self.git_interactive, self.view);
+		if let Some((_, details)) = viewports {
+			self.view.set_viewport(Some(details));
+			self.show_commit.update_pane(&self.git_interactive, self.view);
+		}
+		self.view.set_viewport(None);
+		result
+	}
+
+	fn render_list(&self) {
+		match self.get_split_viewports() {
+			Some((list, details)) => {
+				self.view.set_viewport(Some(list));
+				self.list.render(self.view, &self.git_interactive);
+				self.view.set_viewport(Some(details));
+				self.show_commit.render_pane(self.view, self.details_focused);
+				self.view.set_viewport(None);
+			},
+			None => self.list.render(self.view, &self.git_interactive),
+		}
+	}
+
+	/// Handles input for the list, or for the commit details when they have focus, a click in the other pane focuses it
+	fn handle_list_input(&mut self) -> HandleInputResult {
+		let viewports = self.get_split_viewports();
+		if let (true, Some((list, details))) = (self.details_focused, viewports) {
+			let input = self.input_handler.get_input(InputMode::Default);
+			if input == Input::ToggleFocus || is_mouse_input_in(input, &list) {
+				self.details_focused = false;
+			}
+			else {
+				self.view.set_viewport(Some(details));
+				self.show_commit.handle_scroll_input(input, self.view);
+				self.view.set_viewport(None);
+			}
+			return HandleInputResultBuilder::new(input).build();
+		}
+
+		self.view.set_viewport(viewports.map(|(list, _)| list));
+		let result = self
+			.list
+			.handle_input(&self.input_handler, &mut self.git_interactive, self.view);
+		self.view.set_viewport(None);
+		if let Some((_, details)) = viewports {
+			if result.input == Input::ToggleFocus || is_mouse_input_in(result.input, &details) {
+				self.details_focused = true;
+			}
+		}
+		result
+	}
+
 	fn check_window_size(&self) {
 		let check = self.view.check_window_size();
 		let state = self.get_state();
@@ -247,6 +388,755 @@ impl<'r> Process<'r> {
 				return Err(msg);
 			},
 		}
-		Ok(())
+		self.git_interactive.remove_session()
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Process;
+	use crate::config::split_layout::SplitLayout;
+	use crate::config::Config;
+	use crate::display::virtual_terminal::VirtualTerminal;
+	use crate::display::Display;
+	use crate::edit::history::ExecHistory;
+	use crate::git_interactive::GitInteractive;
+	use crate::input::input_handler::InputHandler;
+	use crate::input::recording::RecordedEvent;
+	use crate::input::Input as ProcessInput;
+	use crate::list::line::Line;
+	use crate::process::exit_status::ExitStatus;
+	use crate::rebase_progress::RebaseProgress;
+	use crate::temp_path::TempPath;
+	use crate::view::View;
+	use pancurses::{Input, MEVENT};
+	use serial_test::serial;
+	use std::fs;
+
+	/// Runs the process against a todo file in its own directory, returning the exit status and written todo file
+	fn run_process(
+		name: &str,
+		lines: &[&str],
+		terminal: &mut VirtualTerminal,
+		replay: &[RecordedEvent],
+	) -> (Option<ExitStatus>, String)
+	{
+		run_process_with_setup(name, lines, terminal, replay, |_, _| {})
+	}
+
+	fn run_process_with_setup<F>(
+		name: &str,
+		lines: &[&str],
+		terminal: &mut VirtualTerminal,
+		replay: &[RecordedEvent],
+		setup: F,
+	) -> (Option<ExitStatus>, String)
+	where
+		F: FnOnce(&mut GitInteractive, &mut ExecHistory),
+	{
+		run_process_with_config(name, lines, terminal, replay, |_| {}, setup)
+	}
+
+	fn run_process_with_config<C, F>(
+		name: &str,
+		lines: &[&str],
+		terminal: &mut VirtualTerminal,
+		replay: &[RecordedEvent],
+		configure: C,
+		setup: F,
+	) -> (Option<ExitStatus>, String)
+	where
+		C: FnOnce(&mut Config),
+		F: FnOnce(&mut GitInteractive, &mut ExecHistory),
+	{
+		let directory = TempPath::new_directory(name);
+		let filepath = directory.join("git-rebase-todo");
+		fs::write(&filepath, lines.join("\n")).unwrap();
+
+		let mut config = Config::new_from_config(&git2::Config::new().unwrap()).unwrap();
+		configure(&mut config);
+		let mut git_interactive = GitInteractive::new_from_filepath(filepath.to_str().unwrap(), "#").unwrap();
+		let display = Display::new(terminal, &config);
+		let mut input_handler = InputHandler::new(&display, &config);
+		if !replay.is_empty() {
+			input_handler.replay(replay.iter().cloned().collect());
+		}
+		let mut exec_history = ExecHistory::new();
+		setup(&mut git_interactive, &mut exec_history);
+		let view = View::new(&display, &config);
+		let mut process = Process::new(git_interactive, exec_history, &view, &display, &input_handler, &config);
+		let exit_status = process.run().unwrap();
+
+		(exit_status, fs::read_to_string(&filepath).unwrap())
+	}
+
+	#[test]
+	fn process_run_render_list() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str("W");
+		let (exit_status, _) = run_process("render-list", &["pick aaa first", "pick bbb second"], &mut terminal, &[]);
+		assert_eq!(exit_status, Some(ExitStatus::Good));
+		assert_eq!(terminal.get_screen(), vec![
+			"Git Interactive Rebase Tool      Help: ?",
+			"   pick   aaa      first",
+			" > pick   bbb      second",
+			"~",
+			"~",
+			"up,dn,q/Q,w/W,c,j,k,b,p,r,e,s,f,d,E,!,?",
+		]);
+	}
+
+	#[test]
+	fn process_run_render_done_lines() {
+		let mut terminal = VirtualTerminal::new(70, 6);
+		terminal.push_str("W");
+		run_process_with_setup("render-done-lines", &["pick bbb second"], &mut terminal, &[], |gi, _| {
+			gi.set_rebase_progress(RebaseProgress::new(vec![Line::new("pick aaa first").unwrap()], "x", "y"));
+		});
+		assert_eq!(terminal.get_screen(), vec![
+			"Git Interactive Rebase Tool - rebasing x onto y: 1/2 done      Help: ?",
+			"   pick   aaa      first",
+			" > pick   bbb      second",
+			"~",
+			"~",
+			" Up, Down, q/Q, w/W, c, j, k, b, p, r, e, s, f, d, E, !, ?",
+		]);
+		assert_eq!(terminal.get_attributes(3, 1) & pancurses::A_DIM, pancurses::A_DIM);
+		assert_eq!(terminal.get_attributes(3, 2) & pancurses::A_DIM, 0);
+	}
+
+	#[test]
+	fn process_run_render_rebase_progress_counts_current_todo() {
+		let mut terminal = VirtualTerminal::new(70, 6);
+		terminal.push_str("b");
+		terminal.push_str("W");
+		run_process_with_setup("render-rebase-progress", &["pick bbb second"], &mut terminal, &[], |gi, _| {
+			gi.set_rebase_progress(RebaseProgress::new(vec![Line::new("pick aaa first").unwrap()], "x", "y"));
+		});
+		let frames = terminal.get_frames();
+		assert_eq!(frames[0][0], "Git Interactive Rebase Tool - rebasing x onto y: 1/2 done      Help: ?");
+		assert_eq!(frames[1][0], "Git Interactive Rebase Tool - rebasing x onto y: 1/3 done      Help: ?");
+	}
+
+	#[test]
+	fn process_run_render_split_horizontal() {
+		let mut terminal = VirtualTerminal::new(100, 6);
+		terminal.push_str("\t");
+		terminal.push_str("\t");
+		terminal.push_str("W");
+		run_process_with_config(
+			"render-split-horizontal",
+			&["exec make", "break"],
+			&mut terminal,
+			&[],
+			|config| config.split_layout = SplitLayout::Horizontal,
+			|_, _| {},
+		);
+		let frames = terminal.get_frames();
+		assert_eq!(frames[0], vec![
+			"Git Interactive Rebase Tool               Help: ? Commit details",
+			" > exec   make                                    Cannot load commit for the selected action",
+			"   break                                          ~",
+			"~                                                 ~",
+			"~                                                 ~",
+			"up,dn,q/Q,w/W,c,j,k,b,p,r,e,s,f,d,E,!,?           Tab to focus details",
+		]);
+		assert_eq!(frames[1][5], "up,dn,q/Q,w/W,c,j,k,b,p,r,e,s,f,d,E,!,?           Tab to return to the list");
+		assert_eq!(frames[2][5], "up,dn,q/Q,w/W,c,j,k,b,p,r,e,s,f,d,E,!,?           Tab to focus details");
+	}
+
+	#[test]
+	fn process_run_render_split_vertical() {
+		let mut terminal = VirtualTerminal::new(60, 20);
+		terminal.push_str("W");
+		run_process_with_config(
+			"render-split-vertical",
+			&["exec make"],
+			&mut terminal,
+			&[],
+			|config| config.split_layout = SplitLayout::Vertical,
+			|_, _| {},
+		);
+		let screen = terminal.get_screen();
+		assert_eq!(screen[1], " > exec   make");
+		assert_eq!(screen[10], "Commit details");
+		assert_eq!(screen[11], "Cannot load commit for the selected action");
+		assert_eq!(screen[19], "Tab to focus details");
+	}
+
+	#[test]
+	fn process_run_render_split_window_too_narrow() {
+		let mut terminal = VirtualTerminal::new(60, 6);
+		terminal.push_str("W");
+		run_process_with_config(
+			"render-split-too-narrow",
+			&["exec make"],
+			&mut terminal,
+			&[],
+			|config| config.split_layout = SplitLayout::Horizontal,
+			|_, _| {},
+		);
+		assert_eq!(terminal.get_screen()[1], " > exec   make");
+		assert!(!terminal.get_screen().iter().any(|l| l.contains("Commit details")));
+	}
+
+	#[test]
+	fn process_run_render_chains() {
+		let mut terminal = VirtualTerminal::new(50, 7);
+		terminal.push_str("z");
+		terminal.push_str("W");
+		run_process(
+			"render-chains",
+			&["pick aaa first", "fixup bbb fix", "squash ccc more", "pick ddd second"],
+			&mut terminal,
+			&[],
+		);
+		let frames = terminal.get_frames();
+		assert_eq!(frames[0][1..5].to_vec(), vec![
+			" > / pick   aaa      first",
+			"   | fixup  bbb      fix",
+			"   \\ squash ccc      more",
+			"     pick   ddd      second",
+		]);
+		assert_eq!(frames[1][1..3].to_vec(), vec![
+			" > / pick   aaa      first +1 fixup, +1 squash",
+			"     pick   ddd      second",
+		]);
+	}
+
+	#[test]
+	fn process_run_move_chain() {
+		let mut terminal = VirtualTerminal::new(40, 7);
+		terminal.push_str("jW");
+		let (_, contents) = run_process(
+			"move-chain",
+			&["pick aaa first", "fixup bbb fix", "pick ccc second", "fixup ddd fix"],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(
+			contents,
+			"pick ccc second\nfixup ddd fix\npick aaa first\nfixup bbb fix\n"
+		);
+	}
+
+	#[test]
+	fn process_run_move_past_collapsed_chain() {
+		let mut terminal = VirtualTerminal::new(40, 7);
+		terminal.push_input(Input::KeyDown);
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str("zkW");
+		let (_, contents) = run_process(
+			"move-past-collapsed-chain",
+			&["pick aaa first", "pick bbb second", "fixup ccc fix"],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(contents, "pick bbb second\nfixup ccc fix\npick aaa first\n");
+	}
+
+	#[test]
+	fn process_run_render_status_bar() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("d");
+		terminal.push_str("W");
+		run_process_with_config(
+			"render-status-bar",
+			&["pick aaa first", "pick bbb second"],
+			&mut terminal,
+			&[],
+			|config| config.status_bar = true,
+			|_, _| {},
+		);
+		let frames = terminal.get_frames();
+		assert_eq!(frames[0][1..5].to_vec(), vec![
+			" > pick   aaa      first",
+			"   pick   bbb      second",
+			"~",
+			"p2|=2|1/2",
+		]);
+		assert_eq!(frames[1][4], "p1 d1|=1|1/2*");
+	}
+
+	#[test]
+	fn process_run_render_wide_characters() {
+		let mut terminal = VirtualTerminal::new(36, 6);
+		terminal.push_input(Input::KeyRight);
+		terminal.push_str("W");
+		run_process(
+			"render-wide-characters",
+			&["pick aaa 修复构建问题并更新文档", "pick bbb 🎉 リリース"],
+			&mut terminal,
+			&[],
+		);
+		let frames = terminal.get_frames();
+		assert_eq!(frames[0][1..3].to_vec(), vec![
+			" > pick   aaa      修复构建问题并更",
+			"   pick   bbb      🎉 リリース",
+		]);
+		assert_eq!(frames[1][1..3].to_vec(), vec![
+			" > pick   aaa       复构建问题并更新",
+			"   pick   bbb        リリース",
+		]);
+	}
+
+	#[test]
+	fn process_run_edit_wide_characters() {
+		let mut terminal = VirtualTerminal::new(21, 6);
+		terminal.push_str("E");
+		terminal.push_str("文档");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process("edit-wide-characters", &["exec echo 修复构建问题"], &mut terminal, &[]);
+		assert_eq!(contents, "exec echo 修复构建问题文档\n");
+		let frames = terminal.get_frames();
+		assert_eq!(frames[2][1], "echo 修复构建问题文");
+		assert_eq!(frames[3][1], "cho 修复构建问题文档");
+	}
+
+	#[test]
+	fn process_run_edit_line_editing() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("E");
+		// Control+w kills the word before the cursor, Control+a moves to the start and Control+y yanks the word back
+		terminal.push_str("\u{17} test\u{1}\u{19}");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process("edit-line-editing", &["exec cargo build"], &mut terminal, &[]);
+		assert_eq!(contents, "exec buildcargo  test\n");
+	}
+
+	#[test]
+	fn process_run_edit_history() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("E");
+		terminal.push_input(Input::KeyUp);
+		terminal.push_input(Input::KeyUp);
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str(" -j4");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process_with_setup(
+			"edit-history",
+			&["exec make"],
+			&mut terminal,
+			&[],
+			|_, exec_history| {
+				exec_history.add("npm test").unwrap();
+				exec_history.add("make check").unwrap();
+			},
+		);
+		assert_eq!(contents, "exec make check -j4\n");
+	}
+
+	#[test]
+	fn process_run_edit_history_restores_draft() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("E");
+		terminal.push_str("s");
+		terminal.push_input(Input::KeyUp);
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str(" -j4");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process_with_setup(
+			"edit-history-restores-draft",
+			&["exec make"],
+			&mut terminal,
+			&[],
+			|_, exec_history| exec_history.add("npm test").unwrap(),
+		);
+		assert_eq!(contents, "exec makes -j4\n");
+	}
+
+	#[test]
+	fn process_run_export_json_message() {
+		let mut terminal = VirtualTerminal::new(120, 6);
+		terminal.push_str("XjW");
+		let (_, contents) = run_process("export-json-message", &["exec make", "exec make test"], &mut terminal, &[]);
+		assert_eq!(contents, "exec make test\nexec make\n");
+		let frames = terminal.get_frames();
+		let export_filepath = std::env::temp_dir()
+			.join(format!("interactive-rebase-tool-{}-export-json-message", std::process::id()))
+			.join("interactive-rebase-tool-todo.json");
+		// the message replaces the footer of the list, and is removed by the next input
+		assert_eq!(frames[1][5], format!("Exported the todo to {}", export_filepath.display()));
+		assert!(!frames[2][5].starts_with("Exported"));
+	}
+
+	#[test]
+	fn process_run_edit_cancel() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("W");
+		let (_, contents) = run_process("edit-cancel", &["exec make"], &mut terminal, &[
+			RecordedEvent::Input(ProcessInput::Edit),
+			RecordedEvent::Input(ProcessInput::KillToStart),
+			RecordedEvent::Input(ProcessInput::Character('x')),
+			RecordedEvent::Input(ProcessInput::Escape),
+		]);
+		assert_eq!(contents, "exec make\n");
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_message() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(40, 8);
+		terminal.push_str("ENew \u{4}");
+		// the message is reopened from the file on the pick line, without adding another exec
+		terminal.push_str("E");
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str("X\u{4}W");
+		let hash = "18d82dcc4c36cade807d7cf79700b6bbad8080b9";
+		let (_, contents) = run_process(
+			"edit-message",
+			&[format!("reword {} Empty commit title", hash).as_str()],
+			&mut terminal,
+			&[],
+		);
+		let message_filepath = std::env::temp_dir()
+			.join(format!("interactive-rebase-tool-{}-edit-message", std::process::id()))
+			.join(format!("interactive-rebase-tool-message-{}", hash));
+		assert_eq!(
+			contents,
+			format!(
+				"pick {} Empty commit title\nexec git commit --amend --allow-empty -F '{}'\n",
+				hash,
+				message_filepath.display()
+			)
+		);
+		let frames = terminal.get_frames();
+		assert_eq!(frames[1], vec![
+			"Git Interactive Rebase Tool",
+			"Empty commit title",
+			"",
+			"Empty commit body",
+			"~",
+			"~",
+			"~",
+			"Control+d to save, Escape to cancel",
+		]);
+		assert_eq!(frames[7][1..4], ["New Empty commit title", "", "Empty commit body"]);
+		assert_eq!(frames[9][1..4], ["New Empty commit title", "X", "Empty commit body"]);
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_hash() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(60, 6);
+		// Control+u clears the hash before the new revision is entered
+		terminal.push_str("H\u{15}e10b3f47");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_input(Input::KeyDown);
+		terminal.push_str("H\u{15}not-a-commit");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str(" W");
+		let (_, contents) = run_process(
+			"edit-hash",
+			&[
+				"pick 18d82dcc4c36cade807d7cf79700b6bbad8080b9 Empty commit title",
+				"pick ac950e31a96660e55d8034948b5d9b985c97692d Author and committer same",
+			],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(
+			contents,
+			"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit\npick ac950e31a96660e55d8034948b5d9b985c97692d \
+			 Author and committer same\n"
+		);
+		let frames = terminal.get_frames();
+		assert!(frames.iter().any(|frame| frame.iter().any(|line| line.contains("Invalid commit, not-a-commit"))));
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_hash_same_commit() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(60, 6);
+		terminal.push_str("H\u{15}e10b3f4");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process(
+			"edit-hash-same-commit",
+			&["pick e10b3f474644 First commit"],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(contents, "pick e10b3f474644 First commit\n");
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_squash_message() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(50, 16);
+		terminal.push_input(Input::KeyDown);
+		// the saved message, without the comments, is shown when the message is edited again
+		terminal.push_str("E\u{4}E\u{4}W");
+		let (_, contents) = run_process(
+			"edit-squash-message",
+			&[
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit",
+				"squash ac950e31a96660e55d8034948b5d9b985c97692d Author and committer same",
+				"fixup 2836dcdcbd040f9157652dd3db0d584a44d4793d Author and committer different",
+			],
+			&mut terminal,
+			&[],
+		);
+		let message_filepath = std::env::temp_dir()
+			.join(format!("interactive-rebase-tool-{}-edit-squash-message", std::process::id()))
+			.join("interactive-rebase-tool-message-e10b3f474644d8566947104c07acba4d6f4f4f9f");
+		assert_eq!(
+			contents,
+			format!(
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit\nfixup \
+				 ac950e31a96660e55d8034948b5d9b985c97692d Author and committer same\nfixup \
+				 2836dcdcbd040f9157652dd3db0d584a44d4793d Author and committer different\nexec git commit --amend \
+				 --allow-empty -F '{}'\n",
+				message_filepath.display()
+			)
+		);
+		assert_eq!(terminal.get_frames()[2], vec![
+			"Git Interactive Rebase Tool",
+			"# This is a combination of 3 commits.",
+			"# This is the 1st commit message:",
+			"",
+			"First commit",
+			"",
+			"# This is the commit message #2:",
+			"",
+			"Author and committer same",
+			"",
+			"# The commit message #3 will be skipped:",
+			"",
+			"# Author and committer different",
+			"~",
+			"~",
+			"Control+d to save, Escape to cancel",
+		]);
+		assert_eq!(terminal.get_frames()[4][1..5], [
+			"First commit",
+			"",
+			"Author and committer same",
+			"~"
+		]);
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_message_drop_line() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(60, 8);
+		terminal.push_str("ENew \u{4}dW");
+		let (_, contents) = run_process(
+			"edit-message-drop-line",
+			&[
+				"reword 18d82dcc4c36cade807d7cf79700b6bbad8080b9 Empty commit title",
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit",
+			],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(
+			contents,
+			"drop 18d82dcc4c36cade807d7cf79700b6bbad8080b9 Empty commit title\npick \
+			 e10b3f474644d8566947104c07acba4d6f4f4f9f First commit\n"
+		);
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_message_move_line() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(60, 8);
+		terminal.push_str("ENew \u{4}jW");
+		let hash = "18d82dcc4c36cade807d7cf79700b6bbad8080b9";
+		let (_, contents) = run_process(
+			"edit-message-move-line",
+			&[
+				format!("reword {} Empty commit title", hash).as_str(),
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit",
+			],
+			&mut terminal,
+			&[],
+		);
+		let message_filepath = std::env::temp_dir()
+			.join(format!("interactive-rebase-tool-{}-edit-message-move-line", std::process::id()))
+			.join(format!("interactive-rebase-tool-message-{}", hash));
+		assert_eq!(
+			contents,
+			format!(
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit\npick {} Empty commit title\nexec git \
+				 commit --amend --allow-empty -F '{}'\n",
+				hash,
+				message_filepath.display()
+			)
+		);
+	}
+
+	#[test]
+	#[serial]
+	fn process_run_edit_message_edit_hash() {
+		std::env::set_var(
+			"GIT_DIR",
+			std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+				.join("test")
+				.join("fixtures")
+				.join("simple"),
+		);
+		let mut terminal = VirtualTerminal::new(60, 8);
+		terminal.push_str("ENew \u{4}H\u{15}ac950e31");
+		terminal.push_input(Input::Character('\n'));
+		terminal.push_str("W");
+		let (_, contents) = run_process(
+			"edit-message-edit-hash",
+			&[
+				"reword 18d82dcc4c36cade807d7cf79700b6bbad8080b9 Empty commit title",
+				"pick e10b3f474644d8566947104c07acba4d6f4f4f9f First commit",
+			],
+			&mut terminal,
+			&[],
+		);
+		assert_eq!(
+			contents,
+			"pick ac950e31a96660e55d8034948b5d9b985c97692d Author and committer same\npick \
+			 e10b3f474644d8566947104c07acba4d6f4f4f9f First commit\n"
+		);
+	}
+
+	#[test]
+	fn process_run_drop_line() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("dW");
+		let (_, contents) = run_process("drop-line", &["pick aaa first", "pick bbb second"], &mut terminal, &[]);
+		assert_eq!(contents, "drop aaa first\npick bbb second\n");
+	}
+
+	#[test]
+	fn process_run_mouse_click() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_mouse_event(MEVENT {
+			id: 0,
+			x: 5,
+			y: 2,
+			z: 0,
+			bstate: pancurses::BUTTON1_CLICKED,
+		});
+		terminal.push_str("fW");
+		let (_, contents) = run_process("mouse-click", &["pick aaa first", "pick bbb second"], &mut terminal, &[]);
+		assert_eq!(contents, "pick aaa first\nfixup bbb second\n");
+	}
+
+	#[test]
+	fn process_run_mouse_click_split() {
+		let mut terminal = VirtualTerminal::new(100, 6);
+		for x in &[60, 5, 5] {
+			terminal.push_mouse_event(MEVENT {
+				id: 0,
+				x: *x,
+				y: 2,
+				z: 0,
+				bstate: pancurses::BUTTON1_CLICKED,
+			});
+		}
+		terminal.push_str("fW");
+		let (_, contents) = run_process_with_config(
+			"mouse-click-split",
+			&["pick aaa first", "pick bbb second"],
+			&mut terminal,
+			&[],
+			|config| config.split_layout = SplitLayout::Horizontal,
+			|_, _| {},
+		);
+		// the click in the details focuses them, the first click in the list returns focus and the second selects
+		let frames = terminal.get_frames();
+		assert!(frames[1][1].starts_with(" > pick   aaa"));
+		assert!(frames[1][5].ends_with("Tab to return to the list"));
+		assert!(frames[2][5].ends_with("Tab to focus details"));
+		assert_eq!(contents, "pick aaa first\nfixup bbb second\n");
+	}
+
+	#[test]
+	fn process_run_abort() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("dqy");
+		let (exit_status, contents) = run_process("abort", &["pick aaa first"], &mut terminal, &[]);
+		assert_eq!(exit_status, Some(ExitStatus::Good));
+		assert_eq!(contents, "");
+		assert_eq!(terminal.get_frames().len(), 3);
+	}
+
+	#[test]
+	fn process_run_window_too_small() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_resize(5, 2);
+		terminal.push_resize(40, 6);
+		terminal.push_str("W");
+		run_process("window-too-small", &["pick aaa first"], &mut terminal, &[]);
+		assert_eq!(terminal.get_frames()[1], vec!["Size!", ""]);
+	}
+
+	#[test]
+	fn process_run_replay() {
+		let mut terminal = VirtualTerminal::new(40, 6);
+		terminal.push_str("W");
+		let (_, contents) = run_process(
+			"replay",
+			&["pick aaa first", "pick bbb second"],
+			&mut terminal,
+			&[
+				RecordedEvent::Size(36, 6),
+				RecordedEvent::Input(ProcessInput::MoveCursorDown),
+				RecordedEvent::Input(ProcessInput::ActionDrop),
+			],
+		);
+		assert_eq!(contents, "pick aaa first\ndrop bbb second\n");
+		assert_eq!(terminal.get_frames()[1], vec![
+			"Git Interactive Rebase Tool  Help: ?",
+			"   pick   aaa      first",
+			" > pick   bbb      second",
+			"~",
+			"~",
+			"Help: ?",
+		]);
 	}
 }
@@ -2,7 +2,11 @@
 pub(crate) enum State {
 	ConfirmAbort,
 	ConfirmRebase,
+	ConfirmRestore,
+	CustomCommand(usize),
 	Edit,
+	EditHash,
+	EditMessage,
 	Error { return_state: Box<State>, message: String },
 	Exiting,
 	ExternalEditor,
@@ -0,0 +1,146 @@
+use crate::list::line::Line;
+use git2::{BranchType, Oid, Repository};
+use std::fs;
+use std::path::Path;
+
+const SHORT_HASH_LENGTH: usize = 7;
+
+/// The state of a rebase that is in progress, read from the `rebase-merge` directory of the repository
+pub(crate) struct RebaseProgress {
+	done_lines: Vec<Line>,
+	head_name: String,
+	onto_name: String,
+}
+
+fn short_hash(hash: &str) -> &str {
+	&hash[0..hash.len().min(SHORT_HASH_LENGTH)]
+}
+
+/// Parses the done file, lines that are not supported by the editor, such as `label` and `reset`, are skipped
+fn parse_done(contents: &str) -> Vec<Line> {
+	contents
+		.lines()
+		.filter(|l| !l.starts_with('#') && !l.trim().is_empty())
+		.filter_map(|l| Line::new(l).ok())
+		.collect()
+}
+
+fn get_head_name(head_name: &str, orig_head: &str) -> String {
+	match head_name.strip_prefix("refs/heads/") {
+		Some(branch) => String::from(branch),
+		None => String::from(short_hash(orig_head)),
+	}
+}
+
+/// Gets the name of a local, or else remote, branch that points at the onto commit
+fn get_onto_name(repository: &Repository, onto: &str) -> String {
+	let oid = match Oid::from_str(onto) {
+		Ok(oid) => oid,
+		Err(_) => return String::from(short_hash(onto)),
+	};
+	for branch_type in &[BranchType::Local, BranchType::Remote] {
+		if let Ok(branches) = repository.branches(Some(*branch_type)) {
+			for (branch, _) in branches.filter_map(Result::ok) {
+				if branch.get().target() == Some(oid) {
+					if let Ok(Some(name)) = branch.name() {
+						return String::from(name);
+					}
+				}
+			}
+		}
+	}
+	String::from(short_hash(onto))
+}
+
+fn read_file(directory: &Path, name: &str) -> Result<String, String> {
+	let path = directory.join(name);
+	fs::read_to_string(&path).map_err(|why| format!("Error reading file, {}\nReason: {}", path.display(), why))
+}
+
+impl RebaseProgress {
+	pub(crate) fn new(done_lines: Vec<Line>, head_name: &str, onto_name: &str) -> Self {
+		Self {
+			done_lines,
+			head_name: String::from(head_name),
+			onto_name: String::from(onto_name),
+		}
+	}
+
+	/// Loads the rebase progress when the todo file belongs to a rebase in progress in the current repository
+	pub(crate) fn load(todo_filepath: &str) -> Option<Self> {
+		let repository = Repository::open_from_env().ok()?;
+		let directory = repository.path().join("rebase-merge");
+		let todo_directory = Path::new(todo_filepath).canonicalize().ok()?.parent()?.to_path_buf();
+		if directory.canonicalize().ok()? != todo_directory {
+			return None;
+		}
+		// the progress only adds context, so a rebase directory that cannot be read is ignored
+		Self::from_directory(&directory, &repository).ok()
+	}
+
+	fn from_directory(directory: &Path, repository: &Repository) -> Result<Self, String> {
+		// the done file does not exist until the first line of the todo is applied
+		let done = if directory.join("done").exists() {
+			read_file(directory, "done")?
+		}
+		else {
+			String::new()
+		};
+		let onto = read_file(directory, "onto")?;
+		let head_name = read_file(directory, "head-name")?;
+		let orig_head = read_file(directory, "orig-head")?;
+
+		Ok(Self::new(
+			parse_done(done.as_str()),
+			get_head_name(head_name.trim(), orig_head.trim()).as_str(),
+			get_onto_name(repository, onto.trim()).as_str(),
+		))
+	}
+
+	pub(crate) fn get_done_lines(&self) -> &Vec<Line> {
+		&self.done_lines
+	}
+
+	/// Gets the title status, the total is the done lines and the lines that remain in the todo
+	pub(crate) fn get_title(&self, remaining: usize) -> String {
+		format!(
+			"rebasing {} onto {}: {}/{} done",
+			self.head_name,
+			self.onto_name,
+			self.done_lines.len(),
+			self.done_lines.len() + remaining
+		)
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_head_name, parse_done, RebaseProgress};
+	use crate::list::line::Line;
+
+	#[test]
+	fn rebase_progress_parse_done() {
+		let lines = parse_done("pick aaa first\n# comment\n\nlabel onto\nexec make\nedit bbb second\n");
+		assert_eq!(lines.iter().map(Line::to_text).collect::<Vec<String>>(), vec![
+			"pick aaa first",
+			"exec make",
+			"edit bbb second"
+		]);
+	}
+
+	#[test]
+	fn rebase_progress_head_name_branch() {
+		assert_eq!(get_head_name("refs/heads/feature-x", "0123456789abcdef"), "feature-x");
+	}
+
+	#[test]
+	fn rebase_progress_head_name_detached() {
+		assert_eq!(get_head_name("detached HEAD", "0123456789abcdef"), "0123456");
+	}
+
+	#[test]
+	fn rebase_progress_title() {
+		let progress = RebaseProgress::new(parse_done("pick aaa first\npick bbb second\n"), "feature-x", "main");
+		assert_eq!(progress.get_title(5), "rebasing feature-x onto main: 2/7 done");
+	}
+}
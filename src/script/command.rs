@@ -0,0 +1,237 @@
+use crate::external_editor::argument_tolkenizer::tolkenize;
+use crate::list::action::Action;
+use std::convert::TryFrom;
+
+#[derive(Clone, Debug, PartialEq)]
+pub(super) enum ScriptCommand {
+	Action { action: Action, start: usize, end: usize },
+	BreakAfter(usize),
+	ExecAfter(usize, String),
+	ExecAfterEach(String),
+	Move { start: usize, end: usize, to: usize },
+}
+
+fn parse_line_number(value: &str) -> Result<usize, String> {
+	match value.parse::<usize>() {
+		Ok(n) if n > 0 => Ok(n),
+		_ => Err(format!("Invalid line number: {}", value)),
+	}
+}
+
+/// Parses a line number, or an inclusive range of line numbers in the form `start..end`
+fn parse_range(value: &str) -> Result<(usize, usize), String> {
+	let (start, end) = match value.find("..") {
+		Some(index) => (parse_line_number(&value[..index])?, parse_line_number(&value[index + 2..])?),
+		None => {
+			let line_number = parse_line_number(value)?;
+			(line_number, line_number)
+		},
+	};
+	if start > end {
+		return Err(format!("Invalid line range: {}", value));
+	}
+	Ok((start, end))
+}
+
+fn get_exec_command(arguments: &[String]) -> Result<String, String> {
+	if arguments.is_empty() {
+		return Err(String::from("Missing exec command"));
+	}
+	Ok(arguments.join(" "))
+}
+
+impl ScriptCommand {
+	pub(super) fn parse(input: &str) -> Result<Self, String> {
+		let tokens = match tolkenize(input) {
+			Some(tokens) if !tokens.is_empty() => tokens,
+			_ => return Err(format!("Invalid command: {}", input)),
+		};
+		let name = tokens[0].as_str();
+		let arguments = &tokens[1..];
+
+		match (name, arguments.len()) {
+			("move", 3) if arguments[1] == "to" => {
+				let (start, end) = parse_range(arguments[0].as_str())?;
+				Ok(ScriptCommand::Move {
+					start,
+					end,
+					to: parse_line_number(arguments[2].as_str())?,
+				})
+			},
+			("move", _) => Err(String::from("Invalid move command, expected: move <range> to <line>")),
+			("break-after", 1) => Ok(ScriptCommand::BreakAfter(parse_line_number(arguments[0].as_str())?)),
+			("break-after", _) => Err(String::from("Invalid break-after command, expected: break-after <line>")),
+			("exec-after", n) if n >= 1 => {
+				Ok(ScriptCommand::ExecAfter(
+					parse_line_number(arguments[0].as_str())?,
+					get_exec_command(&arguments[1..])?,
+				))
+			},
+			("exec-after", _) => {
+				Err(String::from(
+					"Invalid exec-after command, expected: exec-after <line> <command>",
+				))
+			},
+			("exec-after-each", _) => Ok(ScriptCommand::ExecAfterEach(get_exec_command(arguments)?)),
+			(_, 1) => {
+				let action = match Action::try_from(name) {
+					Ok(Action::Break) | Ok(Action::Exec) | Ok(Action::Noop) | Err(_) => {
+						return Err(format!("Invalid command: {}", name));
+					},
+					Ok(action) => action,
+				};
+				let (start, end) = parse_range(arguments[0].as_str())?;
+				Ok(ScriptCommand::Action { action, start, end })
+			},
+			_ => Err(format!("Invalid command: {}", input)),
+		}
+	}
+}
+
+/// Splits a script into commands on semicolons and new lines, ignoring separators within quotes
+pub(super) fn split_script(script: &str) -> Vec<String> {
+	let mut commands = vec![];
+	let mut command = String::new();
+	let mut quote: Option<char> = None;
+	let mut escaped = false;
+
+	for c in script.chars() {
+		if escaped {
+			escaped = false;
+		}
+		else if c == '\\' && quote != Some('\'') {
+			escaped = true;
+		}
+		else if quote.is_some() {
+			if quote == Some(c) {
+				quote = None;
+			}
+		}
+		else if c == '"' || c == '\'' {
+			quote = Some(c);
+		}
+		else if c == ';' || c == '\n' {
+			commands.push(command.trim().to_string());
+			command.clear();
+			continue;
+		}
+		command.push(c);
+	}
+	commands.push(command.trim().to_string());
+	commands.into_iter().filter(|c| !c.is_empty()).collect()
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{split_script, ScriptCommand};
+	use crate::list::action::Action;
+
+	#[test]
+	fn split_script_multiple_commands() {
+		assert_eq!(split_script("fixup 3..5; drop 7\nmove 9 to 1;"), vec![
+			"fixup 3..5",
+			"drop 7",
+			"move 9 to 1"
+		]);
+	}
+
+	#[test]
+	fn split_script_quoted_separator() {
+		assert_eq!(split_script("exec-after-each \"make; make test\"; drop 1"), vec![
+			"exec-after-each \"make; make test\"",
+			"drop 1"
+		]);
+	}
+
+	#[test]
+	fn split_script_escaped_separator() {
+		assert_eq!(split_script("exec-after 1 echo a\\; b"), vec!["exec-after 1 echo a\\; b"]);
+	}
+
+	#[test]
+	fn parse_action_single_line() {
+		assert_eq!(ScriptCommand::parse("drop 7").unwrap(), ScriptCommand::Action {
+			action: Action::Drop,
+			start: 7,
+			end: 7
+		});
+	}
+
+	#[test]
+	fn parse_action_range() {
+		assert_eq!(ScriptCommand::parse("f 3..5").unwrap(), ScriptCommand::Action {
+			action: Action::Fixup,
+			start: 3,
+			end: 5
+		});
+	}
+
+	#[test]
+	fn parse_action_invalid_range() {
+		assert_eq!(
+			ScriptCommand::parse("fixup 5..3").unwrap_err(),
+			"Invalid line range: 5..3"
+		);
+	}
+
+	#[test]
+	fn parse_action_invalid_line_number() {
+		assert_eq!(ScriptCommand::parse("drop 0").unwrap_err(), "Invalid line number: 0");
+	}
+
+	#[test]
+	fn parse_action_break_is_invalid() {
+		assert_eq!(ScriptCommand::parse("break 1").unwrap_err(), "Invalid command: break");
+	}
+
+	#[test]
+	fn parse_unknown_command() {
+		assert_eq!(ScriptCommand::parse("foo 1").unwrap_err(), "Invalid command: foo");
+	}
+
+	#[test]
+	fn parse_move() {
+		assert_eq!(ScriptCommand::parse("move 9 to 1").unwrap(), ScriptCommand::Move {
+			start: 9,
+			end: 9,
+			to: 1
+		});
+	}
+
+	#[test]
+	fn parse_move_invalid() {
+		assert_eq!(
+			ScriptCommand::parse("move 9 1").unwrap_err(),
+			"Invalid move command, expected: move <range> to <line>"
+		);
+	}
+
+	#[test]
+	fn parse_break_after() {
+		assert_eq!(
+			ScriptCommand::parse("break-after 2").unwrap(),
+			ScriptCommand::BreakAfter(2)
+		);
+	}
+
+	#[test]
+	fn parse_exec_after() {
+		assert_eq!(
+			ScriptCommand::parse("exec-after 2 make test").unwrap(),
+			ScriptCommand::ExecAfter(2, String::from("make test"))
+		);
+	}
+
+	#[test]
+	fn parse_exec_after_missing_command() {
+		assert_eq!(ScriptCommand::parse("exec-after 2").unwrap_err(), "Missing exec command");
+	}
+
+	#[test]
+	fn parse_exec_after_each_quoted() {
+		assert_eq!(
+			ScriptCommand::parse("exec-after-each \"make test\"").unwrap(),
+			ScriptCommand::ExecAfterEach(String::from("make test"))
+		);
+	}
+}
@@ -0,0 +1,232 @@
+mod command;
+
+use crate::git_interactive::GitInteractive;
+use crate::list::action::Action;
+use crate::list::line::Line;
+use crate::script::command::{split_script, ScriptCommand};
+
+fn check_line_number(git_interactive: &GitInteractive, line_number: usize) -> Result<(), String> {
+	let length = git_interactive.get_lines().len();
+	if line_number > length {
+		return Err(format!(
+			"Line {} does not exist, the todo file has {} lines",
+			line_number, length
+		));
+	}
+	Ok(())
+}
+
+/// Checks if an action keeps a commit in the rebase
+fn is_commit_action(action: Action) -> bool {
+	action.has_commit() && action != Action::Drop
+}
+
+fn set_action(git_interactive: &mut GitInteractive, action: Action, start: usize, end: usize) -> Result<(), String> {
+	check_line_number(git_interactive, end)?;
+	git_interactive.set_selected_line_index(start);
+	git_interactive.start_visual_mode();
+	git_interactive.set_selected_line_index(end);
+	git_interactive.set_visual_range_action(action);
+	Ok(())
+}
+
+fn move_lines(git_interactive: &mut GitInteractive, start: usize, end: usize, to: usize) -> Result<(), String> {
+	check_line_number(git_interactive, end)?;
+	check_line_number(git_interactive, to + end - start)?;
+	git_interactive.set_selected_line_index(start);
+	git_interactive.start_visual_mode();
+	git_interactive.set_selected_line_index(end);
+
+	let mut top = start;
+	while top > to {
+		git_interactive.swap_visual_range_up();
+		top -= 1;
+	}
+	while top < to {
+		git_interactive.swap_visual_range_down();
+		top += 1;
+	}
+	Ok(())
+}
+
+fn insert_after(git_interactive: &mut GitInteractive, line_number: usize, line: Line) -> Result<(), String> {
+	check_line_number(git_interactive, line_number)?;
+	git_interactive.set_selected_line_index(line_number);
+	git_interactive.insert_line_after_selected(line);
+	Ok(())
+}
+
+/// Adds an exec after each commit, or after the last commit of a squash or fixup chain, like `git rebase --exec`
+fn exec_after_each(git_interactive: &mut GitInteractive, command: &str) -> Result<(), String> {
+	let actions: Vec<Action> = git_interactive.get_lines().iter().map(|l| *l.get_action()).collect();
+	for (index, action) in actions.iter().enumerate().rev() {
+		if !is_commit_action(*action) {
+			continue;
+		}
+		match actions.get(index + 1) {
+			Some(Action::Fixup) | Some(Action::Squash) => continue,
+			_ => {},
+		}
+		insert_after(git_interactive, index + 1, Line::new(format!("exec {}", command).as_str())?)?;
+	}
+	Ok(())
+}
+
+fn validate(git_interactive: &GitInteractive) -> Result<(), String> {
+	match git_interactive
+		.get_lines()
+		.iter()
+		.map(|l| *l.get_action())
+		.find(|a| is_commit_action(*a))
+	{
+		Some(Action::Fixup) | Some(Action::Squash) => {
+			Err(String::from(
+				"The first commit cannot be a fixup or squash, since there is no previous commit",
+			))
+		},
+		_ => Ok(()),
+	}
+}
+
+/// Applies the commands in a script, line numbers refer to the todo lines as they are when each command runs
+pub(crate) fn run_script(git_interactive: &mut GitInteractive, script: &str) -> Result<(), String> {
+	let commands = split_script(script);
+	if commands.is_empty() {
+		return Err(String::from("Error in script: no commands provided"));
+	}
+
+	for (index, input) in commands.iter().enumerate() {
+		let result = match ScriptCommand::parse(input.as_str()) {
+			Ok(ScriptCommand::Action { action, start, end }) => set_action(git_interactive, action, start, end),
+			Ok(ScriptCommand::BreakAfter(line_number)) => {
+				insert_after(git_interactive, line_number, Line::new_break())
+			},
+			Ok(ScriptCommand::ExecAfter(line_number, command)) => {
+				Line::new(format!("exec {}", command).as_str())
+					.and_then(|line| insert_after(git_interactive, line_number, line))
+			},
+			Ok(ScriptCommand::ExecAfterEach(command)) => exec_after_each(git_interactive, command.as_str()),
+			Ok(ScriptCommand::Move { start, end, to }) => move_lines(git_interactive, start, end, to),
+			Err(e) => Err(e),
+		};
+		if let Err(e) = result {
+			return Err(format!("Error in script command {} ({}): {}", index + 1, input, e));
+		}
+	}
+
+	validate(git_interactive).map_err(|e| format!("Error in script: {}", e))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::run_script;
+	use crate::git_interactive::GitInteractive;
+	use crate::temp_path::TempPath;
+	use std::fs;
+
+	/// Runs the script on a todo with the lines, returning the resulting lines or the error
+	fn run(name: &str, lines: &[&str], script: &str) -> Result<Vec<String>, String> {
+		let directory = TempPath::new_directory(format!("script-{}", name).as_str());
+		let todo_filepath = directory.join("git-rebase-todo");
+		fs::write(&todo_filepath, format!("{}\n", lines.join("\n"))).unwrap();
+
+		let mut git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		run_script(&mut git_interactive, script)
+			.map(|_| git_interactive.get_lines().iter().map(|l| l.to_text()).collect())
+	}
+
+	const LINES: [&str; 4] = ["pick aaa c1", "pick bbb c2", "pick ccc c3", "pick ddd c4"];
+
+	#[test]
+	fn run_script_set_action_range() {
+		assert_eq!(run("action-range", &LINES, "fixup 2..3; drop 4").unwrap(), vec![
+			"pick aaa c1",
+			"fixup bbb c2",
+			"fixup ccc c3",
+			"drop ddd c4"
+		]);
+	}
+
+	#[test]
+	fn run_script_move_up() {
+		assert_eq!(run("move-up", &LINES, "move 3..4 to 1").unwrap(), vec![
+			"pick ccc c3",
+			"pick ddd c4",
+			"pick aaa c1",
+			"pick bbb c2"
+		]);
+	}
+
+	#[test]
+	fn run_script_move_down() {
+		assert_eq!(run("move-down", &LINES, "move 1 to 3").unwrap(), vec![
+			"pick bbb c2",
+			"pick ccc c3",
+			"pick aaa c1",
+			"pick ddd c4"
+		]);
+	}
+
+	#[test]
+	fn run_script_move_past_end() {
+		assert_eq!(
+			run("move-past-end", &LINES, "move 2..3 to 4").unwrap_err(),
+			"Error in script command 1 (move 2..3 to 4): Line 5 does not exist, the todo file has 4 lines"
+		);
+	}
+
+	#[test]
+	fn run_script_break_and_exec_after() {
+		// line numbers refer to the lines as they are when each command runs
+		assert_eq!(
+			run("break-and-exec-after", &LINES, "break-after 1; exec-after 3 'make test'").unwrap(),
+			vec!["pick aaa c1", "break", "pick bbb c2", "exec make test", "pick ccc c3", "pick ddd c4"]
+		);
+	}
+
+	#[test]
+	fn run_script_insert_after_missing_line() {
+		assert_eq!(
+			run("insert-after-missing-line", &LINES, "break-after 5").unwrap_err(),
+			"Error in script command 1 (break-after 5): Line 5 does not exist, the todo file has 4 lines"
+		);
+	}
+
+	#[test]
+	fn run_script_exec_after_each() {
+		let lines = ["pick aaa c1", "pick bbb c2", "fixup ccc c3", "drop ddd c4", "break"];
+		assert_eq!(run("exec-after-each", &lines, "exec-after-each make").unwrap(), vec![
+			"pick aaa c1",
+			"exec make",
+			"pick bbb c2",
+			"fixup ccc c3",
+			"exec make",
+			"drop ddd c4",
+			"break"
+		]);
+	}
+
+	#[test]
+	fn run_script_invalid_command() {
+		assert_eq!(
+			run("invalid-command", &LINES, "drop 1; merge 2").unwrap_err(),
+			"Error in script command 2 (merge 2): Invalid command: merge"
+		);
+	}
+
+	#[test]
+	fn run_script_no_commands() {
+		assert_eq!(
+			run("no-commands", &LINES, "").unwrap_err(),
+			"Error in script: no commands provided"
+		);
+	}
+
+	#[test]
+	fn run_script_first_commit_fixup() {
+		assert_eq!(
+			run("first-commit-fixup", &LINES, "drop 1; squash 2").unwrap_err(),
+			"Error in script: The first commit cannot be a fixup or squash, since there is no previous commit"
+		);
+	}
+}
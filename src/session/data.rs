@@ -0,0 +1,184 @@
+use crate::list::line::Line;
+
+const HEADER: &str = "# interactive-rebase-tool session";
+
+fn parse_value<'a>(line: Option<&'a str>, name: &str) -> Result<&'a str, String> {
+	let line = line.ok_or_else(|| format!("Missing session value: {}", name))?;
+	let mut split = line.splitn(2, ' ');
+	match (split.next(), split.next()) {
+		(Some(n), Some(value)) if n == name => Ok(value),
+		_ => Err(format!("Invalid session value: {}", line)),
+	}
+}
+
+fn parse_number(line: Option<&str>, name: &str) -> Result<usize, String> {
+	let value = parse_value(line, name)?;
+	value
+		.parse::<usize>()
+		.map_err(|_| format!("Invalid session value for {}: {}", name, value))
+}
+
+#[derive(Debug, PartialEq)]
+pub(crate) struct SessionData {
+	lines: Vec<Line>,
+	original_lines: Vec<String>,
+	selected_line_index: usize,
+	visual_index_start: usize,
+}
+
+impl SessionData {
+	pub(crate) fn new(
+		original_lines: &[Line],
+		lines: &[Line],
+		selected_line_index: usize,
+		visual_index_start: usize,
+	) -> Self
+	{
+		Self {
+			lines: lines.to_vec(),
+			original_lines: original_lines.iter().map(Line::to_text).collect(),
+			selected_line_index,
+			visual_index_start,
+		}
+	}
+
+	pub(crate) fn parse(content: &str) -> Result<Self, String> {
+		let mut content_lines = content.lines();
+
+		if content_lines.next() != Some(HEADER) {
+			return Err(String::from("Invalid session file"));
+		}
+
+		let selected_line_index = parse_number(content_lines.next(), "selected")?;
+		let visual_index_start = parse_number(content_lines.next(), "visual")?;
+
+		let original_length = parse_number(content_lines.next(), "original")?;
+		let mut original_lines = vec![];
+		for _ in 0..original_length {
+			match content_lines.next() {
+				Some(line) => original_lines.push(String::from(line)),
+				None => return Err(String::from("Session file is incomplete")),
+			}
+		}
+
+		let lines_length = parse_number(content_lines.next(), "lines")?;
+		let mut lines = vec![];
+		for _ in 0..lines_length {
+			match content_lines.next() {
+				Some(line) => lines.push(Line::new(line)?),
+				None => return Err(String::from("Session file is incomplete")),
+			}
+		}
+
+		Ok(Self {
+			lines,
+			original_lines,
+			selected_line_index,
+			visual_index_start,
+		})
+	}
+
+	pub(crate) fn to_text(&self) -> String {
+		let mut text = vec![
+			String::from(HEADER),
+			format!("selected {}", self.selected_line_index),
+			format!("visual {}", self.visual_index_start),
+			format!("original {}", self.original_lines.len()),
+		];
+		text.extend(self.original_lines.iter().cloned());
+		text.push(format!("lines {}", self.lines.len()));
+		text.extend(self.lines.iter().map(Line::to_text));
+		text.push(String::from(""));
+		text.join("\n")
+	}
+
+	/// Checks if the session was started from the provided original todo lines
+	pub(crate) fn matches_original(&self, original_lines: &[Line]) -> bool {
+		self.original_lines.len() == original_lines.len()
+			&& self
+				.original_lines
+				.iter()
+				.zip(original_lines.iter())
+				.all(|(a, b)| *a == b.to_text())
+	}
+
+	pub(crate) fn get_lines(&self) -> &Vec<Line> {
+		&self.lines
+	}
+
+	pub(crate) fn get_selected_line_index(&self) -> usize {
+		self.selected_line_index
+	}
+
+	pub(crate) fn get_visual_index_start(&self) -> usize {
+		self.visual_index_start
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::SessionData;
+	use crate::list::line::Line;
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	#[test]
+	fn session_data_to_text() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let lines = create_lines(&["pick bbb c2", "exec make", "drop aaa c1"]);
+		let data = SessionData::new(&original, &lines, 2, 1);
+		assert_eq!(
+			data.to_text(),
+			"# interactive-rebase-tool session\nselected 2\nvisual 1\noriginal 2\npick aaa c1\npick bbb c2\nlines \
+			 3\npick bbb c2\nexec make\ndrop aaa c1\n"
+		);
+	}
+
+	#[test]
+	fn session_data_parse_round_trip() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let lines = create_lines(&["pick bbb c2", "break", "fixup aaa c1"]);
+		let data = SessionData::new(&original, &lines, 3, 2);
+		let parsed = SessionData::parse(data.to_text().as_str()).unwrap();
+		assert_eq!(parsed, data);
+		assert_eq!(parsed.get_selected_line_index(), 3);
+		assert_eq!(parsed.get_visual_index_start(), 2);
+		assert_eq!(parsed.get_lines(), &lines);
+	}
+
+	#[test]
+	fn session_data_parse_invalid_header() {
+		assert_eq!(
+			SessionData::parse("foo\nselected 1").unwrap_err(),
+			"Invalid session file"
+		);
+	}
+
+	#[test]
+	fn session_data_parse_invalid_value() {
+		assert_eq!(
+			SessionData::parse("# interactive-rebase-tool session\nselected a").unwrap_err(),
+			"Invalid session value for selected: a"
+		);
+	}
+
+	#[test]
+	fn session_data_parse_incomplete() {
+		assert_eq!(
+			SessionData::parse("# interactive-rebase-tool session\nselected 1\nvisual 1\noriginal 2\npick aaa c1")
+				.unwrap_err(),
+			"Session file is incomplete"
+		);
+	}
+
+	#[test]
+	fn session_data_matches_original() {
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let data = SessionData::new(&original, &original, 1, 1);
+		assert!(data.matches_original(&original));
+		assert!(!data.matches_original(&create_lines(&["pick aaa c1"])));
+		assert!(!data.matches_original(&create_lines(&["pick aaa c1", "pick ccc c3"])));
+	}
+}
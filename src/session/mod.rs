@@ -0,0 +1,215 @@
+pub(crate) mod data;
+
+use crate::constants::SESSION_FILE_NAME;
+use crate::session::data::SessionData;
+use std::fs::{remove_file, File};
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+/// An autosaved copy of the in progress edits, stored next to the todo file
+pub(crate) struct Session {
+	filepath: PathBuf,
+	last_saved: String,
+}
+
+impl Session {
+	pub(crate) fn new(todo_filepath: &Path, initial: &SessionData) -> Self {
+		let filepath = match todo_filepath.parent() {
+			Some(parent) => parent.join(SESSION_FILE_NAME),
+			None => PathBuf::from(SESSION_FILE_NAME),
+		};
+		Self {
+			filepath,
+			last_saved: initial.to_text(),
+		}
+	}
+
+	pub(crate) fn load(&self) -> Result<Option<SessionData>, String> {
+		if !self.filepath.exists() {
+			return Ok(None);
+		}
+
+		let mut file = File::open(&self.filepath).map_err(|why| {
+			format!(
+				"Error opening session file, {}\nReason: {}",
+				self.filepath.display(),
+				why
+			)
+		})?;
+		let mut content = String::new();
+		file.read_to_string(&mut content).map_err(|why| {
+			format!(
+				"Error reading session file, {}\nReason: {}",
+				self.filepath.display(),
+				why
+			)
+		})?;
+
+		Ok(Some(SessionData::parse(content.as_str())?))
+	}
+
+	/// Writes the session to disk, skipping the write when nothing has changed since the last save
+	pub(crate) fn save(&mut self, data: &SessionData) -> Result<(), String> {
+		let content = data.to_text();
+		if content == self.last_saved {
+			return Ok(());
+		}
+
+		let mut file = File::create(&self.filepath).map_err(|why| {
+			format!(
+				"Error opening session file, {}\nReason: {}",
+				self.filepath.display(),
+				why
+			)
+		})?;
+		file.write_all(content.as_bytes())
+			.map_err(|why| format!("Error writing to session file, {}", why))?;
+		self.last_saved = content;
+		Ok(())
+	}
+
+	pub(crate) fn remove(&self) -> Result<(), String> {
+		if !self.filepath.exists() {
+			return Ok(());
+		}
+		remove_file(&self.filepath).map_err(|why| {
+			format!(
+				"Error removing session file, {}\nReason: {}",
+				self.filepath.display(),
+				why
+			)
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::Session;
+	use crate::constants::SESSION_FILE_NAME;
+	use crate::git_interactive::GitInteractive;
+	use crate::list::action::Action;
+	use crate::list::line::Line;
+	use crate::session::data::SessionData;
+	use crate::temp_path::TempPath;
+	use std::fs;
+	use std::path::PathBuf;
+
+	/// Writes a todo file to a new directory, returning the directory and the path of the todo file
+	fn create_todo(name: &str, todo: &str) -> (TempPath, PathBuf) {
+		let directory = TempPath::new_directory(format!("session-{}", name).as_str());
+		let todo_filepath = directory.join("git-rebase-todo");
+		fs::write(&todo_filepath, todo).unwrap();
+		(directory, todo_filepath)
+	}
+
+	fn create_lines(lines: &[&str]) -> Vec<Line> {
+		lines.iter().map(|l| Line::new(l).unwrap()).collect()
+	}
+
+	#[test]
+	fn session_save_and_load() {
+		let (_directory, todo_filepath) = create_todo("save-and-load", "pick aaa c1\npick bbb c2\n");
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let mut session = Session::new(&todo_filepath, &SessionData::new(&original, &original, 1, 1));
+		assert_eq!(session.load().unwrap(), None);
+
+		let data = SessionData::new(&original, &create_lines(&["pick bbb c2", "drop aaa c1"]), 2, 1);
+		session.save(&data).unwrap();
+		assert_eq!(session.load().unwrap(), Some(data));
+
+		session.remove().unwrap();
+		assert_eq!(session.load().unwrap(), None);
+	}
+
+	#[test]
+	fn session_save_skips_unchanged() {
+		let (_directory, todo_filepath) = create_todo("save-skips-unchanged", "pick aaa c1\n");
+		let original = create_lines(&["pick aaa c1"]);
+		let mut session = Session::new(&todo_filepath, &SessionData::new(&original, &original, 1, 1));
+		session.save(&SessionData::new(&original, &original, 1, 1)).unwrap();
+		assert!(!todo_filepath.with_file_name(SESSION_FILE_NAME).exists());
+	}
+
+	#[test]
+	fn session_restore() {
+		let (_directory, todo_filepath) = create_todo("restore", "pick aaa c1\npick bbb c2\n");
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		let lines = create_lines(&["pick bbb c2", "fixup aaa c1"]);
+		fs::write(
+			todo_filepath.with_file_name(SESSION_FILE_NAME),
+			SessionData::new(&original, &lines, 2, 1).to_text(),
+		)
+		.unwrap();
+
+		let mut git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		assert!(git_interactive.has_recoverable_session());
+		git_interactive.restore_session();
+		assert!(!git_interactive.has_recoverable_session());
+		assert_eq!(git_interactive.get_lines(), &lines);
+		assert_eq!(*git_interactive.get_selected_line_index(), 2);
+		assert_eq!(*git_interactive.get_lines()[1].get_action(), Action::Fixup);
+	}
+
+	#[test]
+	fn session_restore_clamps_selected_line() {
+		let (_directory, todo_filepath) = create_todo("restore-clamps", "pick aaa c1\npick bbb c2\n");
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		fs::write(
+			todo_filepath.with_file_name(SESSION_FILE_NAME),
+			SessionData::new(&original, &create_lines(&["pick aaa c1"]), 5, 0).to_text(),
+		)
+		.unwrap();
+
+		let mut git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		git_interactive.restore_session();
+		assert_eq!(*git_interactive.get_selected_line_index(), 1);
+	}
+
+	#[test]
+	fn session_discard() {
+		let (_directory, todo_filepath) = create_todo("discard", "pick aaa c1\npick bbb c2\n");
+		let session_filepath = todo_filepath.with_file_name(SESSION_FILE_NAME);
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		fs::write(
+			&session_filepath,
+			SessionData::new(&original, &create_lines(&["drop aaa c1", "pick bbb c2"]), 1, 1).to_text(),
+		)
+		.unwrap();
+
+		let mut git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		git_interactive.discard_session().unwrap();
+		assert!(!git_interactive.has_recoverable_session());
+		assert!(!session_filepath.exists());
+		assert_eq!(git_interactive.get_lines(), &original);
+	}
+
+	#[test]
+	fn session_stale_is_not_recoverable() {
+		let (_directory, todo_filepath) = create_todo("stale", "pick aaa c1\npick ccc c3\n");
+		let original = create_lines(&["pick aaa c1", "pick bbb c2"]);
+		fs::write(
+			todo_filepath.with_file_name(SESSION_FILE_NAME),
+			SessionData::new(&original, &create_lines(&["pick bbb c2", "pick aaa c1"]), 1, 1).to_text(),
+		)
+		.unwrap();
+
+		let git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		assert!(!git_interactive.has_recoverable_session());
+		assert_eq!(git_interactive.get_lines(), &create_lines(&["pick aaa c1", "pick ccc c3"]));
+	}
+
+	#[test]
+	fn session_corrupt_is_not_recoverable() {
+		let (_directory, todo_filepath) = create_todo("corrupt", "pick aaa c1\n");
+		let session_filepath = todo_filepath.with_file_name(SESSION_FILE_NAME);
+		fs::write(&session_filepath, "# interactive-rebase-tool session\nselected one\n").unwrap();
+
+		let original = create_lines(&["pick aaa c1"]);
+		let session = Session::new(&todo_filepath, &SessionData::new(&original, &original, 1, 1));
+		assert_eq!(session.load().unwrap_err(), "Invalid session value for selected: one");
+
+		let git_interactive = GitInteractive::new_from_filepath(todo_filepath.to_str().unwrap(), "#").unwrap();
+		assert!(!git_interactive.has_recoverable_session());
+		assert_eq!(git_interactive.get_lines(), &original);
+	}
+}
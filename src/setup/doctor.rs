@@ -0,0 +1,194 @@
+use crate::config::Config;
+use crate::constants::VERSION;
+use crate::display::backend::Backend;
+use crate::display::curses::Curses;
+use crate::setup::open_effective_config;
+use crate::setup::utils::{find_program, get_command_program, is_tool_command};
+use std::env;
+
+struct Report {
+	lines: Vec<String>,
+	problems: usize,
+}
+
+impl Report {
+	fn new() -> Self {
+		Self {
+			lines: vec![],
+			problems: 0,
+		}
+	}
+
+	fn section(&mut self, title: &str) {
+		if !self.lines.is_empty() {
+			self.lines.push(String::new());
+		}
+		self.lines.push(String::from(title));
+	}
+
+	fn entry(&mut self, name: &str, value: &str) {
+		self.lines.push(format!("  {}: {}", name, value));
+	}
+
+	fn problem(&mut self, message: &str) {
+		self.problems += 1;
+		self.lines.push(format!("  Problem: {}", message));
+	}
+}
+
+/// Finds the sequence editor git will run and where it was set, following the same precedence as git
+fn get_sequence_editor<F>(config: Option<&git2::Config>, get_env: F) -> (String, &'static str)
+where F: Fn(&str) -> Option<String> {
+	let get_config = |name: &str| config.and_then(|c| c.get_string(name).ok());
+	if let Some(editor) = get_env("GIT_SEQUENCE_EDITOR") {
+		return (editor, "GIT_SEQUENCE_EDITOR");
+	}
+	if let Some(editor) = get_config("sequence.editor") {
+		return (editor, "sequence.editor");
+	}
+	if let Some(editor) = get_env("GIT_EDITOR") {
+		return (editor, "GIT_EDITOR");
+	}
+	if let Some(editor) = get_config("core.editor") {
+		return (editor, "core.editor");
+	}
+	if let Some(editor) = get_env("VISUAL") {
+		return (editor, "VISUAL");
+	}
+	if let Some(editor) = get_env("EDITOR") {
+		return (editor, "EDITOR");
+	}
+	(String::from("vi"), "default")
+}
+
+fn check_command(report: &mut Report, name: &str, command: &str) {
+	report.entry(name, command);
+	match get_command_program(command) {
+		Some(program) => {
+			match find_program(program.as_str(), env::var_os("PATH")) {
+				Some(path) => report.entry("Resolved", path.to_string_lossy().as_ref()),
+				None => report.problem(format!("{} was not found on the PATH", program).as_str()),
+			}
+		},
+		None => report.problem("the command is empty"),
+	}
+}
+
+fn check_git(report: &mut Report, config: Option<&git2::Config>) {
+	report.section("Git");
+	let (editor, source) = get_sequence_editor(config, |name| env::var(name).ok());
+	check_command(report, format!("Sequence editor ({})", source).as_str(), editor.as_str());
+	if !is_tool_command(editor.as_str()) {
+		report.problem("the sequence editor is not this tool, run the install subcommand to set it");
+	}
+}
+
+fn check_config(report: &mut Report, config: Option<&git2::Config>) {
+	report.section("Configuration");
+	if let Some(config) = config {
+		if let Ok(entries) = config.entries(Some("interactive-rebase-tool\\..*")) {
+			for entry in (&entries).flatten() {
+				report.entry(entry.name().unwrap_or(""), entry.value().unwrap_or(""));
+			}
+		}
+	}
+	match Config::new() {
+		Ok(config) => check_command(report, "External editor", config.editor.as_str()),
+		Err(message) => report.problem(message.as_str()),
+	}
+}
+
+fn check_terminal(report: &mut Report) {
+	report.section("Terminal");
+	match env::var("TERM") {
+		Ok(term) => report.entry("TERM", term.as_str()),
+		Err(_) => {
+			// curses exits the process when the terminal cannot be opened
+			if !cfg!(windows) {
+				report.problem("TERM is not set, the terminal was not checked");
+				return;
+			}
+		},
+	}
+	let curses = Curses::new();
+	let capabilities = curses.get_capabilities();
+	curses.endwin();
+	for (name, value) in capabilities {
+		report.entry(name, value.as_str());
+	}
+}
+
+/// Checks the installation, configuration and terminal, returning the report and if any problems were found
+pub(crate) fn doctor() -> (String, bool) {
+	let mut report = Report::new();
+	report.section("Version");
+	report.entry("interactive-rebase-tool", VERSION);
+
+	let config = open_effective_config();
+	check_git(&mut report, config.as_ref());
+	check_config(&mut report, config.as_ref());
+	check_terminal(&mut report);
+
+	if report.problems > 0 {
+		report.section(format!("Found {} problem(s)", report.problems).as_str());
+	}
+	(report.lines.join("\n"), report.problems > 0)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_sequence_editor, Report};
+	use crate::temp_path::TempPath;
+
+	#[test]
+	fn doctor_sequence_editor_precedence() {
+		let env = |name: &str| {
+			match name {
+				"GIT_EDITOR" => Some(String::from("nano")),
+				"EDITOR" => Some(String::from("vim")),
+				_ => None,
+			}
+		};
+		assert_eq!(get_sequence_editor(None, env), (String::from("nano"), "GIT_EDITOR"));
+	}
+
+	#[test]
+	fn doctor_sequence_editor_environment_override() {
+		let env = |name: &str| {
+			match name {
+				"GIT_SEQUENCE_EDITOR" => Some(String::from("cat")),
+				_ => None,
+			}
+		};
+		let mut config = git2::Config::new().unwrap();
+		let path = TempPath::new("doctor.gitconfig");
+		config
+			.add_file(path.get_path(), git2::ConfigLevel::Local, true)
+			.unwrap();
+		config.set_str("sequence.editor", "interactive-rebase-tool").unwrap();
+		assert_eq!(get_sequence_editor(Some(&config), env), (
+			String::from("cat"),
+			"GIT_SEQUENCE_EDITOR"
+		));
+		assert_eq!(get_sequence_editor(Some(&config), |_| None), (
+			String::from("interactive-rebase-tool"),
+			"sequence.editor"
+		));
+	}
+
+	#[test]
+	fn doctor_sequence_editor_default() {
+		assert_eq!(get_sequence_editor(None, |_| None), (String::from("vi"), "default"));
+	}
+
+	#[test]
+	fn doctor_report() {
+		let mut report = Report::new();
+		report.section("First");
+		report.entry("name", "value");
+		report.section("Second");
+		report.problem("broken");
+		assert_eq!(report.lines.join("\n"), "First\n  name: value\n\nSecond\n  Problem: broken");
+		assert_eq!(report.problems, 1);
+	}
+}
@@ -0,0 +1,235 @@
+pub(crate) mod doctor;
+mod utils;
+
+use crate::setup::utils::is_tool_command;
+use git2::{ConfigLevel, Repository};
+use std::env;
+use std::path::PathBuf;
+
+const SEQUENCE_EDITOR: &str = "sequence.editor";
+
+fn get_level_name(local: bool) -> &'static str {
+	if local {
+		"local"
+	}
+	else {
+		"global"
+	}
+}
+
+fn get_global_config_path() -> Result<PathBuf, String> {
+	if let Ok(path) = git2::Config::find_global() {
+		return Ok(path);
+	}
+	// the global config file may not exist yet, so fall back to where git would create it
+	env::var_os("HOME")
+		.or_else(|| env::var_os("USERPROFILE"))
+		.map(|home| PathBuf::from(home).join(".gitconfig"))
+		.ok_or_else(|| String::from("Error opening git config: could not find the home directory"))
+}
+
+fn open_config(local: bool) -> Result<git2::Config, String> {
+	if local {
+		Repository::open_from_env()
+			.and_then(|repository| repository.config())
+			.and_then(|config| config.open_level(ConfigLevel::Local))
+			.map_err(|e| format!("Error opening git config: {}", e))
+	}
+	else {
+		git2::Config::open(get_global_config_path()?.as_path()).map_err(|e| format!("Error opening git config: {}", e))
+	}
+}
+
+fn open_effective_config() -> Option<git2::Config> {
+	match Repository::open_from_env() {
+		Ok(repository) => repository.config().ok(),
+		Err(_) => git2::Config::open_default().ok(),
+	}
+}
+
+/// Gets the command for the running executable, quoted when the path contains whitespace
+pub(crate) fn get_tool_command() -> Result<String, String> {
+	let path = env::current_exe().map_err(|e| format!("Error finding the executable path: {}", e))?;
+	let path = path.to_string_lossy();
+	// git runs the editor through a shell, that expects forward slashes on Windows
+	let path = if cfg!(windows) {
+		path.replace('\\', "/")
+	}
+	else {
+		path.to_string()
+	};
+	if path.contains(char::is_whitespace) {
+		Ok(format!("'{}'", path))
+	}
+	else {
+		Ok(path)
+	}
+}
+
+fn get_string(config: &git2::Config, name: &str) -> Option<String> {
+	config.get_string(name).ok()
+}
+
+/// Finds settings that would take precedence over, or be confused with, the installed sequence editor
+fn get_install_warnings(
+	config: Option<&git2::Config>,
+	local: bool,
+	sequence_editor_env: Option<String>,
+) -> Vec<String>
+{
+	let mut warnings = vec![];
+	if let Some(editor) = sequence_editor_env {
+		warnings.push(format!(
+			"GIT_SEQUENCE_EDITOR is set to \"{}\" and overrides sequence.editor",
+			editor
+		));
+	}
+	if let Some(config) = config {
+		if !local {
+			if let Ok(entry) = config.get_entry(SEQUENCE_EDITOR) {
+				if entry.level() == ConfigLevel::Local {
+					warnings.push(format!(
+						"The local git config sets sequence.editor to \"{}\" and overrides the global config",
+						entry.value().unwrap_or("")
+					));
+				}
+			}
+		}
+		if let Some(editor) = get_string(config, "core.editor") {
+			warnings.push(format!(
+				"core.editor is set to \"{}\", it is still used for commit messages, but not the rebase todo",
+				editor
+			));
+		}
+	}
+	warnings
+}
+
+/// Sets the sequence editor, returning the value that was replaced
+fn set_sequence_editor(config: &mut git2::Config, command: &str) -> Result<Option<String>, String> {
+	let previous = get_string(config, SEQUENCE_EDITOR);
+	config
+		.set_str(SEQUENCE_EDITOR, command)
+		.map_err(|e| format!("Error writing git config: {}", e))?;
+	Ok(previous)
+}
+
+/// Removes the sequence editor, returning the value that was removed, other editors are left unchanged
+fn unset_sequence_editor(config: &mut git2::Config) -> Result<Option<String>, String> {
+	let editor = match get_string(config, SEQUENCE_EDITOR) {
+		Some(editor) => editor,
+		None => return Ok(None),
+	};
+	if !is_tool_command(editor.as_str()) {
+		return Err(format!(
+			"sequence.editor is set to \"{}\", which is not this tool, so it was left unchanged",
+			editor
+		));
+	}
+	config
+		.remove(SEQUENCE_EDITOR)
+		.map_err(|e| format!("Error writing git config: {}", e))?;
+	Ok(Some(editor))
+}
+
+/// Configures git to use the tool as the sequence editor, returning a report of the change
+pub(crate) fn install(local: bool) -> Result<String, String> {
+	let command = get_tool_command()?;
+	let mut config = open_config(local)?;
+	let previous = set_sequence_editor(&mut config, command.as_str())?;
+
+	let mut report = vec![format!(
+		"Set sequence.editor to \"{}\" in the {} git config",
+		command,
+		get_level_name(local)
+	)];
+	if let Some(previous) = previous.filter(|p| *p != command) {
+		report.push(format!("The previous value was \"{}\"", previous));
+	}
+	for warning in get_install_warnings(
+		open_effective_config().as_ref(),
+		local,
+		env::var("GIT_SEQUENCE_EDITOR").ok(),
+	) {
+		report.push(format!("Warning: {}", warning));
+	}
+	Ok(report.join("\n"))
+}
+
+/// Removes the tool as the sequence editor, returning a report of the change
+pub(crate) fn uninstall(local: bool) -> Result<String, String> {
+	let mut config = open_config(local)?;
+	match unset_sequence_editor(&mut config)? {
+		Some(editor) => {
+			Ok(format!(
+				"Removed sequence.editor \"{}\" from the {} git config",
+				editor,
+				get_level_name(local)
+			))
+		},
+		None => Ok(format!("sequence.editor is not set in the {} git config", get_level_name(local))),
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{get_install_warnings, set_sequence_editor, unset_sequence_editor};
+	use crate::temp_path::TempPath;
+	use std::fs;
+
+	fn create_config(name: &str) -> (git2::Config, TempPath) {
+		let path = TempPath::new(format!("{}.gitconfig", name).as_str());
+		fs::write(path.get_path(), "").unwrap();
+		(git2::Config::open(path.get_path()).unwrap(), path)
+	}
+
+	#[test]
+	fn setup_set_sequence_editor() {
+		let (mut config, path) = create_config("set");
+		assert_eq!(set_sequence_editor(&mut config, "interactive-rebase-tool").unwrap(), None);
+		assert_eq!(
+			set_sequence_editor(&mut config, "/usr/bin/interactive-rebase-tool").unwrap(),
+			Some(String::from("interactive-rebase-tool"))
+		);
+		assert_eq!(
+			fs::read_to_string(path.get_path()).unwrap(),
+			"[sequence]\n\teditor = /usr/bin/interactive-rebase-tool\n"
+		);
+	}
+
+	#[test]
+	fn setup_unset_sequence_editor() {
+		let (mut config, _path) = create_config("unset");
+		config.set_str("sequence.editor", "'/opt/my tools/interactive-rebase-tool'").unwrap();
+		assert_eq!(
+			unset_sequence_editor(&mut config).unwrap(),
+			Some(String::from("'/opt/my tools/interactive-rebase-tool'"))
+		);
+		assert_eq!(unset_sequence_editor(&mut config).unwrap(), None);
+	}
+
+	#[test]
+	fn setup_unset_sequence_editor_other_editor() {
+		let (mut config, _path) = create_config("unset-other");
+		config.set_str("sequence.editor", "vim").unwrap();
+		assert_eq!(
+			unset_sequence_editor(&mut config).unwrap_err(),
+			"sequence.editor is set to \"vim\", which is not this tool, so it was left unchanged"
+		);
+	}
+
+	#[test]
+	fn setup_install_warnings() {
+		let (mut config, _path) = create_config("warnings");
+		config.set_str("core.editor", "vim").unwrap();
+		assert_eq!(get_install_warnings(Some(&config), false, Some(String::from("nano"))), vec![
+			"GIT_SEQUENCE_EDITOR is set to \"nano\" and overrides sequence.editor",
+			"core.editor is set to \"vim\", it is still used for commit messages, but not the rebase todo",
+		]);
+	}
+
+	#[test]
+	fn setup_install_warnings_none() {
+		assert!(get_install_warnings(None, false, None).is_empty());
+	}
+}
@@ -0,0 +1,125 @@
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+const TOOL_NAME: &str = "interactive-rebase-tool";
+
+/// Splits a shell command into its program, the first word with any quotes removed, and the rest of the command
+fn split_command_program(command: &str) -> Option<(String, &str)> {
+	let command = command.trim_start();
+	let mut program = String::new();
+	let mut quote = None;
+	let mut end = command.len();
+	for (index, c) in command.char_indices() {
+		match (quote, c) {
+			(None, '\'') | (None, '"') => quote = Some(c),
+			(Some(q), c) if q == c => quote = None,
+			(None, c) if c.is_whitespace() => {
+				end = index;
+				break;
+			},
+			(_, c) => program.push(c),
+		}
+	}
+	if program.is_empty() {
+		None
+	}
+	else {
+		Some((program, &command[end..]))
+	}
+}
+
+/// Gets the program of a shell command, the first word with any quotes removed
+pub(super) fn get_command_program(command: &str) -> Option<String> {
+	split_command_program(command).map(|(program, _)| program)
+}
+
+/// Gets the name of a program without its directory or extension, either separator is used for Windows paths
+fn get_program_name(program: &str) -> &str {
+	let file_name = program.rsplit(&['/', '\\'][..]).next().unwrap_or(program);
+	Path::new(file_name).file_stem().and_then(|s| s.to_str()).unwrap_or(file_name)
+}
+
+/// Checks if a shell command runs this tool, directly or through a wrapper such as `winpty`
+pub(super) fn is_tool_command(command: &str) -> bool {
+	match split_command_program(command) {
+		Some((program, rest)) => {
+			match get_program_name(program.as_str()) {
+				TOOL_NAME => true,
+				"winpty" => is_tool_command(rest),
+				_ => false,
+			}
+		},
+		None => false,
+	}
+}
+
+/// Finds the path of a program, searching the given path variable when the program is not a path itself
+pub(super) fn find_program(program: &str, path_env: Option<OsString>) -> Option<PathBuf> {
+	let path = Path::new(program);
+	if path.components().count() > 1 {
+		return if path.is_file() { Some(path.to_path_buf()) } else { None };
+	}
+	let extensions: &[&str] = if cfg!(windows) { &["exe", "cmd", "bat"] } else { &[] };
+	for directory in std::env::split_paths(&path_env?) {
+		let candidate = directory.join(program);
+		if candidate.is_file() {
+			return Some(candidate);
+		}
+		for extension in extensions {
+			let candidate = directory.join(format!("{}.{}", program, extension));
+			if candidate.is_file() {
+				return Some(candidate);
+			}
+		}
+	}
+	None
+}
+
+#[cfg(test)]
+mod tests {
+	use super::{find_program, get_command_program, is_tool_command};
+	use crate::temp_path::TempPath;
+	use std::ffi::OsString;
+	use std::fs;
+
+	#[test]
+	fn setup_get_command_program() {
+		assert_eq!(get_command_program("vim -f"), Some(String::from("vim")));
+		assert_eq!(get_command_program("  code --wait"), Some(String::from("code")));
+		assert_eq!(
+			get_command_program("'/opt/my tools/editor' --wait"),
+			Some(String::from("/opt/my tools/editor"))
+		);
+		assert_eq!(
+			get_command_program("\"C:/Program Files/editor.exe\""),
+			Some(String::from("C:/Program Files/editor.exe"))
+		);
+		assert_eq!(get_command_program(""), None);
+	}
+
+	#[test]
+	fn setup_is_tool_command() {
+		assert!(is_tool_command("interactive-rebase-tool"));
+		assert!(is_tool_command("'/opt/my tools/interactive-rebase-tool'"));
+		assert!(is_tool_command("winpty /c/tools/interactive-rebase-tool.exe"));
+		assert!(!is_tool_command("vim"));
+		assert!(is_tool_command("\"C:\\tools\\interactive-rebase-tool.exe\""));
+		assert!(!is_tool_command("my-interactive-rebase-tool-wrapper.sh"));
+		assert!(!is_tool_command("vim /tmp/interactive-rebase-tool"));
+	}
+
+	#[test]
+	fn setup_find_program() {
+		let temp_directory = TempPath::new_directory("find-program");
+		let directory = temp_directory.get_path();
+		fs::write(directory.join("my-editor"), "").unwrap();
+		let path_env = Some(OsString::from(directory.as_os_str()));
+		assert_eq!(find_program("my-editor", path_env.clone()), Some(directory.join("my-editor")));
+		assert_eq!(find_program("missing-editor", path_env), None);
+		assert_eq!(
+			find_program(directory.join("my-editor").to_str().unwrap(), None),
+			Some(directory.join("my-editor"))
+		);
+		assert_eq!(find_program("my-editor", None), None);
+	}
+}
@@ -0,0 +1,15 @@
+/// Quotes a value for a POSIX shell, which is how git runs the sequence editor and exec commands
+pub(crate) fn shell_quote(value: &str) -> String {
+	format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+	use super::shell_quote;
+
+	#[test]
+	fn shell_quote_value() {
+		assert_eq!(shell_quote("/tmp/my todo"), "'/tmp/my todo'");
+		assert_eq!(shell_quote("/tmp/it's here"), "'/tmp/it'\\''s here'");
+	}
+}
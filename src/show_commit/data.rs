@@ -4,8 +4,8 @@ use crate::display::display_color::DisplayColor;
 use crate::show_commit::util::get_stat_item_segments;
 use crate::view::line_segment::LineSegment;
 use crate::view::view_line::ViewLine;
+use crate::view::utils::get_display_width;
 use std::cmp;
-use unicode_segmentation::UnicodeSegmentation;
 
 pub(super) struct Data {
 	height: usize,
@@ -84,8 +84,7 @@ impl Data {
 				};
 				self.lines
 					.push(ViewLine::new(vec![LineSegment::new(author_line.as_str())]));
-				self.line_lengths
-					.push(UnicodeSegmentation::graphemes(author_line.as_str(), true).count());
+				self.line_lengths.push(get_display_width(author_line.as_str()));
 			}
 
 			if let Some(c) = committer.to_string() {
@@ -97,16 +96,14 @@ impl Data {
 				};
 				self.lines
 					.push(ViewLine::new(vec![LineSegment::new(committer_line.as_str())]));
-				self.line_lengths
-					.push(UnicodeSegmentation::graphemes(committer_line.as_str(), true).count());
+				self.line_lengths.push(get_display_width(committer_line.as_str()));
 			}
 
 			match body {
 				Some(b) => {
 					for line in b.lines() {
 						self.lines.push(ViewLine::new(vec![LineSegment::new(line)]));
-						self.line_lengths
-							.push(UnicodeSegmentation::graphemes(line, true).count());
+						self.line_lengths.push(get_display_width(line));
 					}
 				},
 				None => {},
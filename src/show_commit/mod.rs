@@ -2,6 +2,7 @@ mod data;
 mod util;
 
 use crate::commit::Commit;
+use crate::config::Config;
 use crate::display::display_color::DisplayColor;
 use crate::git_interactive::GitInteractive;
 use crate::input::input_handler::{InputHandler, InputMode};
@@ -11,12 +12,17 @@ use crate::process::process_module::ProcessModule;
 use crate::process::process_result::{ProcessResult, ProcessResultBuilder};
 use crate::process::state::State;
 use crate::show_commit::data::Data;
+use crate::view::line_segment::LineSegment;
 use crate::view::scroll_position::ScrollPosition;
+use crate::view::view_line::ViewLine;
 use crate::view::View;
 
 pub(crate) struct ShowCommit {
 	commit: Option<Result<Commit, String>>,
 	data: Data,
+	pane_footer: String,
+	pane_footer_focused: String,
+	pane_hash: Option<String>,
 	scroll_position: ScrollPosition,
 }
 
@@ -24,6 +30,7 @@ impl ProcessModule for ShowCommit {
 	fn activate(&mut self, _state: State, git_interactive: &GitInteractive) {
 		self.scroll_position.reset();
 		self.commit = Some(git_interactive.load_commit_stats());
+		self.pane_hash = None;
 	}
 
 	fn deactivate(&mut self) {
@@ -55,8 +62,54 @@ impl ProcessModule for ShowCommit {
 	) -> HandleInputResult
 	{
 		let input = input_handler.get_input(InputMode::Default);
-		let (view_width, view_height) = view.get_view_size();
 		let mut result = HandleInputResultBuilder::new(input);
+		if !self.handle_scroll_input(input, view) {
+			result = result.state(State::List(false));
+		}
+		result.build()
+	}
+
+	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
+		let (_, window_height) = view.get_view_size();
+		let view_height = window_height - 2;
+
+		view.draw_title(false);
+
+		match &self.commit {
+			None => {
+				view.draw_error("Not commit data to show");
+				return;
+			},
+			Some(c) => c.as_ref().unwrap(), // safe unwrap
+		};
+
+		view.draw_view_lines(
+			self.data.get_lines(),
+			self.scroll_position.get_top_position(),
+			self.scroll_position.get_left_position(),
+			view_height,
+		);
+
+		view.set_color(DisplayColor::IndicatorColor, false);
+		view.draw_str("Any key to close");
+	}
+}
+
+impl ShowCommit {
+	pub(crate) fn new(config: &Config) -> Self {
+		Self {
+			commit: None,
+			data: Data::new(),
+			pane_footer: format!("{} to focus details", config.input_toggle_focus),
+			pane_footer_focused: format!("{} to return to the list", config.input_toggle_focus),
+			pane_hash: None,
+			scroll_position: ScrollPosition::new(3),
+		}
+	}
+
+	/// Scrolls the commit details, returning false when the input is not a scroll input
+	pub(crate) fn handle_scroll_input(&mut self, input: Input, view: &View) -> bool {
+		let (view_width, view_height) = view.get_view_size();
 		match input {
 			Input::MoveCursorLeft => {
 				self.scroll_position
@@ -90,46 +143,65 @@ impl ProcessModule for ShowCommit {
 					self.get_max_line_length(view_height),
 				)
 			},
-			_ => {
-				result = result.state(State::List(false));
-			},
+			_ => return false,
 		}
-		result.build()
+		true
 	}
 
-	fn render(&self, view: &View, _git_interactive: &GitInteractive) {
-		let (_, window_height) = view.get_view_size();
-		let view_height = window_height - 2;
-
-		view.draw_title(false);
-
-		match &self.commit {
-			None => {
-				view.draw_error("Not commit data to show");
-				return;
-			},
-			Some(c) => c.as_ref().unwrap(), // safe unwrap
-		};
-
-		view.draw_view_lines(
-			self.data.get_lines(),
-			self.scroll_position.get_top_position(),
-			self.scroll_position.get_left_position(),
-			view_height,
-		);
-
-		view.set_color(DisplayColor::IndicatorColor, false);
-		view.draw_str("Any key to close");
+	/// Updates the details pane for the selected line, the commit is only loaded when the selected hash changes
+	pub(crate) fn update_pane(&mut self, git_interactive: &GitInteractive, view: &View) {
+		if git_interactive.get_lines().is_empty() {
+			self.commit = None;
+			self.pane_hash = None;
+			return;
+		}
+		let hash = git_interactive.get_selected_line_hash();
+		if self.pane_hash.as_ref() != Some(hash) {
+			self.pane_hash = Some(hash.clone());
+			self.commit = Some(git_interactive.load_commit_stats());
+			self.scroll_position.reset();
+			self.data.reset();
+		}
+		if let Some(Ok(ref commit)) = self.commit {
+			let (view_width, view_height) = view.get_view_size();
+			self.data.update(commit, view_width, view_height);
+		}
 	}
-}
 
-impl ShowCommit {
-	pub(crate) fn new() -> Self {
-		Self {
-			commit: None,
-			data: Data::new(),
-			scroll_position: ScrollPosition::new(3),
+	/// Renders the commit details into a pane beside the list, errors are shown in the pane instead of a new state
+	pub(crate) fn render_pane(&self, view: &View, focused: bool) {
+		let (_, view_height) = view.get_view_size();
+		let view_height = view_height - 2;
+
+		view.draw_heading("Commit details", focused);
+		match self.commit {
+			Some(Ok(_)) => {
+				view.draw_view_lines(
+					self.data.get_lines(),
+					self.scroll_position.get_top_position(),
+					self.scroll_position.get_left_position(),
+					view_height,
+				);
+			},
+			Some(Err(ref message)) => {
+				view.draw_view_lines(
+					&[ViewLine::new(vec![LineSegment::new(message.as_str())])],
+					0,
+					0,
+					view_height,
+				);
+			},
+			None => view.draw_view_lines(&[], 0, 0, view_height),
 		}
+		view.draw_heading(
+			if focused {
+				self.pane_footer_focused.as_str()
+			}
+			else {
+				self.pane_footer.as_str()
+			},
+			focused,
+		);
 	}
 
 	fn get_commit_stats_length(&self) -> usize {
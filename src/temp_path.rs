@@ -0,0 +1,47 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// A file or directory in the temporary directory for a test, removed with its contents when dropped, so a failed
+/// assertion does not leave it behind
+pub(crate) struct TempPath {
+	path: PathBuf,
+}
+
+impl TempPath {
+	/// Creates a path unique to the test process and name, removing anything left at it by an earlier run
+	pub(crate) fn new(name: &str) -> Self {
+		let path = std::env::temp_dir().join(format!("interactive-rebase-tool-{}-{}", std::process::id(), name));
+		remove_path(&path);
+		Self { path }
+	}
+
+	/// Creates the path as an empty directory
+	pub(crate) fn new_directory(name: &str) -> Self {
+		let temp_path = Self::new(name);
+		fs::create_dir_all(&temp_path.path).unwrap();
+		temp_path
+	}
+
+	pub(crate) fn get_path(&self) -> &Path {
+		&self.path
+	}
+
+	pub(crate) fn join<P: AsRef<Path>>(&self, path: P) -> PathBuf {
+		self.path.join(path)
+	}
+}
+
+fn remove_path(path: &Path) {
+	if path.is_dir() {
+		let _ = fs::remove_dir_all(path);
+	}
+	else {
+		let _ = fs::remove_file(path);
+	}
+}
+
+impl Drop for TempPath {
+	fn drop(&mut self) {
+		remove_path(&self.path);
+	}
+}
@@ -1,4 +1,6 @@
 use crate::display::display_color::DisplayColor;
+use crate::view::utils::{get_display_width, get_grapheme_width};
+use std::cmp;
 use unicode_segmentation::UnicodeSegmentation;
 
 pub(super) struct SegmentPartial {
@@ -51,11 +53,18 @@ impl LineSegment {
 			color,
 			reverse,
 			dim,
-			length: UnicodeSegmentation::graphemes(text, true).count(),
+			length: get_display_width(text),
 			underline,
 		}
 	}
 
+	/// Removes the color from the segment and dims it
+	pub(crate) fn set_greyed(mut self) -> Self {
+		self.color = DisplayColor::Normal;
+		self.dim = true;
+		self
+	}
+
 	pub(super) fn get_color(&self) -> DisplayColor {
 		self.color
 	}
@@ -72,27 +81,40 @@ impl LineSegment {
 		self.reverse
 	}
 
-	pub(super) fn get_length(&self) -> usize {
+	pub(crate) fn get_length(&self) -> usize {
 		self.length
 	}
 
+	/// Gets the part of the segment from the left column that fits in the width, where the left and width are in
+	/// terminal columns
+	///
+	/// A wide character that is cut by either edge is replaced with spaces, so the following columns stay aligned.
 	pub(super) fn get_partial_segment(&self, left: usize, max_width: usize) -> SegmentPartial {
-		let segment_length = UnicodeSegmentation::graphemes(self.text.as_str(), true).count();
-
-		// segment is hidden to the left of the line/scroll
-		if segment_length <= left {
-			SegmentPartial::new(String::from(""), 0)
-		}
-		else if segment_length - left >= max_width {
-			let graphemes = UnicodeSegmentation::graphemes(self.text.as_str(), true);
-			let partial_line = graphemes.skip(left).take(max_width).collect::<String>();
-			SegmentPartial::new(partial_line, max_width)
-		}
-		else {
-			let graphemes = UnicodeSegmentation::graphemes(self.text.as_str(), true);
-			let partial_line = graphemes.skip(left).collect::<String>();
-			SegmentPartial::new(partial_line, segment_length - left)
+		let mut content = String::new();
+		let mut length = 0;
+		let mut end = 0;
+		for grapheme in UnicodeSegmentation::graphemes(self.text.as_str(), true) {
+			let start = end;
+			end += get_grapheme_width(grapheme);
+			// segment is hidden to the left of the line/scroll
+			if end < left || (end == left && start < end) {
+				continue;
+			}
+			let width = end - cmp::max(start, left);
+			if length + width > max_width {
+				content.push_str(" ".repeat(max_width - length).as_str());
+				length = max_width;
+				break;
+			}
+			if start < left {
+				content.push_str(" ".repeat(width).as_str());
+			}
+			else {
+				content.push_str(grapheme);
+			}
+			length += width;
 		}
+		SegmentPartial::new(content, length)
 	}
 }
 
@@ -187,6 +209,70 @@ mod tests {
 		assert_eq!(line_segment.is_reversed(), true);
 	}
 
+	#[test]
+	fn line_segment_case_new_east_asian() {
+		let line_segment = LineSegment::new("修复构建");
+
+		assert_eq!(line_segment.get_length(), 8);
+	}
+
+	#[test]
+	fn line_segment_case_new_emoji() {
+		let line_segment = LineSegment::new("🎉 release");
+
+		assert_eq!(line_segment.get_length(), 10);
+	}
+
+	#[test]
+	fn line_segment_case_get_partial_segment_east_asian_exact_fit() {
+		let line_segment = LineSegment::new("修复构建");
+
+		let partial = line_segment.get_partial_segment(2, 4);
+
+		assert_eq!(partial.get_content(), "复构");
+		assert_eq!(partial.get_length(), 4);
+	}
+
+	#[test]
+	fn line_segment_case_get_partial_segment_east_asian_cut_right() {
+		let line_segment = LineSegment::new("修复构建");
+
+		let partial = line_segment.get_partial_segment(0, 5);
+
+		assert_eq!(partial.get_content(), "修复 ");
+		assert_eq!(partial.get_length(), 5);
+	}
+
+	#[test]
+	fn line_segment_case_get_partial_segment_east_asian_cut_left() {
+		let line_segment = LineSegment::new("修复构建");
+
+		let partial = line_segment.get_partial_segment(1, 4);
+
+		assert_eq!(partial.get_content(), " 复 ");
+		assert_eq!(partial.get_length(), 4);
+	}
+
+	#[test]
+	fn line_segment_case_get_partial_segment_emoji_cut_right() {
+		let line_segment = LineSegment::new("ok🎉");
+
+		let partial = line_segment.get_partial_segment(0, 3);
+
+		assert_eq!(partial.get_content(), "ok ");
+		assert_eq!(partial.get_length(), 3);
+	}
+
+	#[test]
+	fn line_segment_case_get_partial_segment_combining() {
+		let line_segment = LineSegment::new("Cafe\u{301} au lait");
+
+		let partial = line_segment.get_partial_segment(3, 4);
+
+		assert_eq!(partial.get_content(), "e\u{301} au");
+		assert_eq!(partial.get_length(), 4);
+	}
+
 	#[test]
 	fn line_segment_case_get_partial_segment_full_segment_exact_fit() {
 		let line_segment = LineSegment::new("1234567890");
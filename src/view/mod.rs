@@ -2,6 +2,7 @@ pub(crate) mod line_segment;
 pub(crate) mod scroll_position;
 pub(super) mod utils;
 pub(crate) mod view_line;
+pub(crate) mod viewport;
 
 use crate::constants::{
 	MINIMUM_COMPACT_WINDOW_WIDTH,
@@ -14,18 +15,62 @@ use crate::constants::{
 };
 use crate::display::display_color::DisplayColor;
 use crate::display::Display;
-use crate::view::utils::get_scroll_position_index;
+use crate::view::line_segment::LineSegment;
+use crate::view::utils::{get_display_width, get_scroll_position_index};
 use crate::view::view_line::ViewLine;
+use crate::view::viewport::Viewport;
 use crate::Config;
+use std::cell::{Cell, RefCell};
 
 pub(crate) struct View<'v> {
 	config: &'v Config,
 	display: &'v Display<'v>,
+	title_status: RefCell<Option<String>>,
+	viewport: Cell<Option<Viewport>>,
+	viewport_row: Cell<usize>,
 }
 
 impl<'v> View<'v> {
 	pub(crate) fn new(display: &'v Display, config: &'v Config) -> Self {
-		Self { display, config }
+		Self {
+			display,
+			config,
+			title_status: RefCell::new(None),
+			viewport: Cell::new(None),
+			viewport_row: Cell::new(0),
+		}
+	}
+
+	/// Limits rendering to a region of the window, or to the full window when none
+	pub(crate) fn set_viewport(&self, viewport: Option<Viewport>) {
+		self.viewport.set(viewport);
+		self.viewport_row.set(0);
+		if let Some(v) = viewport {
+			self.display.move_cursor(v.x as i32, v.y as i32);
+		}
+	}
+
+	/// Converts a column and row of the window to a position in the viewport, or `None` if it is outside the viewport
+	pub(crate) fn get_viewport_position(&self, column: usize, row: usize) -> Option<(usize, usize)> {
+		match self.viewport.get() {
+			Some(v) if v.contains(column, row) => Some((column - v.x, row - v.y)),
+			Some(_) => None,
+			None => Some((column, row)),
+		}
+	}
+
+	/// Moves to the start of the next line of the viewport, lines of the full window instead wrap to the next line
+	fn end_line(&self) {
+		if let Some(v) = self.viewport.get() {
+			let row = self.viewport_row.get() + 1;
+			self.viewport_row.set(row);
+			self.display.move_cursor(v.x as i32, (v.y + row) as i32);
+		}
+	}
+
+	/// Sets a status that is shown after the title, when there is room for it
+	pub(crate) fn set_title_status(&self, status: Option<String>) {
+		self.title_status.replace(status);
 	}
 
 	pub(crate) fn draw_str(&self, s: &str) {
@@ -60,6 +105,9 @@ impl<'v> View<'v> {
 	}
 
 	pub(crate) fn get_view_size(&self) -> (usize, usize) {
+		if let Some(v) = self.viewport.get() {
+			return (v.width, v.height);
+		}
 		let (view_width, view_height) = self.display.get_window_size();
 		(view_width as usize, view_height as usize)
 	}
@@ -87,6 +135,7 @@ impl<'v> View<'v> {
 			}
 			self.display.color(DisplayColor::Normal, false);
 			self.display.set_style(false, false, false);
+			self.end_line();
 			index += 1;
 		}
 
@@ -96,8 +145,8 @@ impl<'v> View<'v> {
 	}
 
 	fn draw_view_line(&self, line: &ViewLine, left: usize, scrollbar: bool) {
-		let (window_width, _) = self.display.get_window_size();
-		let window_width = if scrollbar { window_width - 1 } else { window_width } as usize;
+		let (window_width, _) = self.get_view_size();
+		let window_width = if scrollbar { window_width - 1 } else { window_width };
 
 		let mut start = 0;
 		let mut left_start = 0;
@@ -135,17 +184,31 @@ impl<'v> View<'v> {
 	pub(crate) fn draw_title(&self, show_help: bool) {
 		self.display.color(DisplayColor::Normal, false);
 		self.display.set_style(false, true, false);
-		let (window_width, _) = self.display.get_window_size();
+		let (window_width, _) = self.get_view_size();
+		let window_width = window_width as i32;
 
 		let title_help_indicator_total_length = TITLE_HELP_INDICATOR_LENGTH + self.config.input_help.len() as i32;
 
-		if window_width >= TITLE_LENGTH {
-			self.display.draw_str(TITLE);
+		let title = match *self.title_status.borrow() {
+			Some(ref status) => format!("{} - {}", TITLE, status),
+			None => String::from(TITLE),
+		};
+		let title_length = get_display_width(title.as_str()) as i32;
+		// the status is dropped before the help indicator when there is not enough room
+		let (title, title_length) = if window_width > title_length + title_help_indicator_total_length {
+			(title.as_str(), title_length)
+		}
+		else {
+			(TITLE, TITLE_LENGTH)
+		};
+
+		if window_width >= title_length {
+			self.display.draw_str(title);
 			// only draw help if there is room
-			if window_width > TITLE_LENGTH + title_help_indicator_total_length {
-				if (window_width - TITLE_LENGTH - title_help_indicator_total_length) > 0 {
+			if window_width > title_length + title_help_indicator_total_length {
+				if (window_width - title_length - title_help_indicator_total_length) > 0 {
 					let padding =
-						" ".repeat((window_width - TITLE_LENGTH - title_help_indicator_total_length) as usize);
+						" ".repeat((window_width - title_length - title_help_indicator_total_length) as usize);
 					self.display.draw_str(padding.as_str());
 				}
 				if show_help {
@@ -157,8 +220,8 @@ impl<'v> View<'v> {
 					self.display.draw_str(padding.as_str());
 				}
 			}
-			else if (window_width - TITLE_LENGTH) > 0 {
-				let padding = " ".repeat((window_width - TITLE_LENGTH) as usize);
+			else if (window_width - title_length) > 0 {
+				let padding = " ".repeat((window_width - title_length) as usize);
 				self.display.draw_str(padding.as_str());
 			}
 		}
@@ -169,14 +232,42 @@ impl<'v> View<'v> {
 				self.display.draw_str(padding.as_str());
 			}
 		}
+		self.end_line();
+	}
+
+	/// Draws an underlined heading across the view, highlighted to show the view has focus
+	pub(crate) fn draw_heading(&self, heading: &str, highlight: bool) {
+		let (view_width, _) = self.get_view_size();
+		self.display.color(
+			if highlight {
+				DisplayColor::IndicatorColor
+			}
+			else {
+				DisplayColor::Normal
+			},
+			false,
+		);
+		self.display.set_style(!highlight, true, false);
+		let heading = LineSegment::new(heading).get_partial_segment(0, view_width);
+		self.display.draw_str(heading.get_content());
+		self.display.draw_str(" ".repeat(view_width - heading.get_length()).as_str());
+		self.end_line();
 	}
 
 	fn draw_vertical_spacer(&self, repeat: i32) {
 		self.display.color(DisplayColor::Normal, false);
 		self.display.set_style(false, false, false);
 		for _x in 0..repeat {
-			self.display
-				.draw_str(format!("{}\n", self.config.theme.character_vertical_spacing).as_str());
+			// a new line would clear the rest of the line, including any other viewport on the line
+			if self.viewport.get().is_some() {
+				self.display
+					.draw_str(self.config.theme.character_vertical_spacing.as_str());
+				self.end_line();
+			}
+			else {
+				self.display
+					.draw_str(format!("{}\n", self.config.theme.character_vertical_spacing).as_str());
+			}
 		}
 	}
 
@@ -186,10 +277,21 @@ impl<'v> View<'v> {
 		self.display.draw_str(&format!("\n{} ", message));
 	}
 
-	pub(crate) fn draw_confirm(&self, message: &str) {
-		self.draw_prompt(&format!(
+	fn get_confirm_message(&self, message: &str) -> String {
+		format!(
 			"{} ({}/{})? ",
 			message, self.config.input_confirm_yes, self.config.input_confirm_no
-		));
+		)
+	}
+
+	pub(crate) fn draw_confirm(&self, message: &str) {
+		self.draw_prompt(self.get_confirm_message(message).as_str());
+	}
+
+	/// Draws a confirm prompt on the current line, without a title
+	pub(crate) fn draw_confirm_prompt(&self, message: &str) {
+		self.display.color(DisplayColor::IndicatorColor, false);
+		self.display.set_style(false, false, false);
+		self.display.draw_str(self.get_confirm_message(message).as_str());
 	}
 }
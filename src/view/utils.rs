@@ -1,3 +1,22 @@
+use std::cmp;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+/// Gets the number of terminal columns a grapheme takes, emoji take two columns and combining characters none
+pub(crate) fn get_grapheme_width(grapheme: &str) -> usize {
+	// the emoji presentation selector draws the preceding character as a wide emoji
+	if grapheme.contains('\u{fe0f}') {
+		return 2;
+	}
+	// joined sequences, such as family emoji, are drawn as a single wide character
+	cmp::min(grapheme.width(), 2)
+}
+
+/// Gets the number of terminal columns the text takes
+pub(crate) fn get_display_width(text: &str) -> usize {
+	UnicodeSegmentation::graphemes(text, true).map(get_grapheme_width).sum()
+}
+
 pub(crate) fn get_scroll_position_index(position: usize, item_count: usize, height: usize) -> usize {
 	if position == 0 || item_count < height {
 		return 0;
@@ -23,7 +42,32 @@ pub(crate) fn get_scroll_position_index(position: usize, item_count: usize, heig
 
 #[cfg(test)]
 mod tests {
-	use crate::view::utils::get_scroll_position_index;
+	use crate::view::utils::{get_display_width, get_grapheme_width, get_scroll_position_index};
+
+	#[test]
+	fn get_display_width_ascii() {
+		assert_eq!(get_display_width("Fix the build"), 13);
+	}
+
+	#[test]
+	fn get_display_width_east_asian() {
+		assert_eq!(get_display_width("修复构建"), 8);
+		assert_eq!(get_display_width("ビルドを修正"), 12);
+		assert_eq!(get_display_width("빌드 수정"), 9);
+	}
+
+	#[test]
+	fn get_display_width_combining() {
+		assert_eq!(get_display_width("Cafe\u{301}"), 4);
+		assert_eq!(get_grapheme_width("e\u{301}"), 1);
+	}
+
+	#[test]
+	fn get_display_width_emoji() {
+		assert_eq!(get_display_width("🎉 release"), 10);
+		assert_eq!(get_grapheme_width("❤\u{fe0f}"), 2);
+		assert_eq!(get_grapheme_width("👨\u{200d}👩\u{200d}👧"), 2);
+	}
 
 	#[test]
 	fn get_scroll_position_case_top_position() {
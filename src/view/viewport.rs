@@ -0,0 +1,19 @@
+/// A region of the window, that a view renders into instead of the full window
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) struct Viewport {
+	pub(crate) x: usize,
+	pub(crate) y: usize,
+	pub(crate) width: usize,
+	pub(crate) height: usize,
+}
+
+impl Viewport {
+	pub(crate) fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+		Self { x, y, width, height }
+	}
+
+	/// Whether the column and row of the window are in the region
+	pub(crate) fn contains(&self, column: usize, row: usize) -> bool {
+		column >= self.x && column < self.x + self.width && row >= self.y && row < self.y + self.height
+	}
+}